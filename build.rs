@@ -1,30 +1,59 @@
-use build_script::cargo_rerun_if_changed;
-use std::path::PathBuf;
-use std::{env, path::Path};
+use build_script::{
+    cargo_rerun_if_changed, cargo_rerun_if_env_changed, cargo_rustc_env, cargo_warning,
+};
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
 
-fn main() {
-    let duckdb_root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
-        .join("duckdb")
-        .canonicalize()
-        .expect("duckdb source root");
+/// Works out the version of the DuckDB sources, the same way DuckDB's own CMake build does: the tag of the
+/// checked out commit, or the next patch version with a `-dev` suffix if the commit comes after the tag.
+///
+/// Can be overridden with the `DUCKDB_VERSION` environment variable, e.g. when the sources aren't a git checkout.
+#[cfg(feature = "buildtime_bindgen")]
+fn duckdb_version(duckdb_root: &Path) -> String {
+    use std::process::Command;
 
-    let header = "src/wrapper.hpp";
+    cargo_rerun_if_env_changed("DUCKDB_VERSION");
+    if let Ok(version) = env::var("DUCKDB_VERSION") {
+        return version;
+    }
 
-    #[cfg(feature = "statically_linked")]
-    {
-        use build_script::{cargo_rustc_link_lib, cargo_rustc_link_search};
-        cargo_rustc_link_lib("duckdb");
-        cargo_rustc_link_search(duckdb_root.join("build/debug/src"));
-        cargo_rustc_link_search(duckdb_root.join("build/release/src"));
+    let output = Command::new("git")
+        .args(["describe", "--tags", "--long"])
+        .current_dir(duckdb_root)
+        .output()
+        .expect("git describe, set DUCKDB_VERSION if the duckdb sources aren't a git checkout");
+    assert!(
+        output.status.success(),
+        "git describe failed, set DUCKDB_VERSION if the duckdb sources aren't a git checkout: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let description = String::from_utf8(output.stdout).expect("git describe output");
+
+    // v0.7.1-42-g1234abcd
+    let mut parts = description.trim().rsplitn(3, '-');
+    let _hash = parts.next();
+    let distance: u32 = parts
+        .next()
+        .and_then(|distance| distance.parse().ok())
+        .expect("commit distance");
+    let tag = parts.next().expect("tag");
+    if distance == 0 {
+        return tag.to_owned();
     }
 
-    // Tell cargo to invalidate the built crate whenever the wrapper changes
-    cargo_rerun_if_changed(header);
+    let (prefix, patch) = tag.rsplit_once('.').expect("version tag");
+    let patch: u32 = patch.parse().expect("patch version");
+    format!("{}.{}-dev{}", prefix, patch + 1, distance)
+}
 
+/// Generates the bindings for `header` into `$OUT_DIR/bindings.rs`
+#[cfg(feature = "buildtime_bindgen")]
+fn generate_bindings(header: &str, duckdb_include: &Path) {
     // The bindgen::Builder is the main entry point
     // to bindgen, and lets you build up options for
     // the resulting bindings.
-    let duckdb_include = duckdb_root.join("src/include");
     let bindings = bindgen::Builder::default()
         // The input header we would like to generate
         // bindings for.
@@ -41,6 +70,8 @@ fn main() {
         // .opaque_type("std::.*")
         .derive_debug(true)
         .derive_default(true)
+        // Throws, so it's declared `extern "C-unwind"` in src/entrypoint.rs instead
+        .blocklist_function("duckdb_extension_load")
         // Tell cargo to invalidate the built crate whenever any of the
         // included header files changed.
         .parse_callbacks(Box::new(bindgen::CargoCallbacks))
@@ -50,17 +81,134 @@ fn main() {
         .expect("Unable to generate bindings");
 
     // Write the bindings to the $OUT_DIR/bindings.rs file.
-    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let out_path = std::path::PathBuf::from(env::var("OUT_DIR").unwrap());
     bindings
         .write_to_file(out_path.join("bindings.rs"))
         .expect("Couldn't write bindings!");
+}
+
+/// Drives DuckDB's `scripts/amalgamation.py` from the root of the sources, writing `duckdb.cpp` and `duckdb.hpp`
+/// (and the script's temporary files) into the directory given as the first argument instead of the source tree.
+/// The script's own `--source`/`--header` options can't be used, as they turn absolute paths into relative ones.
+#[cfg(feature = "bundled")]
+const AMALGAMATE: &str = r#"
+import os, sys
+sys.dont_write_bytecode = True
+sys.path.insert(0, 'scripts')
+import amalgamation
+out = sys.argv[1]
+amalgamation.temp_header = os.path.join(out, 'duckdb.hpp.tmp')
+amalgamation.temp_source = os.path.join(out, 'duckdb.cpp.tmp')
+amalgamation.generate_amalgamation(os.path.join(out, 'duckdb.cpp'), os.path.join(out, 'duckdb.hpp'))
+"#;
+
+/// Compiles DuckDB itself from the amalgamation, so tests and binaries don't need a separately built libduckdb.
+///
+/// The amalgamation is read from `DUCKDB_AMALGAMATION_DIR` if set, otherwise it's generated into `$OUT_DIR` by
+/// DuckDB's own script, leaving the `duckdb` submodule untouched.
+#[cfg(feature = "bundled")]
+fn compile_bundled(duckdb_root: &Path) {
+    use std::process::Command;
 
-    cc::Build::new()
-        .include(duckdb_include)
-        .flag_if_supported("-Wno-unused-parameter")
-        .flag_if_supported("-Wno-redundant-move")
-        .flag_if_supported("-std=c++14")
+    cargo_rerun_if_env_changed("DUCKDB_AMALGAMATION_DIR");
+    let amalgamation = match env::var("DUCKDB_AMALGAMATION_DIR") {
+        Ok(dir) => Path::new(&dir).to_owned(),
+        Err(_) => {
+            let dir = PathBuf::from(env::var("OUT_DIR").unwrap()).join("amalgamation");
+            if !dir.join("duckdb.cpp").exists() {
+                std::fs::create_dir_all(&dir).expect("amalgamation output directory");
+                let status = Command::new("python3")
+                    .arg("-c")
+                    .arg(AMALGAMATE)
+                    .arg(&dir)
+                    .current_dir(duckdb_root)
+                    .status()
+                    .expect("python3 scripts/amalgamation.py, set DUCKDB_AMALGAMATION_DIR to use a prebuilt amalgamation");
+                assert!(status.success(), "generating the amalgamation failed");
+            }
+            dir
+        }
+    };
+    cargo_rerun_if_changed(&*amalgamation.join("duckdb.cpp").to_string_lossy());
+
+    let mut build = cc::Build::new();
+    build
+        .include(&amalgamation)
+        .file(amalgamation.join("duckdb.cpp"))
         .cpp(true)
-        .file("src/wrapper.cpp")
-        .compile("duckdb_extension_framework");
+        .flag_if_supported("-std=c++11")
+        .flag_if_supported("/bigobj")
+        // The amalgamation is third party code, its warnings aren't actionable here
+        .warnings(false)
+        .flag_if_supported("-w");
+    if env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("windows") {
+        build.define("DUCKDB_BUILD_LIBRARY", None);
+        build_script::cargo_rustc_link_lib("rstrtmgr");
+    }
+    build.compile("duckdb");
+}
+
+fn main() {
+    // Only needed to generate the bindings or the amalgamation, or for DuckDB's headers when `DUCKDB_INCLUDE_DIR`
+    // isn't set
+    let duckdb_root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("duckdb");
+
+    let header = "src/wrapper.hpp";
+
+    // Checked against the version of DuckDB an extension is loaded into, see `VersionPolicy`
+    #[cfg(feature = "buildtime_bindgen")]
+    let headers_version = duckdb_version(&duckdb_root);
+    #[cfg(all(not(feature = "buildtime_bindgen"), feature = "duckdb-v0_7"))]
+    let headers_version = "v0.7.1".to_owned();
+    #[cfg(not(any(feature = "buildtime_bindgen", feature = "duckdb-v0_7")))]
+    let headers_version = String::new(); // `duckly` reports the missing feature
+    cargo_rustc_env("DUCKDB_HEADERS_VERSION", &headers_version);
+
+    #[cfg(all(feature = "statically_linked", not(feature = "bundled")))]
+    {
+        use build_script::{cargo_rustc_link_lib, cargo_rustc_link_search};
+        cargo_rustc_link_lib("duckdb");
+        cargo_rustc_link_search(duckdb_root.join("build/debug/src"));
+        cargo_rustc_link_search(duckdb_root.join("build/release/src"));
+    }
+
+    // Tell cargo to invalidate the built crate whenever the wrapper changes
+    cargo_rerun_if_changed(header);
+
+    cargo_rerun_if_env_changed("DUCKDB_INCLUDE_DIR");
+    let duckdb_include = env::var("DUCKDB_INCLUDE_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| duckdb_root.join("src/include"));
+    #[cfg(feature = "buildtime_bindgen")]
+    generate_bindings(header, &duckdb_include);
+
+    // The wrapper uses DuckDB's C++ API, including internal catalog and storage headers that the amalgamated
+    // `duckdb.hpp` doesn't contain, so it needs the full `src/include` of the exact release the bindings are for.
+    // Without the wrapper an extension won't link or load, so missing headers fail the build, except on docs.rs or
+    // with `DUCKDB_SKIP_WRAPPER` set, where only type checking (`cargo check`, `cargo clippy`, `cargo doc`) is needed.
+    cargo_rerun_if_env_changed("DUCKDB_SKIP_WRAPPER");
+    if duckdb_include.join("duckdb.hpp").exists() {
+        cc::Build::new()
+            .include(duckdb_include)
+            .flag_if_supported("-Wno-unused-parameter")
+            .flag_if_supported("-Wno-redundant-move")
+            .flag_if_supported("-std=c++14")
+            .cpp(true)
+            .file("src/wrapper.cpp")
+            .compile("duckdb_extension_framework");
+    } else {
+        let message = format!(
+            "DuckDB headers not found in {}, check out the duckdb submodule at {} or point DUCKDB_INCLUDE_DIR at the src/include of its source tree",
+            duckdb_include.display(),
+            headers_version,
+        );
+        if env::var_os("DOCS_RS").is_none() && env::var_os("DUCKDB_SKIP_WRAPPER").is_none() {
+            panic!("{}", message);
+        }
+        cargo_warning(&format!("{}; src/wrapper.cpp wasn't compiled", message));
+    }
+
+    // Compiled after the wrapper, so the linker sees the wrapper's references to DuckDB before DuckDB itself
+    #[cfg(feature = "bundled")]
+    compile_bundled(&duckdb_root);
 }
@@ -0,0 +1,228 @@
+//! Procedural macros for [duckdb-extension-framework](https://docs.rs/duckdb-extension-framework)
+
+use proc_macro::TokenStream;
+use proc_macro2::Literal;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Fields, Ident, ItemFn, LitStr};
+
+/// Generates the entry points DuckDB looks up when loading an extension named `name`
+///
+/// ```ignore
+/// #[duckdb_extension(name = "delta")]
+/// fn init(db: &Database) -> Result<(), Box<dyn Error>> {
+///     db.connect()?.register_table_function(build_table_function_def())?;
+///     Ok(())
+/// }
+/// ```
+///
+/// This emits `delta_init`, which checks the version of DuckDB loading the extension, calls the annotated function
+/// and turns an incompatible version, an error or a panic into an error reported by `LOAD`, and `delta_version`,
+/// which returns the version of DuckDB loading the extension.
+///
+/// By default the extension only loads into the exact DuckDB version its bindings were generated from, which can be
+/// relaxed with `version_policy`:
+///
+/// ```ignore
+/// #[duckdb_extension(name = "delta", version_policy = VersionPolicy::MinorCompatible)]
+/// ```
+#[proc_macro_attribute]
+pub fn duckdb_extension(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut name: Option<LitStr> = None;
+    let mut version_policy: Option<Expr> = None;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("name") {
+            name = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("version_policy") {
+            version_policy = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("unsupported duckdb_extension property"))
+        }
+    });
+    parse_macro_input!(args with parser);
+
+    let function = parse_macro_input!(input as ItemFn);
+
+    let name = match name {
+        Some(name) => name,
+        None => {
+            return syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "expected `#[duckdb_extension(name = \"...\")]`",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+    if syn::parse_str::<Ident>(&name.value()).is_err() {
+        return syn::Error::new(name.span(), "the extension name must be a valid identifier")
+            .to_compile_error()
+            .into();
+    }
+    if function.sig.inputs.len() != 1 {
+        return syn::Error::new_spanned(
+            &function.sig,
+            "the extension's init function must take a single `&Database`",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let version_policy = match version_policy {
+        Some(version_policy) => quote!(#version_policy),
+        None => quote!(::duckdb_extension_framework::version::VersionPolicy::Exact),
+    };
+
+    let init = &function.sig.ident;
+    let init_symbol = format_ident!("{}_init", name.value());
+    let version_symbol = format_ident!("{}_version", name.value());
+
+    quote! {
+        #function
+
+        /// Called by DuckDB when the extension is loaded
+        ///
+        /// # Safety
+        /// Must only be called by DuckDB, with a pointer to the database the extension is loaded into. A failed
+        /// load unwinds through this function as a C++ exception.
+        #[no_mangle]
+        pub unsafe extern "C-unwind" fn #init_symbol(db: *mut ::std::ffi::c_void) {
+            ::duckdb_extension_framework::entrypoint::init(db, #version_policy, #init)
+        }
+
+        /// Called by DuckDB to check that the extension was built for the version that is loading it, returning
+        /// the version of the headers it was built against
+        #[no_mangle]
+        pub extern "C" fn #version_symbol() -> *const ::std::ffi::c_char {
+            ::duckdb_extension_framework::entrypoint::version()
+        }
+    }
+    .into()
+}
+
+/// Implements `DuckRow` for a struct with named fields, making each field a column, and `DuckValue`, so that it can
+/// be nested in another row as a `STRUCT`
+///
+/// ```ignore
+/// #[derive(DuckRow)]
+/// struct Commit {
+///     hash: String,
+///     parents: Vec<String>,
+///     message: Option<String>,
+/// }
+/// ```
+///
+/// The columns are named after the fields, and take their types from the fields' `DuckValue` implementations.
+#[proc_macro_derive(DuckRow)]
+pub fn derive_duck_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return syn::Error::new_spanned(
+                    &input.ident,
+                    "DuckRow can only be derived for structs with named fields",
+                )
+                .to_compile_error()
+                .into()
+            }
+        },
+        _ => {
+            return syn::Error::new_spanned(&input.ident, "DuckRow can only be derived for structs")
+                .to_compile_error()
+                .into()
+        }
+    };
+
+    let names: Vec<_> = fields
+        .iter()
+        .map(|field| field.ident.as_ref().expect("named field"))
+        .collect();
+    let columns: Vec<_> = names
+        .iter()
+        .map(|name| LitStr::new(&name.unraw().to_string(), name.span()))
+        .collect();
+    let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let indices: Vec<_> = (0..fields.len()).map(Literal::usize_unsuffixed).collect();
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let framework = quote!(::duckdb_extension_framework);
+    let result = quote!(::std::result::Result<Self, #framework::Error>);
+
+    quote! {
+        impl #impl_generics #framework::rows::DuckRow for #ident #ty_generics #where_clause {
+            fn columns() -> ::std::result::Result<
+                ::std::vec::Vec<(&'static str, #framework::LogicalType)>,
+                #framework::Error,
+            > {
+                ::std::result::Result::Ok(::std::vec![
+                    #((#columns, <#types as #framework::rows::DuckValue>::logical_type()?),)*
+                ])
+            }
+
+            unsafe fn write_column(
+                rows: &[Self],
+                column: usize,
+                vector: &#framework::Vector<()>,
+            ) -> ::std::result::Result<(), #framework::Error> {
+                match column {
+                    #(#indices => {
+                        for (row, value) in rows.iter().enumerate() {
+                            #framework::rows::DuckValue::write(&value.#names, vector, row as _)?;
+                        }
+                    })*
+                    _ => {}
+                }
+                ::std::result::Result::Ok(())
+            }
+
+            unsafe fn read_row(
+                vectors: &[#framework::Vector<()>],
+                row: #framework::duckly::idx_t,
+            ) -> #result {
+                ::std::result::Result::Ok(Self {
+                    #(#names: <#types as #framework::rows::DuckValue>::read(&vectors[#indices], row)?,)*
+                })
+            }
+        }
+
+        impl #impl_generics #framework::rows::DuckValue for #ident #ty_generics #where_clause {
+            fn logical_type() -> ::std::result::Result<#framework::LogicalType, #framework::Error> {
+                #framework::LogicalType::new_struct_type(
+                    <Self as #framework::rows::DuckRow>::columns()?,
+                )
+            }
+
+            unsafe fn write(
+                &self,
+                vector: &#framework::Vector<()>,
+                row: #framework::duckly::idx_t,
+            ) -> ::std::result::Result<(), #framework::Error> {
+                #(#framework::rows::DuckValue::write(&self.#names, &vector.struct_child(#indices), row)?;)*
+                ::std::result::Result::Ok(())
+            }
+
+            unsafe fn write_null(vector: &#framework::Vector<()>, row: #framework::duckly::idx_t) {
+                // DuckDB expects the fields of a NULL struct to be NULL too
+                vector.ensure_validity_writable();
+                vector.get_validity().set_row_invalid(row);
+                #(<#types as #framework::rows::DuckValue>::write_null(&vector.struct_child(#indices), row);)*
+            }
+
+            unsafe fn read_valid(
+                vector: &#framework::Vector<()>,
+                row: #framework::duckly::idx_t,
+            ) -> #result {
+                ::std::result::Result::Ok(Self {
+                    #(#names: <#types as #framework::rows::DuckValue>::read(&vector.struct_child(#indices), row)?,)*
+                })
+            }
+        }
+    }
+    .into()
+}
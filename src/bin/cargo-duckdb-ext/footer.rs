@@ -0,0 +1,241 @@
+//! The metadata footer DuckDB expects at the end of a `.duckdb_extension` file
+//!
+//! The footer is a WebAssembly custom section named `duckdb_signature`, so that WebAssembly extensions remain valid
+//! modules. Its 512 byte payload consists of 8 metadata fields of 32 bytes each, stored in reverse order, followed
+//! by a 256 byte signature.
+
+use std::error::Error;
+use std::fmt;
+
+/// Identifies the version of the footer layout
+pub const MAGIC_VALUE: &str = "4";
+
+const FIELD_SIZE: usize = 32;
+const FIELD_COUNT: usize = 8;
+const SIGNATURE_SIZE: usize = 256;
+const PAYLOAD_SIZE: usize = FIELD_SIZE * FIELD_COUNT + SIGNATURE_SIZE;
+
+/// Custom section id, section size (531) as LEB128, name length, name and payload size (512) as LEB128
+const HEADER: &[u8] = b"\x00\x93\x04\x10duckdb_signature\x80\x04";
+
+/// The total number of bytes appended to the shared library
+pub const FOOTER_SIZE: usize = HEADER.len() + PAYLOAD_SIZE;
+
+/// The metadata stored in the footer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metadata {
+    /// The platform the extension was built for, e.g. `linux_amd64`
+    pub platform: String,
+    /// The DuckDB version the extension was built for, e.g. `v0.7.1`
+    pub duckdb_version: String,
+    /// The version of the extension itself
+    pub extension_version: String,
+}
+
+/// A footer that could not be parsed or does not match expectations
+#[derive(Debug, PartialEq, Eq)]
+pub struct FooterError(pub String);
+
+impl fmt::Display for FooterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for FooterError {}
+
+fn encode_field(value: &str) -> Result<[u8; FIELD_SIZE], FooterError> {
+    if value.len() > FIELD_SIZE {
+        return Err(FooterError(format!(
+            "metadata field \"{}\" is longer than {} bytes",
+            value, FIELD_SIZE
+        )));
+    }
+    if value.contains('\0') {
+        return Err(FooterError(format!(
+            "metadata field \"{}\" contains a NUL byte",
+            value.escape_debug()
+        )));
+    }
+    let mut field = [0u8; FIELD_SIZE];
+    field[..value.len()].copy_from_slice(value.as_bytes());
+    Ok(field)
+}
+
+fn decode_field(field: &[u8]) -> Result<String, FooterError> {
+    let end = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+    String::from_utf8(field[..end].to_vec())
+        .map_err(|_| FooterError("metadata field is not valid UTF-8".to_owned()))
+}
+
+impl Metadata {
+    /// Builds the footer to append to the shared library, with an empty signature.
+    pub fn to_footer(&self) -> Result<Vec<u8>, FooterError> {
+        let fields = [
+            MAGIC_VALUE,
+            &self.platform,
+            &self.duckdb_version,
+            &self.extension_version,
+            "",
+            "",
+            "",
+            "",
+        ];
+
+        let mut footer = Vec::with_capacity(FOOTER_SIZE);
+        footer.extend_from_slice(HEADER);
+        for field in fields.iter().rev() {
+            footer.extend_from_slice(&encode_field(field)?);
+        }
+        footer.resize(FOOTER_SIZE, 0);
+        Ok(footer)
+    }
+
+    /// Parses the footer at the end of a `.duckdb_extension` file.
+    pub fn from_file_contents(contents: &[u8]) -> Result<Self, FooterError> {
+        if contents.len() < FOOTER_SIZE {
+            return Err(FooterError(format!(
+                "file is {} bytes, too short to contain a {} byte footer",
+                contents.len(),
+                FOOTER_SIZE
+            )));
+        }
+        let footer = &contents[contents.len() - FOOTER_SIZE..];
+        if &footer[..HEADER.len()] != HEADER {
+            return Err(FooterError(
+                "file does not end with a duckdb_signature section".to_owned(),
+            ));
+        }
+
+        let payload = &footer[HEADER.len()..];
+        let mut fields = payload[..FIELD_SIZE * FIELD_COUNT]
+            .chunks(FIELD_SIZE)
+            .rev()
+            .map(decode_field)
+            .collect::<Result<Vec<_>, _>>()?;
+        if fields[0] != MAGIC_VALUE {
+            return Err(FooterError(format!(
+                "unsupported footer version \"{}\", expected \"{}\"",
+                fields[0], MAGIC_VALUE
+            )));
+        }
+
+        let metadata = Metadata {
+            extension_version: fields.remove(3),
+            duckdb_version: fields.remove(2),
+            platform: fields.remove(1),
+        };
+        if metadata.platform.is_empty() {
+            return Err(FooterError("footer does not specify a platform".to_owned()));
+        }
+        if metadata.duckdb_version.is_empty() {
+            return Err(FooterError(
+                "footer does not specify a DuckDB version".to_owned(),
+            ));
+        }
+        Ok(metadata)
+    }
+}
+
+/// Returns DuckDB's name for the platform this tool is running on, if DuckDB supports it.
+pub fn host_platform() -> Option<&'static str> {
+    use std::env::consts::{ARCH, OS};
+
+    Some(match (OS, ARCH) {
+        ("linux", "x86_64") => "linux_amd64",
+        ("linux", "aarch64") => "linux_arm64",
+        ("macos", "x86_64") => "osx_amd64",
+        ("macos", "aarch64") => "osx_arm64",
+        ("windows", "x86_64") => "windows_amd64",
+        ("windows", "aarch64") => "windows_arm64",
+        _ => return None,
+    })
+}
+
+/// Returns DuckDB's name for the platform of a target triple, such as `aarch64-apple-darwin`, if DuckDB supports it.
+pub fn target_platform(target: &str) -> Option<&'static str> {
+    let mut parts = target.split('-');
+    let arch = parts.next()?;
+    let os = parts.find(|part| ["linux", "darwin", "windows"].contains(part))?;
+
+    Some(match (os, arch) {
+        ("linux", "x86_64") => "linux_amd64",
+        ("linux", "aarch64") => "linux_arm64",
+        ("darwin", "x86_64") => "osx_amd64",
+        ("darwin", "aarch64") => "osx_arm64",
+        ("windows", "x86_64") => "windows_amd64",
+        ("windows", "aarch64") => "windows_arm64",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{target_platform, FooterError, Metadata, FOOTER_SIZE};
+
+    fn metadata() -> Metadata {
+        Metadata {
+            platform: "linux_amd64".to_owned(),
+            duckdb_version: "v0.7.1".to_owned(),
+            extension_version: "0.7.0".to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<(), FooterError> {
+        let mut contents = b"\x7fELF library contents".to_vec();
+        contents.extend(metadata().to_footer()?);
+
+        assert_eq!(Metadata::from_file_contents(&contents)?, metadata());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_layout() -> Result<(), FooterError> {
+        let footer = metadata().to_footer()?;
+
+        assert_eq!(footer.len(), FOOTER_SIZE);
+        assert_eq!(footer.len(), 534);
+        assert_eq!(&footer[..4], b"\x00\x93\x04\x10");
+        assert_eq!(&footer[4..20], b"duckdb_signature");
+        assert_eq!(&footer[20..22], b"\x80\x04");
+        // fields are stored in reverse, so the magic value is the last of the 8
+        assert_eq!(footer[22 + 7 * 32], b'4');
+        assert_eq!(&footer[22 + 6 * 32..22 + 6 * 32 + 11], b"linux_amd64");
+        assert!(footer[22 + 256..].iter().all(|&b| b == 0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(Metadata::from_file_contents(b"too short").is_err());
+        assert!(Metadata::from_file_contents(&[0u8; 1024]).is_err());
+
+        let mut footer = metadata().to_footer().unwrap();
+        footer[22 + 7 * 32] = b'3';
+        let error = Metadata::from_file_contents(&footer).unwrap_err();
+        assert!(error.0.contains("unsupported footer version"), "{}", error);
+
+        let long = Metadata {
+            platform: "x".repeat(33),
+            ..metadata()
+        };
+        assert!(long.to_footer().is_err());
+    }
+
+    #[test]
+    fn test_target_platform() {
+        assert_eq!(
+            target_platform("x86_64-unknown-linux-gnu"),
+            Some("linux_amd64")
+        );
+        assert_eq!(target_platform("aarch64-apple-darwin"), Some("osx_arm64"));
+        assert_eq!(
+            target_platform("x86_64-pc-windows-msvc"),
+            Some("windows_amd64")
+        );
+        assert_eq!(target_platform("wasm32-unknown-unknown"), None);
+    }
+}
@@ -0,0 +1,325 @@
+//! `cargo duckdb-ext`: turns the shared library of an extension into a `.duckdb_extension` that `LOAD` accepts
+//!
+//! ```text
+//! cargo duckdb-ext package [--duckdb-version v0.7.1] [--release] [--target aarch64-apple-darwin] [--platform osx_arm64] [--manifest-path Cargo.toml] [--output path]
+//! cargo duckdb-ext verify <file> [--platform linux_amd64] [--duckdb-version v0.7.1]
+//! ```
+//!
+//! `package` appends the metadata footer to the library built by `cargo build`, using the crate's version as the
+//! extension's version and, unless given, the version of the DuckDB headers this tool was built with. The library is
+//! looked for where cargo put it: in its target directory, which follows `CARGO_TARGET_DIR` and `build.target-dir`,
+//! under the `--target` triple, or `CARGO_BUILD_TARGET`, when cross compiling. The platform defaults to the target's.
+//! `verify` parses the footer of an existing file and checks it against the given values.
+//!
+//! Only DuckDB 0.10.0 and later read the footer. Older versions, including the 0.7.1 the checked in bindings are
+//! for, load the library as is and check the version its `<name>_version` function returns instead, so packaging is
+//! only needed when targeting a newer DuckDB.
+
+mod footer;
+
+use crate::footer::{host_platform, target_platform, Metadata};
+use serde_json::Value;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::{exit, Command};
+use std::{env, fs};
+
+/// The version of DuckDB the framework was built against
+const HEADERS_VERSION: &str = env!("DUCKDB_HEADERS_VERSION");
+
+const USAGE: &str = "\
+Usage:
+    cargo duckdb-ext package [--duckdb-version <version>] [--release] [--target <triple>] [--platform <platform>] [--manifest-path <path>] [--output <path>]
+    cargo duckdb-ext verify <file> [--platform <platform>] [--duckdb-version <version>]";
+
+/// Command line arguments, split into positional arguments, flags and options with values
+#[derive(Debug, Default)]
+struct Args {
+    positional: Vec<String>,
+    release: bool,
+    target: Option<String>,
+    platform: Option<String>,
+    duckdb_version: Option<String>,
+    manifest_path: Option<PathBuf>,
+    output: Option<PathBuf>,
+}
+
+impl Args {
+    /// Parses the arguments of a command, which accepts only the `options` given and up to `max_positional`
+    /// positional arguments.
+    fn parse(
+        mut args: impl Iterator<Item = String>,
+        options: &[&str],
+        max_positional: usize,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut parsed = Args::default();
+        while let Some(arg) = args.next() {
+            if arg.starts_with("--") && !options.contains(&arg.as_str()) {
+                Err(format!("unknown option {}", arg))?;
+            }
+            let mut value = |name: &str| args.next().ok_or(format!("{} requires a value", name));
+            match arg.as_str() {
+                "--release" => parsed.release = true,
+                "--target" => parsed.target = Some(value("--target")?),
+                "--platform" => parsed.platform = Some(value("--platform")?),
+                "--duckdb-version" => parsed.duckdb_version = Some(value("--duckdb-version")?),
+                "--manifest-path" => parsed.manifest_path = Some(value("--manifest-path")?.into()),
+                "--output" => parsed.output = Some(value("--output")?.into()),
+                _ if parsed.positional.len() < max_positional => parsed.positional.push(arg),
+                _ => Err(format!("unexpected argument {}", arg))?,
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// The parts of `cargo metadata` needed to locate the built library
+struct Package {
+    version: String,
+    library_name: String,
+    target_directory: PathBuf,
+}
+
+fn read_package(manifest_path: Option<&Path>) -> Result<Package, Box<dyn Error>> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
+    let mut command = Command::new(cargo);
+    command.args(["metadata", "--format-version", "1", "--no-deps"]);
+    if let Some(manifest_path) = manifest_path {
+        command.arg("--manifest-path").arg(manifest_path);
+    }
+    let output = command.output()?;
+    if !output.status.success() {
+        Err(format!(
+            "cargo metadata failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ))?;
+    }
+    let metadata: Value = serde_json::from_slice(&output.stdout)?;
+
+    let packages = metadata["packages"].as_array().ok_or("no packages found")?;
+    let package = match manifest_path {
+        Some(manifest_path) => {
+            let manifest_path = manifest_path.canonicalize()?;
+            packages
+                .iter()
+                .find(|package| {
+                    package["manifest_path"]
+                        .as_str()
+                        .and_then(|path| Path::new(path).canonicalize().ok())
+                        .map_or(false, |path| path == manifest_path)
+                })
+                .ok_or("the manifest does not belong to a package")?
+        }
+        None if packages.len() == 1 => &packages[0],
+        None => Err("the workspace contains several packages, pass --manifest-path")?,
+    };
+
+    let library = package["targets"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|target| {
+            target["crate_types"]
+                .as_array()
+                .map_or(false, |types| types.iter().any(|typ| typ == "cdylib"))
+        })
+        .ok_or("the package has no cdylib target")?;
+
+    Ok(Package {
+        version: package["version"]
+            .as_str()
+            .ok_or("package has no version")?
+            .to_owned(),
+        library_name: library["name"]
+            .as_str()
+            .ok_or("target has no name")?
+            .replace('-', "_"),
+        target_directory: metadata["target_directory"]
+            .as_str()
+            .ok_or("no target directory")?
+            .into(),
+    })
+}
+
+/// The directory cargo writes the libraries built for `target`, or the host if `None`, with `profile` to
+fn output_directory(target_directory: &Path, target: Option<&str>, profile: &str) -> PathBuf {
+    let mut directory = target_directory.to_owned();
+    directory.extend(target);
+    directory.join(profile)
+}
+
+/// The file name cargo gives the library built for `target`, or the host if `None`
+fn library_file_name(library_name: &str, target: Option<&str>) -> String {
+    let (prefix, suffix) = match target {
+        None => (env::consts::DLL_PREFIX, env::consts::DLL_SUFFIX),
+        Some(target) if target.contains("windows") => ("", ".dll"),
+        Some(target) if target.contains("apple") => ("lib", ".dylib"),
+        Some(_) => ("lib", ".so"),
+    };
+    format!("{}{}{}", prefix, library_name, suffix)
+}
+
+fn package(args: Args) -> Result<(), Box<dyn Error>> {
+    let duckdb_version = args
+        .duckdb_version
+        .unwrap_or_else(|| HEADERS_VERSION.to_owned());
+    let target = args.target.or_else(|| env::var("CARGO_BUILD_TARGET").ok());
+    let platform = match (args.platform, &target) {
+        (Some(platform), _) => platform,
+        (None, Some(target)) => target_platform(target)
+            .ok_or_else(|| format!("DuckDB does not support {}, pass --platform", target))?
+            .to_owned(),
+        (None, None) => host_platform()
+            .ok_or("DuckDB does not support this platform, pass --platform")?
+            .to_owned(),
+    };
+    let package = read_package(args.manifest_path.as_deref())?;
+
+    let profile = if args.release { "release" } else { "debug" };
+    let directory = output_directory(&package.target_directory, target.as_deref(), profile);
+    let library = directory.join(library_file_name(&package.library_name, target.as_deref()));
+    let mut contents = fs::read(&library)
+        .map_err(|err| format!("could not read {}: {}", library.display(), err))?;
+
+    let metadata = Metadata {
+        platform,
+        duckdb_version,
+        extension_version: package.version,
+    };
+    contents.extend(metadata.to_footer()?);
+
+    let output = args
+        .output
+        .unwrap_or_else(|| directory.join(format!("{}.duckdb_extension", package.library_name)));
+    fs::write(&output, contents)?;
+
+    println!("wrote {}", output.display());
+    Ok(())
+}
+
+fn verify(args: Args) -> Result<(), Box<dyn Error>> {
+    let path = args.positional.first().ok_or("verify takes a file")?;
+    let metadata = Metadata::from_file_contents(&fs::read(path)?)?;
+
+    if let Some(platform) = args.platform {
+        if metadata.platform != platform {
+            Err(format!(
+                "built for platform {}, expected {}",
+                metadata.platform, platform
+            ))?;
+        }
+    }
+    if let Some(duckdb_version) = args.duckdb_version {
+        if metadata.duckdb_version != duckdb_version {
+            Err(format!(
+                "built for DuckDB {}, expected {}",
+                metadata.duckdb_version, duckdb_version
+            ))?;
+        }
+    }
+
+    println!("platform: {}", metadata.platform);
+    println!("duckdb version: {}", metadata.duckdb_version);
+    println!("extension version: {}", metadata.extension_version);
+    Ok(())
+}
+
+fn main() {
+    // When run as `cargo duckdb-ext`, cargo passes the subcommand's name as the first argument
+    let mut args = env::args().skip(1).peekable();
+    if args.peek().map(String::as_str) == Some("duckdb-ext") {
+        args.next();
+    }
+
+    let result = match args.next().as_deref() {
+        Some("package") => Args::parse(
+            args,
+            &[
+                "--duckdb-version",
+                "--release",
+                "--target",
+                "--platform",
+                "--manifest-path",
+                "--output",
+            ],
+            0,
+        )
+        .and_then(package),
+        Some("verify") => {
+            Args::parse(args, &["--platform", "--duckdb-version"], 1).and_then(verify)
+        }
+        _ => Err(USAGE.into()),
+    };
+
+    if let Err(error) = result {
+        eprintln!("error: {}", error);
+        exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{library_file_name, output_directory, Args};
+    use std::path::Path;
+
+    fn parse(args: &[&str], options: &[&str], max_positional: usize) -> Result<Args, String> {
+        Args::parse(
+            args.iter().map(|arg| arg.to_string()),
+            options,
+            max_positional,
+        )
+        .map_err(|error| error.to_string())
+    }
+
+    #[test]
+    fn test_parse() {
+        let args = parse(
+            &["lib.duckdb_extension", "--platform", "osx_arm64"],
+            &["--platform"],
+            1,
+        )
+        .unwrap();
+        assert_eq!(args.positional, ["lib.duckdb_extension"]);
+        assert_eq!(args.platform.as_deref(), Some("osx_arm64"));
+        assert_eq!(args.duckdb_version, None);
+
+        assert_eq!(
+            parse(&["extra"], &[], 0).unwrap_err(),
+            "unexpected argument extra"
+        );
+        assert_eq!(
+            parse(&["--output", "x"], &["--platform"], 0).unwrap_err(),
+            "unknown option --output"
+        );
+        assert_eq!(
+            parse(&["--platform"], &["--platform"], 0).unwrap_err(),
+            "--platform requires a value"
+        );
+    }
+
+    #[test]
+    fn test_library_path() {
+        let target_directory = Path::new("/work/target");
+        assert_eq!(
+            output_directory(target_directory, None, "debug"),
+            Path::new("/work/target/debug")
+        );
+        assert_eq!(
+            output_directory(target_directory, Some("aarch64-apple-darwin"), "release"),
+            Path::new("/work/target/aarch64-apple-darwin/release")
+        );
+
+        assert_eq!(
+            library_file_name("ext", Some("x86_64-unknown-linux-gnu")),
+            "libext.so"
+        );
+        assert_eq!(
+            library_file_name("ext", Some("aarch64-apple-darwin")),
+            "libext.dylib"
+        );
+        assert_eq!(
+            library_file_name("ext", Some("x86_64-pc-windows-msvc")),
+            "ext.dll"
+        );
+    }
+}
@@ -0,0 +1,1143 @@
+// Bindings for `src/wrapper.hpp` against DuckDB v0.7.1, used unless the `buildtime_bindgen` feature is enabled.
+//
+// To regenerate, check out the matching tag in the `duckdb` submodule, run
+// `cargo build --no-default-features --features buildtime_bindgen` and copy `$OUT_DIR/bindings.rs` below this comment.
+
+pub type idx_t = u64;
+pub type DUCKDB_TYPE = ::std::os::raw::c_uint;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_INVALID: DUCKDB_TYPE = 0;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_BOOLEAN: DUCKDB_TYPE = 1;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_TINYINT: DUCKDB_TYPE = 2;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_SMALLINT: DUCKDB_TYPE = 3;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_INTEGER: DUCKDB_TYPE = 4;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_BIGINT: DUCKDB_TYPE = 5;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_UTINYINT: DUCKDB_TYPE = 6;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_USMALLINT: DUCKDB_TYPE = 7;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_UINTEGER: DUCKDB_TYPE = 8;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_UBIGINT: DUCKDB_TYPE = 9;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_FLOAT: DUCKDB_TYPE = 10;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_DOUBLE: DUCKDB_TYPE = 11;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP: DUCKDB_TYPE = 12;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_DATE: DUCKDB_TYPE = 13;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_TIME: DUCKDB_TYPE = 14;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_INTERVAL: DUCKDB_TYPE = 15;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_HUGEINT: DUCKDB_TYPE = 16;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_VARCHAR: DUCKDB_TYPE = 17;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_BLOB: DUCKDB_TYPE = 18;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_DECIMAL: DUCKDB_TYPE = 19;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_S: DUCKDB_TYPE = 20;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_MS: DUCKDB_TYPE = 21;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_NS: DUCKDB_TYPE = 22;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_ENUM: DUCKDB_TYPE = 23;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_LIST: DUCKDB_TYPE = 24;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_STRUCT: DUCKDB_TYPE = 25;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_MAP: DUCKDB_TYPE = 26;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_UUID: DUCKDB_TYPE = 27;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_UNION: DUCKDB_TYPE = 28;
+pub const DUCKDB_TYPE_DUCKDB_TYPE_BIT: DUCKDB_TYPE = 29;
+pub use self::DUCKDB_TYPE as duckdb_type;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct duckdb_date {
+    pub days: i32,
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct duckdb_time {
+    pub micros: i64,
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct duckdb_timestamp {
+    pub micros: i64,
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct duckdb_interval {
+    pub months: i32,
+    pub days: i32,
+    pub micros: i64,
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct duckdb_hugeint {
+    pub lower: u64,
+    pub upper: i64,
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct duckdb_decimal {
+    pub width: u8,
+    pub scale: u8,
+    pub value: duckdb_hugeint,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct duckdb_string_t {
+    pub value: duckdb_string_t__bindgen_ty_1,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union duckdb_string_t__bindgen_ty_1 {
+    pub pointer: duckdb_string_t__bindgen_ty_1__bindgen_ty_1,
+    pub inlined: duckdb_string_t__bindgen_ty_1__bindgen_ty_2,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct duckdb_string_t__bindgen_ty_1__bindgen_ty_1 {
+    pub length: u32,
+    pub prefix: [::std::os::raw::c_char; 4usize],
+    pub ptr: *mut ::std::os::raw::c_char,
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct duckdb_string_t__bindgen_ty_1__bindgen_ty_2 {
+    pub length: u32,
+    pub inlined: [::std::os::raw::c_char; 12usize],
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct duckdb_list_entry {
+    pub offset: u64,
+    pub length: u64,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct duckdb_column {
+    pub __deprecated_data: *mut ::std::os::raw::c_void,
+    pub __deprecated_nullmask: *mut bool,
+    pub __deprecated_type: duckdb_type,
+    pub __deprecated_name: *mut ::std::os::raw::c_char,
+    pub internal_data: *mut ::std::os::raw::c_void,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct duckdb_result {
+    pub __deprecated_column_count: idx_t,
+    pub __deprecated_row_count: idx_t,
+    pub __deprecated_rows_changed: idx_t,
+    pub __deprecated_columns: *mut duckdb_column,
+    pub __deprecated_error_message: *mut ::std::os::raw::c_char,
+    pub internal_data: *mut ::std::os::raw::c_void,
+}
+pub type duckdb_database = *mut ::std::os::raw::c_void;
+pub type duckdb_connection = *mut ::std::os::raw::c_void;
+pub type duckdb_prepared_statement = *mut ::std::os::raw::c_void;
+pub type duckdb_appender = *mut ::std::os::raw::c_void;
+pub type duckdb_arrow = *mut ::std::os::raw::c_void;
+pub type duckdb_config = *mut ::std::os::raw::c_void;
+pub type duckdb_logical_type = *mut ::std::os::raw::c_void;
+pub type duckdb_data_chunk = *mut ::std::os::raw::c_void;
+pub type duckdb_vector = *mut ::std::os::raw::c_void;
+pub type duckdb_value = *mut ::std::os::raw::c_void;
+pub const duckdb_state_DuckDBSuccess: duckdb_state = 0;
+pub const duckdb_state_DuckDBError: duckdb_state = 1;
+pub type duckdb_state = ::std::os::raw::c_uint;
+pub type duckdb_table_function = *mut ::std::os::raw::c_void;
+pub type duckdb_bind_info = *mut ::std::os::raw::c_void;
+pub type duckdb_init_info = *mut ::std::os::raw::c_void;
+pub type duckdb_function_info = *mut ::std::os::raw::c_void;
+pub type duckdb_table_function_bind_t =
+    ::std::option::Option<unsafe extern "C" fn(info: duckdb_bind_info)>;
+pub type duckdb_table_function_init_t =
+    ::std::option::Option<unsafe extern "C" fn(info: duckdb_init_info)>;
+pub type duckdb_table_function_t = ::std::option::Option<
+    unsafe extern "C" fn(info: duckdb_function_info, output: duckdb_data_chunk),
+>;
+pub type duckdb_delete_callback_t =
+    ::std::option::Option<unsafe extern "C" fn(data: *mut ::std::os::raw::c_void)>;
+pub type duckdb_replacement_scan_info = *mut ::std::os::raw::c_void;
+pub type duckdb_replacement_callback_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        info: duckdb_replacement_scan_info,
+        table_name: *const ::std::os::raw::c_char,
+        data: *mut ::std::os::raw::c_void,
+    ),
+>;
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct duckdb_date_struct {
+    pub year: i32,
+    pub month: i8,
+    pub day: i8,
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct duckdb_time_struct {
+    pub hour: i8,
+    pub min: i8,
+    pub sec: i8,
+    pub micros: i32,
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct duckdb_timestamp_struct {
+    pub date: duckdb_date_struct,
+    pub time: duckdb_time_struct,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct duckdb_blob {
+    pub data: *mut ::std::os::raw::c_void,
+    pub size: idx_t,
+}
+pub type duckdb_pending_result = *mut ::std::os::raw::c_void;
+pub type duckdb_extracted_statements = *mut ::std::os::raw::c_void;
+pub type duckdb_arrow_schema = *mut ::std::os::raw::c_void;
+pub type duckdb_arrow_array = *mut ::std::os::raw::c_void;
+pub type duckdb_task_state = *mut ::std::os::raw::c_void;
+pub const duckdb_pending_state_DUCKDB_PENDING_RESULT_READY: duckdb_pending_state = 0;
+pub const duckdb_pending_state_DUCKDB_PENDING_RESULT_NOT_READY: duckdb_pending_state = 1;
+pub const duckdb_pending_state_DUCKDB_PENDING_ERROR: duckdb_pending_state = 2;
+pub type duckdb_pending_state = ::std::os::raw::c_uint;
+extern "C" {
+    pub fn duckdb_open(
+        path: *const ::std::os::raw::c_char,
+        out_database: *mut duckdb_database,
+    ) -> duckdb_state;
+    pub fn duckdb_open_ext(
+        path: *const ::std::os::raw::c_char,
+        out_database: *mut duckdb_database,
+        config: duckdb_config,
+        out_error: *mut *mut ::std::os::raw::c_char,
+    ) -> duckdb_state;
+    pub fn duckdb_close(database: *mut duckdb_database);
+    pub fn duckdb_connect(
+        database: duckdb_database,
+        out_connection: *mut duckdb_connection,
+    ) -> duckdb_state;
+    pub fn duckdb_disconnect(connection: *mut duckdb_connection);
+    pub fn duckdb_library_version() -> *const ::std::os::raw::c_char;
+    pub fn duckdb_create_config(out_config: *mut duckdb_config) -> duckdb_state;
+    pub fn duckdb_config_count() -> usize;
+    pub fn duckdb_get_config_flag(
+        index: usize,
+        out_name: *mut *const ::std::os::raw::c_char,
+        out_description: *mut *const ::std::os::raw::c_char,
+    ) -> duckdb_state;
+    pub fn duckdb_set_config(
+        config: duckdb_config,
+        name: *const ::std::os::raw::c_char,
+        option: *const ::std::os::raw::c_char,
+    ) -> duckdb_state;
+    pub fn duckdb_destroy_config(config: *mut duckdb_config);
+    pub fn duckdb_query(
+        connection: duckdb_connection,
+        query: *const ::std::os::raw::c_char,
+        out_result: *mut duckdb_result,
+    ) -> duckdb_state;
+    pub fn duckdb_destroy_result(result: *mut duckdb_result);
+    pub fn duckdb_column_name(
+        result: *mut duckdb_result,
+        col: idx_t,
+    ) -> *const ::std::os::raw::c_char;
+    pub fn duckdb_column_type(result: *mut duckdb_result, col: idx_t) -> duckdb_type;
+    pub fn duckdb_column_logical_type(
+        result: *mut duckdb_result,
+        col: idx_t,
+    ) -> duckdb_logical_type;
+    pub fn duckdb_column_count(result: *mut duckdb_result) -> idx_t;
+    pub fn duckdb_row_count(result: *mut duckdb_result) -> idx_t;
+    pub fn duckdb_rows_changed(result: *mut duckdb_result) -> idx_t;
+    pub fn duckdb_column_data(
+        result: *mut duckdb_result,
+        col: idx_t,
+    ) -> *mut ::std::os::raw::c_void;
+    pub fn duckdb_nullmask_data(result: *mut duckdb_result, col: idx_t) -> *mut bool;
+    pub fn duckdb_result_error(result: *mut duckdb_result) -> *const ::std::os::raw::c_char;
+    pub fn duckdb_result_get_chunk(result: duckdb_result, chunk_index: idx_t) -> duckdb_data_chunk;
+    pub fn duckdb_result_chunk_count(result: duckdb_result) -> idx_t;
+    pub fn duckdb_value_boolean(result: *mut duckdb_result, col: idx_t, row: idx_t) -> bool;
+    pub fn duckdb_value_int8(result: *mut duckdb_result, col: idx_t, row: idx_t) -> i8;
+    pub fn duckdb_value_int16(result: *mut duckdb_result, col: idx_t, row: idx_t) -> i16;
+    pub fn duckdb_value_int32(result: *mut duckdb_result, col: idx_t, row: idx_t) -> i32;
+    pub fn duckdb_value_int64(result: *mut duckdb_result, col: idx_t, row: idx_t) -> i64;
+    pub fn duckdb_value_hugeint(
+        result: *mut duckdb_result,
+        col: idx_t,
+        row: idx_t,
+    ) -> duckdb_hugeint;
+    pub fn duckdb_value_decimal(
+        result: *mut duckdb_result,
+        col: idx_t,
+        row: idx_t,
+    ) -> duckdb_decimal;
+    pub fn duckdb_value_uint8(result: *mut duckdb_result, col: idx_t, row: idx_t) -> u8;
+    pub fn duckdb_value_uint16(result: *mut duckdb_result, col: idx_t, row: idx_t) -> u16;
+    pub fn duckdb_value_uint32(result: *mut duckdb_result, col: idx_t, row: idx_t) -> u32;
+    pub fn duckdb_value_uint64(result: *mut duckdb_result, col: idx_t, row: idx_t) -> u64;
+    pub fn duckdb_value_float(result: *mut duckdb_result, col: idx_t, row: idx_t) -> f32;
+    pub fn duckdb_value_double(result: *mut duckdb_result, col: idx_t, row: idx_t) -> f64;
+    pub fn duckdb_value_date(result: *mut duckdb_result, col: idx_t, row: idx_t) -> duckdb_date;
+    pub fn duckdb_value_time(result: *mut duckdb_result, col: idx_t, row: idx_t) -> duckdb_time;
+    pub fn duckdb_value_timestamp(
+        result: *mut duckdb_result,
+        col: idx_t,
+        row: idx_t,
+    ) -> duckdb_timestamp;
+    pub fn duckdb_value_interval(
+        result: *mut duckdb_result,
+        col: idx_t,
+        row: idx_t,
+    ) -> duckdb_interval;
+    pub fn duckdb_value_varchar(
+        result: *mut duckdb_result,
+        col: idx_t,
+        row: idx_t,
+    ) -> *mut ::std::os::raw::c_char;
+    pub fn duckdb_value_varchar_internal(
+        result: *mut duckdb_result,
+        col: idx_t,
+        row: idx_t,
+    ) -> *mut ::std::os::raw::c_char;
+    pub fn duckdb_value_blob(result: *mut duckdb_result, col: idx_t, row: idx_t) -> duckdb_blob;
+    pub fn duckdb_value_is_null(result: *mut duckdb_result, col: idx_t, row: idx_t) -> bool;
+    pub fn duckdb_malloc(size: usize) -> *mut ::std::os::raw::c_void;
+    pub fn duckdb_free(ptr: *mut ::std::os::raw::c_void);
+    pub fn duckdb_vector_size() -> idx_t;
+    pub fn duckdb_from_date(date: duckdb_date) -> duckdb_date_struct;
+    pub fn duckdb_to_date(date: duckdb_date_struct) -> duckdb_date;
+    pub fn duckdb_from_time(time: duckdb_time) -> duckdb_time_struct;
+    pub fn duckdb_to_time(time: duckdb_time_struct) -> duckdb_time;
+    pub fn duckdb_from_timestamp(ts: duckdb_timestamp) -> duckdb_timestamp_struct;
+    pub fn duckdb_to_timestamp(ts: duckdb_timestamp_struct) -> duckdb_timestamp;
+    pub fn duckdb_hugeint_to_double(val: duckdb_hugeint) -> f64;
+    pub fn duckdb_double_to_hugeint(val: f64) -> duckdb_hugeint;
+    pub fn duckdb_decimal_to_double(val: duckdb_decimal) -> f64;
+    pub fn duckdb_prepare(
+        connection: duckdb_connection,
+        query: *const ::std::os::raw::c_char,
+        out_prepared_statement: *mut duckdb_prepared_statement,
+    ) -> duckdb_state;
+    pub fn duckdb_destroy_prepare(prepared_statement: *mut duckdb_prepared_statement);
+    pub fn duckdb_prepare_error(
+        prepared_statement: duckdb_prepared_statement,
+    ) -> *const ::std::os::raw::c_char;
+    pub fn duckdb_nparams(prepared_statement: duckdb_prepared_statement) -> idx_t;
+    pub fn duckdb_param_type(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+    ) -> duckdb_type;
+    pub fn duckdb_clear_bindings(prepared_statement: duckdb_prepared_statement) -> duckdb_state;
+    pub fn duckdb_bind_boolean(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        val: bool,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_int8(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        val: i8,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_int16(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        val: i16,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_int32(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        val: i32,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_int64(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        val: i64,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_hugeint(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        val: duckdb_hugeint,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_decimal(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        val: duckdb_decimal,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_uint8(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        val: u8,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_uint16(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        val: u16,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_uint32(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        val: u32,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_uint64(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        val: u64,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_float(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        val: f32,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_double(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        val: f64,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_date(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        val: duckdb_date,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_time(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        val: duckdb_time,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_timestamp(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        val: duckdb_timestamp,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_interval(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        val: duckdb_interval,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_varchar(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        val: *const ::std::os::raw::c_char,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_varchar_length(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        val: *const ::std::os::raw::c_char,
+        length: idx_t,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_blob(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+        data: *const ::std::os::raw::c_void,
+        length: idx_t,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_null(
+        prepared_statement: duckdb_prepared_statement,
+        param_idx: idx_t,
+    ) -> duckdb_state;
+    pub fn duckdb_execute_prepared(
+        prepared_statement: duckdb_prepared_statement,
+        out_result: *mut duckdb_result,
+    ) -> duckdb_state;
+    pub fn duckdb_execute_prepared_arrow(
+        prepared_statement: duckdb_prepared_statement,
+        out_result: *mut duckdb_arrow,
+    ) -> duckdb_state;
+    pub fn duckdb_extract_statements(
+        connection: duckdb_connection,
+        query: *const ::std::os::raw::c_char,
+        out_extracted_statements: *mut duckdb_extracted_statements,
+    ) -> idx_t;
+    pub fn duckdb_prepare_extracted_statement(
+        connection: duckdb_connection,
+        extracted_statements: duckdb_extracted_statements,
+        index: idx_t,
+        out_prepared_statement: *mut duckdb_prepared_statement,
+    ) -> duckdb_state;
+    pub fn duckdb_extract_statements_error(
+        extracted_statements: duckdb_extracted_statements,
+    ) -> *const ::std::os::raw::c_char;
+    pub fn duckdb_destroy_extracted(extracted_statements: *mut duckdb_extracted_statements);
+    pub fn duckdb_pending_prepared(
+        prepared_statement: duckdb_prepared_statement,
+        out_result: *mut duckdb_pending_result,
+    ) -> duckdb_state;
+    pub fn duckdb_destroy_pending(pending_result: *mut duckdb_pending_result);
+    pub fn duckdb_pending_error(
+        pending_result: duckdb_pending_result,
+    ) -> *const ::std::os::raw::c_char;
+    pub fn duckdb_pending_execute_task(
+        pending_result: duckdb_pending_result,
+    ) -> duckdb_pending_state;
+    pub fn duckdb_execute_pending(
+        pending_result: duckdb_pending_result,
+        out_result: *mut duckdb_result,
+    ) -> duckdb_state;
+    pub fn duckdb_destroy_value(value: *mut duckdb_value);
+    pub fn duckdb_create_varchar(text: *const ::std::os::raw::c_char) -> duckdb_value;
+    pub fn duckdb_create_varchar_length(
+        text: *const ::std::os::raw::c_char,
+        length: idx_t,
+    ) -> duckdb_value;
+    pub fn duckdb_create_int64(val: i64) -> duckdb_value;
+    pub fn duckdb_get_varchar(value: duckdb_value) -> *mut ::std::os::raw::c_char;
+    pub fn duckdb_get_int64(value: duckdb_value) -> i64;
+    pub fn duckdb_create_logical_type(type_: duckdb_type) -> duckdb_logical_type;
+    pub fn duckdb_create_list_type(type_: duckdb_logical_type) -> duckdb_logical_type;
+    pub fn duckdb_create_map_type(
+        key_type: duckdb_logical_type,
+        value_type: duckdb_logical_type,
+    ) -> duckdb_logical_type;
+    pub fn duckdb_create_decimal_type(width: u8, scale: u8) -> duckdb_logical_type;
+    pub fn duckdb_get_type_id(type_: duckdb_logical_type) -> duckdb_type;
+    pub fn duckdb_decimal_width(type_: duckdb_logical_type) -> u8;
+    pub fn duckdb_decimal_scale(type_: duckdb_logical_type) -> u8;
+    pub fn duckdb_decimal_internal_type(type_: duckdb_logical_type) -> duckdb_type;
+    pub fn duckdb_enum_internal_type(type_: duckdb_logical_type) -> duckdb_type;
+    pub fn duckdb_enum_dictionary_size(type_: duckdb_logical_type) -> u32;
+    pub fn duckdb_enum_dictionary_value(
+        type_: duckdb_logical_type,
+        index: idx_t,
+    ) -> *mut ::std::os::raw::c_char;
+    pub fn duckdb_list_type_child_type(type_: duckdb_logical_type) -> duckdb_logical_type;
+    pub fn duckdb_map_type_key_type(type_: duckdb_logical_type) -> duckdb_logical_type;
+    pub fn duckdb_map_type_value_type(type_: duckdb_logical_type) -> duckdb_logical_type;
+    pub fn duckdb_struct_type_child_count(type_: duckdb_logical_type) -> idx_t;
+    pub fn duckdb_struct_type_child_name(
+        type_: duckdb_logical_type,
+        index: idx_t,
+    ) -> *mut ::std::os::raw::c_char;
+    pub fn duckdb_struct_type_child_type(
+        type_: duckdb_logical_type,
+        index: idx_t,
+    ) -> duckdb_logical_type;
+    pub fn duckdb_destroy_logical_type(type_: *mut duckdb_logical_type);
+    pub fn duckdb_create_data_chunk(
+        types: *mut duckdb_logical_type,
+        column_count: idx_t,
+    ) -> duckdb_data_chunk;
+    pub fn duckdb_destroy_data_chunk(chunk: *mut duckdb_data_chunk);
+    pub fn duckdb_data_chunk_reset(chunk: duckdb_data_chunk);
+    pub fn duckdb_data_chunk_get_column_count(chunk: duckdb_data_chunk) -> idx_t;
+    pub fn duckdb_data_chunk_get_vector(chunk: duckdb_data_chunk, col_idx: idx_t) -> duckdb_vector;
+    pub fn duckdb_data_chunk_get_size(chunk: duckdb_data_chunk) -> idx_t;
+    pub fn duckdb_data_chunk_set_size(chunk: duckdb_data_chunk, size: idx_t);
+    pub fn duckdb_vector_get_column_type(vector: duckdb_vector) -> duckdb_logical_type;
+    pub fn duckdb_vector_get_data(vector: duckdb_vector) -> *mut ::std::os::raw::c_void;
+    pub fn duckdb_vector_get_validity(vector: duckdb_vector) -> *mut u64;
+    pub fn duckdb_vector_ensure_validity_writable(vector: duckdb_vector);
+    pub fn duckdb_vector_assign_string_element(
+        vector: duckdb_vector,
+        index: idx_t,
+        str_: *const ::std::os::raw::c_char,
+    );
+    pub fn duckdb_vector_assign_string_element_len(
+        vector: duckdb_vector,
+        index: idx_t,
+        str_: *const ::std::os::raw::c_char,
+        str_len: idx_t,
+    );
+    pub fn duckdb_list_vector_get_child(vector: duckdb_vector) -> duckdb_vector;
+    pub fn duckdb_list_vector_get_size(vector: duckdb_vector) -> idx_t;
+    pub fn duckdb_list_vector_set_size(vector: duckdb_vector, size: idx_t) -> duckdb_state;
+    pub fn duckdb_list_vector_reserve(
+        vector: duckdb_vector,
+        required_capacity: idx_t,
+    ) -> duckdb_state;
+    pub fn duckdb_struct_vector_get_child(vector: duckdb_vector, index: idx_t) -> duckdb_vector;
+    pub fn duckdb_validity_row_is_valid(validity: *mut u64, row: idx_t) -> bool;
+    pub fn duckdb_validity_set_row_validity(validity: *mut u64, row: idx_t, valid: bool);
+    pub fn duckdb_validity_set_row_invalid(validity: *mut u64, row: idx_t);
+    pub fn duckdb_validity_set_row_valid(validity: *mut u64, row: idx_t);
+    pub fn duckdb_create_table_function() -> duckdb_table_function;
+    pub fn duckdb_destroy_table_function(table_function: *mut duckdb_table_function);
+    pub fn duckdb_table_function_set_name(
+        table_function: duckdb_table_function,
+        name: *const ::std::os::raw::c_char,
+    );
+    pub fn duckdb_table_function_add_parameter(
+        table_function: duckdb_table_function,
+        type_: duckdb_logical_type,
+    );
+    pub fn duckdb_table_function_add_named_parameter(
+        table_function: duckdb_table_function,
+        name: *const ::std::os::raw::c_char,
+        type_: duckdb_logical_type,
+    );
+    pub fn duckdb_table_function_set_extra_info(
+        table_function: duckdb_table_function,
+        extra_info: *mut ::std::os::raw::c_void,
+        destroy: duckdb_delete_callback_t,
+    );
+    pub fn duckdb_table_function_set_bind(
+        table_function: duckdb_table_function,
+        bind: duckdb_table_function_bind_t,
+    );
+    pub fn duckdb_table_function_set_init(
+        table_function: duckdb_table_function,
+        init: duckdb_table_function_init_t,
+    );
+    pub fn duckdb_table_function_set_local_init(
+        table_function: duckdb_table_function,
+        init: duckdb_table_function_init_t,
+    );
+    pub fn duckdb_table_function_set_function(
+        table_function: duckdb_table_function,
+        function: duckdb_table_function_t,
+    );
+    pub fn duckdb_table_function_supports_projection_pushdown(
+        table_function: duckdb_table_function,
+        pushdown: bool,
+    );
+    pub fn duckdb_register_table_function(
+        con: duckdb_connection,
+        function: duckdb_table_function,
+    ) -> duckdb_state;
+    pub fn duckdb_bind_get_extra_info(info: duckdb_bind_info) -> *mut ::std::os::raw::c_void;
+    pub fn duckdb_bind_add_result_column(
+        info: duckdb_bind_info,
+        name: *const ::std::os::raw::c_char,
+        type_: duckdb_logical_type,
+    );
+    pub fn duckdb_bind_get_parameter_count(info: duckdb_bind_info) -> idx_t;
+    pub fn duckdb_bind_get_parameter(info: duckdb_bind_info, index: idx_t) -> duckdb_value;
+    pub fn duckdb_bind_get_named_parameter(
+        info: duckdb_bind_info,
+        name: *const ::std::os::raw::c_char,
+    ) -> duckdb_value;
+    pub fn duckdb_bind_set_bind_data(
+        info: duckdb_bind_info,
+        bind_data: *mut ::std::os::raw::c_void,
+        destroy: duckdb_delete_callback_t,
+    );
+    pub fn duckdb_bind_set_cardinality(info: duckdb_bind_info, cardinality: idx_t, is_exact: bool);
+    pub fn duckdb_bind_set_error(info: duckdb_bind_info, error: *const ::std::os::raw::c_char);
+    pub fn duckdb_init_get_extra_info(info: duckdb_init_info) -> *mut ::std::os::raw::c_void;
+    pub fn duckdb_init_get_bind_data(info: duckdb_init_info) -> *mut ::std::os::raw::c_void;
+    pub fn duckdb_init_set_init_data(
+        info: duckdb_init_info,
+        init_data: *mut ::std::os::raw::c_void,
+        destroy: duckdb_delete_callback_t,
+    );
+    pub fn duckdb_init_get_column_count(info: duckdb_init_info) -> idx_t;
+    pub fn duckdb_init_get_column_index(info: duckdb_init_info, column_index: idx_t) -> idx_t;
+    pub fn duckdb_init_set_max_threads(info: duckdb_init_info, max_threads: idx_t);
+    pub fn duckdb_init_set_error(info: duckdb_init_info, error: *const ::std::os::raw::c_char);
+    pub fn duckdb_function_get_extra_info(
+        info: duckdb_function_info,
+    ) -> *mut ::std::os::raw::c_void;
+    pub fn duckdb_function_get_bind_data(info: duckdb_function_info)
+        -> *mut ::std::os::raw::c_void;
+    pub fn duckdb_function_get_init_data(info: duckdb_function_info)
+        -> *mut ::std::os::raw::c_void;
+    pub fn duckdb_function_get_local_init_data(
+        info: duckdb_function_info,
+    ) -> *mut ::std::os::raw::c_void;
+    pub fn duckdb_function_set_error(
+        info: duckdb_function_info,
+        error: *const ::std::os::raw::c_char,
+    );
+    pub fn duckdb_add_replacement_scan(
+        db: duckdb_database,
+        replacement: duckdb_replacement_callback_t,
+        extra_data: *mut ::std::os::raw::c_void,
+        delete_callback: duckdb_delete_callback_t,
+    );
+    pub fn duckdb_replacement_scan_set_function_name(
+        info: duckdb_replacement_scan_info,
+        function_name: *const ::std::os::raw::c_char,
+    );
+    pub fn duckdb_replacement_scan_add_parameter(
+        info: duckdb_replacement_scan_info,
+        parameter: duckdb_value,
+    );
+    pub fn duckdb_replacement_scan_set_error(
+        info: duckdb_replacement_scan_info,
+        error: *const ::std::os::raw::c_char,
+    );
+    pub fn duckdb_appender_create(
+        connection: duckdb_connection,
+        schema: *const ::std::os::raw::c_char,
+        table: *const ::std::os::raw::c_char,
+        out_appender: *mut duckdb_appender,
+    ) -> duckdb_state;
+    pub fn duckdb_appender_error(appender: duckdb_appender) -> *const ::std::os::raw::c_char;
+    pub fn duckdb_appender_flush(appender: duckdb_appender) -> duckdb_state;
+    pub fn duckdb_appender_close(appender: duckdb_appender) -> duckdb_state;
+    pub fn duckdb_appender_destroy(appender: *mut duckdb_appender) -> duckdb_state;
+    pub fn duckdb_appender_begin_row(appender: duckdb_appender) -> duckdb_state;
+    pub fn duckdb_appender_end_row(appender: duckdb_appender) -> duckdb_state;
+    pub fn duckdb_append_bool(appender: duckdb_appender, value: bool) -> duckdb_state;
+    pub fn duckdb_append_int8(appender: duckdb_appender, value: i8) -> duckdb_state;
+    pub fn duckdb_append_int16(appender: duckdb_appender, value: i16) -> duckdb_state;
+    pub fn duckdb_append_int32(appender: duckdb_appender, value: i32) -> duckdb_state;
+    pub fn duckdb_append_int64(appender: duckdb_appender, value: i64) -> duckdb_state;
+    pub fn duckdb_append_hugeint(appender: duckdb_appender, value: duckdb_hugeint) -> duckdb_state;
+    pub fn duckdb_append_uint8(appender: duckdb_appender, value: u8) -> duckdb_state;
+    pub fn duckdb_append_uint16(appender: duckdb_appender, value: u16) -> duckdb_state;
+    pub fn duckdb_append_uint32(appender: duckdb_appender, value: u32) -> duckdb_state;
+    pub fn duckdb_append_uint64(appender: duckdb_appender, value: u64) -> duckdb_state;
+    pub fn duckdb_append_float(appender: duckdb_appender, value: f32) -> duckdb_state;
+    pub fn duckdb_append_double(appender: duckdb_appender, value: f64) -> duckdb_state;
+    pub fn duckdb_append_date(appender: duckdb_appender, value: duckdb_date) -> duckdb_state;
+    pub fn duckdb_append_time(appender: duckdb_appender, value: duckdb_time) -> duckdb_state;
+    pub fn duckdb_append_timestamp(
+        appender: duckdb_appender,
+        value: duckdb_timestamp,
+    ) -> duckdb_state;
+    pub fn duckdb_append_interval(
+        appender: duckdb_appender,
+        value: duckdb_interval,
+    ) -> duckdb_state;
+    pub fn duckdb_append_varchar(
+        appender: duckdb_appender,
+        val: *const ::std::os::raw::c_char,
+    ) -> duckdb_state;
+    pub fn duckdb_append_varchar_length(
+        appender: duckdb_appender,
+        val: *const ::std::os::raw::c_char,
+        length: idx_t,
+    ) -> duckdb_state;
+    pub fn duckdb_append_blob(
+        appender: duckdb_appender,
+        data: *const ::std::os::raw::c_void,
+        length: idx_t,
+    ) -> duckdb_state;
+    pub fn duckdb_append_null(appender: duckdb_appender) -> duckdb_state;
+    pub fn duckdb_append_data_chunk(
+        appender: duckdb_appender,
+        chunk: duckdb_data_chunk,
+    ) -> duckdb_state;
+    pub fn duckdb_query_arrow(
+        connection: duckdb_connection,
+        query: *const ::std::os::raw::c_char,
+        out_result: *mut duckdb_arrow,
+    ) -> duckdb_state;
+    pub fn duckdb_query_arrow_schema(
+        result: duckdb_arrow,
+        out_schema: *mut duckdb_arrow_schema,
+    ) -> duckdb_state;
+    pub fn duckdb_query_arrow_array(
+        result: duckdb_arrow,
+        out_array: *mut duckdb_arrow_array,
+    ) -> duckdb_state;
+    pub fn duckdb_arrow_column_count(result: duckdb_arrow) -> idx_t;
+    pub fn duckdb_arrow_row_count(result: duckdb_arrow) -> idx_t;
+    pub fn duckdb_arrow_rows_changed(result: duckdb_arrow) -> idx_t;
+    pub fn duckdb_query_arrow_error(result: duckdb_arrow) -> *const ::std::os::raw::c_char;
+    pub fn duckdb_destroy_arrow(result: *mut duckdb_arrow);
+    pub fn duckdb_execute_tasks(database: duckdb_database, max_tasks: idx_t);
+    pub fn duckdb_create_task_state(database: duckdb_database) -> duckdb_task_state;
+    pub fn duckdb_execute_tasks_state(state: duckdb_task_state);
+    pub fn duckdb_execute_n_tasks_state(state: duckdb_task_state, max_tasks: idx_t) -> idx_t;
+    pub fn duckdb_finish_execution(state: duckdb_task_state);
+    pub fn duckdb_task_state_is_finished(state: duckdb_task_state) -> bool;
+    pub fn duckdb_destroy_task_state(state: duckdb_task_state);
+}
+extern "C" {
+    pub fn duckdb_create_union(
+        nmembers: idx_t,
+        names: *mut *const ::std::os::raw::c_char,
+        types: *const duckdb_logical_type,
+    ) -> duckdb_logical_type;
+    pub fn duckdb_create_struct_type(
+        n_pairs: idx_t,
+        names: *mut *const ::std::os::raw::c_char,
+        types: *const duckdb_logical_type,
+    ) -> duckdb_logical_type;
+}
+extern "C" {
+    pub fn duckdb_create_enum_type(
+        member_names: *mut *const ::std::os::raw::c_char,
+        member_count: idx_t,
+    ) -> duckdb_logical_type;
+}
+pub const DUCKDB_EXTENSION_TYPE_TIME_TZ: u32 = 30;
+pub const DUCKDB_EXTENSION_TYPE_TIMESTAMP_TZ: u32 = 31;
+extern "C" {
+    pub fn duckdb_extension_create_logical_type(type_: duckdb_type) -> duckdb_logical_type;
+}
+extern "C" {
+    pub fn duckdb_extension_get_type_id(type_: duckdb_logical_type) -> duckdb_type;
+}
+extern "C" {
+    pub fn duckdb_copy_logical_type(type_: duckdb_logical_type) -> duckdb_logical_type;
+}
+extern "C" {
+    pub fn duckdb_logical_type_equals(
+        left: duckdb_logical_type,
+        right: duckdb_logical_type,
+    ) -> bool;
+}
+extern "C" {
+    pub fn duckdb_union_type_member_count(type_: duckdb_logical_type) -> idx_t;
+}
+extern "C" {
+    pub fn duckdb_union_type_member_name(
+        type_: duckdb_logical_type,
+        index: idx_t,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn duckdb_union_type_member_type(
+        type_: duckdb_logical_type,
+        index: idx_t,
+    ) -> duckdb_logical_type;
+}
+extern "C" {
+    pub fn duckdb_extension_last_error() -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn duckdb_extension_register_table_function(
+        connection: duckdb_connection,
+        function: duckdb_table_function,
+    ) -> duckdb_state;
+}
+pub type duckdb_pragma_function = *mut ::std::os::raw::c_void;
+pub type duckdb_pragma_info = *mut ::std::os::raw::c_void;
+pub type duckdb_pragma_function_t =
+    ::std::option::Option<unsafe extern "C" fn(info: duckdb_pragma_info)>;
+extern "C" {
+    pub fn duckdb_create_pragma_function(is_statement: bool) -> duckdb_pragma_function;
+    pub fn duckdb_destroy_pragma_function(pragma_function: *mut duckdb_pragma_function);
+    pub fn duckdb_pragma_function_set_name(
+        pragma_function: duckdb_pragma_function,
+        name: *const ::std::os::raw::c_char,
+    );
+    pub fn duckdb_pragma_function_add_parameter(
+        pragma_function: duckdb_pragma_function,
+        type_: duckdb_logical_type,
+    );
+    pub fn duckdb_pragma_function_set_function(
+        pragma_function: duckdb_pragma_function,
+        function: duckdb_pragma_function_t,
+    );
+    pub fn duckdb_pragma_function_set_query(
+        pragma_function: duckdb_pragma_function,
+        query: duckdb_pragma_function_t,
+    );
+    pub fn duckdb_pragma_function_set_extra_info(
+        pragma_function: duckdb_pragma_function,
+        extra_info: *mut ::std::os::raw::c_void,
+        destroy: duckdb_delete_callback_t,
+    );
+    pub fn duckdb_register_pragma_function(
+        con: duckdb_connection,
+        pragma_function: duckdb_pragma_function,
+    ) -> duckdb_state;
+    pub fn duckdb_pragma_get_extra_info(info: duckdb_pragma_info) -> *mut ::std::os::raw::c_void;
+    pub fn duckdb_pragma_get_parameter_count(info: duckdb_pragma_info) -> idx_t;
+    pub fn duckdb_pragma_get_parameter(info: duckdb_pragma_info, index: idx_t) -> duckdb_value;
+    pub fn duckdb_pragma_set_query(info: duckdb_pragma_info, query: *const ::std::os::raw::c_char);
+    pub fn duckdb_pragma_set_error(info: duckdb_pragma_info, error: *const ::std::os::raw::c_char);
+}
+pub type duckdb_copy_function = *mut ::std::os::raw::c_void;
+pub type duckdb_copy_info = *mut ::std::os::raw::c_void;
+pub type duckdb_copy_function_t =
+    ::std::option::Option<unsafe extern "C" fn(info: duckdb_copy_info)>;
+pub type duckdb_copy_sink_t =
+    ::std::option::Option<unsafe extern "C" fn(info: duckdb_copy_info, input: duckdb_data_chunk)>;
+extern "C" {
+    pub fn duckdb_create_copy_function() -> duckdb_copy_function;
+    pub fn duckdb_destroy_copy_function(copy_function: *mut duckdb_copy_function);
+    pub fn duckdb_copy_function_set_name(
+        copy_function: duckdb_copy_function,
+        name: *const ::std::os::raw::c_char,
+    );
+    pub fn duckdb_copy_function_set_extra_info(
+        copy_function: duckdb_copy_function,
+        extra_info: *mut ::std::os::raw::c_void,
+        destroy: duckdb_delete_callback_t,
+    );
+    pub fn duckdb_copy_function_set_bind(
+        copy_function: duckdb_copy_function,
+        bind: duckdb_copy_function_t,
+    );
+    pub fn duckdb_copy_function_set_init_global(
+        copy_function: duckdb_copy_function,
+        init: duckdb_copy_function_t,
+    );
+    pub fn duckdb_copy_function_set_init_local(
+        copy_function: duckdb_copy_function,
+        init: duckdb_copy_function_t,
+    );
+    pub fn duckdb_copy_function_set_sink(
+        copy_function: duckdb_copy_function,
+        sink: duckdb_copy_sink_t,
+    );
+    pub fn duckdb_copy_function_set_combine(
+        copy_function: duckdb_copy_function,
+        combine: duckdb_copy_function_t,
+    );
+    pub fn duckdb_copy_function_set_finalize(
+        copy_function: duckdb_copy_function,
+        finalize: duckdb_copy_function_t,
+    );
+    pub fn duckdb_register_copy_function(
+        con: duckdb_connection,
+        copy_function: duckdb_copy_function,
+    ) -> duckdb_state;
+    pub fn duckdb_copy_get_extra_info(info: duckdb_copy_info) -> *mut ::std::os::raw::c_void;
+    pub fn duckdb_copy_get_option_count(info: duckdb_copy_info) -> idx_t;
+    pub fn duckdb_copy_get_option_name(
+        info: duckdb_copy_info,
+        index: idx_t,
+    ) -> *const ::std::os::raw::c_char;
+    pub fn duckdb_copy_get_option_value_count(info: duckdb_copy_info, index: idx_t) -> idx_t;
+    pub fn duckdb_copy_get_option_value(
+        info: duckdb_copy_info,
+        index: idx_t,
+        value_index: idx_t,
+    ) -> duckdb_value;
+    pub fn duckdb_copy_get_column_count(info: duckdb_copy_info) -> idx_t;
+    pub fn duckdb_copy_get_column_name(
+        info: duckdb_copy_info,
+        index: idx_t,
+    ) -> *const ::std::os::raw::c_char;
+    pub fn duckdb_copy_get_column_type(info: duckdb_copy_info, index: idx_t)
+        -> duckdb_logical_type;
+    pub fn duckdb_copy_get_file_path(info: duckdb_copy_info) -> *const ::std::os::raw::c_char;
+    pub fn duckdb_copy_set_bind_data(
+        info: duckdb_copy_info,
+        bind_data: *mut ::std::os::raw::c_void,
+        destroy: duckdb_delete_callback_t,
+    );
+    pub fn duckdb_copy_get_bind_data(info: duckdb_copy_info) -> *mut ::std::os::raw::c_void;
+    pub fn duckdb_copy_set_global_state(
+        info: duckdb_copy_info,
+        global_state: *mut ::std::os::raw::c_void,
+        destroy: duckdb_delete_callback_t,
+    );
+    pub fn duckdb_copy_get_global_state(info: duckdb_copy_info) -> *mut ::std::os::raw::c_void;
+    pub fn duckdb_copy_set_local_state(
+        info: duckdb_copy_info,
+        local_state: *mut ::std::os::raw::c_void,
+        destroy: duckdb_delete_callback_t,
+    );
+    pub fn duckdb_copy_get_local_state(info: duckdb_copy_info) -> *mut ::std::os::raw::c_void;
+    pub fn duckdb_copy_set_error(info: duckdb_copy_info, error: *const ::std::os::raw::c_char);
+}
+pub type duckdb_file_system = *mut ::std::os::raw::c_void;
+pub type duckdb_file_system_info = *mut ::std::os::raw::c_void;
+pub type duckdb_glob_result = *mut ::std::os::raw::c_void;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct duckdb_file_stat {
+    pub size: idx_t,
+    pub last_modified: i64,
+    pub is_directory: bool,
+}
+pub type duckdb_file_system_open_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        info: duckdb_file_system_info,
+        path: *const ::std::os::raw::c_char,
+        flags: u8,
+    ) -> *mut ::std::os::raw::c_void,
+>;
+pub type duckdb_file_system_close_t = ::std::option::Option<
+    unsafe extern "C" fn(info: duckdb_file_system_info, handle: *mut ::std::os::raw::c_void),
+>;
+pub type duckdb_file_system_read_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        info: duckdb_file_system_info,
+        handle: *mut ::std::os::raw::c_void,
+        buffer: *mut ::std::os::raw::c_void,
+        nr_bytes: i64,
+        location: idx_t,
+    ) -> i64,
+>;
+pub type duckdb_file_system_write_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        info: duckdb_file_system_info,
+        handle: *mut ::std::os::raw::c_void,
+        buffer: *const ::std::os::raw::c_void,
+        nr_bytes: i64,
+        location: idx_t,
+    ) -> i64,
+>;
+pub type duckdb_file_system_stat_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        info: duckdb_file_system_info,
+        path: *const ::std::os::raw::c_char,
+        out_stat: *mut duckdb_file_stat,
+    ) -> bool,
+>;
+pub type duckdb_file_system_glob_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        info: duckdb_file_system_info,
+        pattern: *const ::std::os::raw::c_char,
+        result: duckdb_glob_result,
+    ),
+>;
+pub type duckdb_file_system_remove_t = ::std::option::Option<
+    unsafe extern "C" fn(info: duckdb_file_system_info, path: *const ::std::os::raw::c_char),
+>;
+extern "C" {
+    pub fn duckdb_create_file_system() -> duckdb_file_system;
+    pub fn duckdb_destroy_file_system(file_system: *mut duckdb_file_system);
+    pub fn duckdb_file_system_set_prefix(
+        file_system: duckdb_file_system,
+        prefix: *const ::std::os::raw::c_char,
+    );
+    pub fn duckdb_file_system_set_extra_info(
+        file_system: duckdb_file_system,
+        extra_info: *mut ::std::os::raw::c_void,
+        destroy: duckdb_delete_callback_t,
+    );
+    pub fn duckdb_file_system_set_open(
+        file_system: duckdb_file_system,
+        open: duckdb_file_system_open_t,
+    );
+    pub fn duckdb_file_system_set_close(
+        file_system: duckdb_file_system,
+        close: duckdb_file_system_close_t,
+    );
+    pub fn duckdb_file_system_set_read(
+        file_system: duckdb_file_system,
+        read: duckdb_file_system_read_t,
+    );
+    pub fn duckdb_file_system_set_write(
+        file_system: duckdb_file_system,
+        write: duckdb_file_system_write_t,
+    );
+    pub fn duckdb_file_system_set_stat(
+        file_system: duckdb_file_system,
+        stat: duckdb_file_system_stat_t,
+    );
+    pub fn duckdb_file_system_set_glob(
+        file_system: duckdb_file_system,
+        glob: duckdb_file_system_glob_t,
+    );
+    pub fn duckdb_file_system_set_remove(
+        file_system: duckdb_file_system,
+        remove: duckdb_file_system_remove_t,
+    );
+    pub fn duckdb_register_file_system(
+        database: duckdb_database,
+        file_system: duckdb_file_system,
+    ) -> duckdb_state;
+    pub fn duckdb_file_system_get_extra_info(
+        info: duckdb_file_system_info,
+    ) -> *mut ::std::os::raw::c_void;
+    pub fn duckdb_file_system_set_error(
+        info: duckdb_file_system_info,
+        error: *const ::std::os::raw::c_char,
+    );
+    pub fn duckdb_glob_result_add(result: duckdb_glob_result, path: *const ::std::os::raw::c_char);
+}
+pub type duckdb_storage_extension = *mut ::std::os::raw::c_void;
+pub type duckdb_storage_info = *mut ::std::os::raw::c_void;
+pub type duckdb_storage_attach_t =
+    ::std::option::Option<unsafe extern "C" fn(info: duckdb_storage_info)>;
+extern "C" {
+    pub fn duckdb_create_storage_extension() -> duckdb_storage_extension;
+    pub fn duckdb_destroy_storage_extension(storage_extension: *mut duckdb_storage_extension);
+    pub fn duckdb_storage_extension_set_name(
+        storage_extension: duckdb_storage_extension,
+        name: *const ::std::os::raw::c_char,
+    );
+    pub fn duckdb_storage_extension_set_scan_function(
+        storage_extension: duckdb_storage_extension,
+        function_name: *const ::std::os::raw::c_char,
+    );
+    pub fn duckdb_storage_extension_set_attach(
+        storage_extension: duckdb_storage_extension,
+        attach: duckdb_storage_attach_t,
+    );
+    pub fn duckdb_storage_extension_set_extra_info(
+        storage_extension: duckdb_storage_extension,
+        extra_info: *mut ::std::os::raw::c_void,
+        destroy: duckdb_delete_callback_t,
+    );
+    pub fn duckdb_register_storage_extension(
+        database: duckdb_database,
+        storage_extension: duckdb_storage_extension,
+    ) -> duckdb_state;
+    pub fn duckdb_unregister_storage_extension(
+        database: duckdb_database,
+        name: *const ::std::os::raw::c_char,
+    );
+    pub fn duckdb_storage_get_extra_info(info: duckdb_storage_info) -> *mut ::std::os::raw::c_void;
+    pub fn duckdb_storage_get_path(info: duckdb_storage_info) -> *const ::std::os::raw::c_char;
+    pub fn duckdb_storage_add_schema(
+        info: duckdb_storage_info,
+        schema: *const ::std::os::raw::c_char,
+    );
+    pub fn duckdb_storage_add_table(
+        info: duckdb_storage_info,
+        schema: *const ::std::os::raw::c_char,
+        table: *const ::std::os::raw::c_char,
+        column_count: idx_t,
+        names: *mut *const ::std::os::raw::c_char,
+        types: *const duckdb_logical_type,
+    );
+    pub fn duckdb_storage_set_error(
+        info: duckdb_storage_info,
+        error: *const ::std::os::raw::c_char,
+    );
+}
+pub type duckdb_extension_option_info = *mut ::std::os::raw::c_void;
+pub type duckdb_extension_option_validate_t = ::std::option::Option<
+    unsafe extern "C" fn(info: duckdb_extension_option_info, value: duckdb_value),
+>;
+extern "C" {
+    pub fn duckdb_add_extension_option(
+        database: duckdb_database,
+        name: *const ::std::os::raw::c_char,
+        description: *const ::std::os::raw::c_char,
+        type_: duckdb_logical_type,
+        default_value: duckdb_value,
+        validate: duckdb_extension_option_validate_t,
+        extra_info: *mut ::std::os::raw::c_void,
+        destroy: duckdb_delete_callback_t,
+    ) -> duckdb_state;
+    pub fn duckdb_extension_option_get_extra_info(
+        info: duckdb_extension_option_info,
+    ) -> *mut ::std::os::raw::c_void;
+    pub fn duckdb_extension_option_set_error(
+        info: duckdb_extension_option_info,
+        error: *const ::std::os::raw::c_char,
+    );
+    pub fn duckdb_get_extension_option(
+        connection: duckdb_connection,
+        name: *const ::std::os::raw::c_char,
+    ) -> duckdb_value;
+    pub fn duckdb_bind_get_extension_option(
+        info: duckdb_bind_info,
+        name: *const ::std::os::raw::c_char,
+    ) -> duckdb_value;
+}
+pub type duckdb_collation_info = *mut ::std::os::raw::c_void;
+pub type duckdb_collation_function_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        info: duckdb_collation_info,
+        input: duckdb_vector,
+        output: duckdb_vector,
+        count: idx_t,
+    ),
+>;
+extern "C" {
+    pub fn duckdb_register_collation(
+        con: duckdb_connection,
+        name: *const ::std::os::raw::c_char,
+        function: duckdb_collation_function_t,
+        extra_info: *mut ::std::os::raw::c_void,
+        destroy: duckdb_delete_callback_t,
+    ) -> duckdb_state;
+    pub fn duckdb_collation_get_extra_info(
+        info: duckdb_collation_info,
+    ) -> *mut ::std::os::raw::c_void;
+    pub fn duckdb_collation_set_error(
+        info: duckdb_collation_info,
+        error: *const ::std::os::raw::c_char,
+    );
+}
+pub type duckdb_extension_init_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        instance: *mut ::std::os::raw::c_void,
+        state: *mut ::std::os::raw::c_void,
+    ) -> *mut ::std::os::raw::c_char,
+>;
+extern "C" {
+    pub fn duckdb_database_from_instance(instance: *mut ::std::os::raw::c_void) -> duckdb_database;
+}
+extern "C" {
+    pub fn duckdb_destroy_database_ref(database: *mut duckdb_database);
+}
+extern "C" {
+    pub fn duckdb_extension_connect(
+        database: duckdb_database,
+        out: *mut duckdb_connection,
+        out_error: *mut *mut ::std::os::raw::c_char,
+    ) -> duckdb_state;
+}
@@ -0,0 +1,427 @@
+use crate::chunk_serde::in_field;
+use crate::duckly::{duckdb_list_entry, idx_t};
+use crate::rows::{string_bytes, value_at, DuckValue};
+use crate::{Error, LogicalType, LogicalTypeId, Vector};
+use serde::de::{DeserializeSeed, Deserializer, IntoDeserializer, MapAccess, SeqAccess, Visitor};
+use serde::forward_to_deserialize_any;
+
+/// Reads a row of the chunk's columns as a struct or tuple, the first column as the first field
+pub(super) struct RowDeserializer<'a> {
+    pub(super) columns: &'a [(Vector<()>, LogicalType)],
+    pub(super) row: idx_t,
+}
+
+impl<'a> RowDeserializer<'a> {
+    fn fields(self, names: Option<&'static [&'static str]>) -> RowFields<'a> {
+        RowFields {
+            columns: self.columns,
+            names,
+            row: self.row,
+            index: 0,
+        }
+    }
+
+    fn check_len(&self, len: usize) -> Result<(), Error> {
+        if len != self.columns.len() {
+            return Err(Error::Conversion(format!(
+                "the row has {} fields, but the chunk has {} columns",
+                len,
+                self.columns.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl<'de> Deserializer<'de> for RowDeserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(self.fields(None))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        self.check_len(len)?;
+        visitor.visit_seq(self.fields(None))
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.check_len(fields.len())?;
+        visitor.visit_map(self.fields(Some(fields)))
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option unit
+        unit_struct seq map enum identifier ignored_any
+    }
+}
+
+/// The columns of a row, as a sequence, or as a map with the names of the struct's fields
+struct RowFields<'a> {
+    columns: &'a [(Vector<()>, LogicalType)],
+    names: Option<&'static [&'static str]>,
+    row: idx_t,
+    index: usize,
+}
+
+impl RowFields<'_> {
+    fn name(&self) -> String {
+        match self.names {
+            Some(names) => names[self.index].to_string(),
+            None => self.index.to_string(),
+        }
+    }
+
+    fn next<'de, T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<T::Value, Error> {
+        let (vector, typ) = &self.columns[self.index];
+        let value = seed
+            .deserialize(ValueDeserializer {
+                vector,
+                typ,
+                row: self.row,
+            })
+            .map_err(|error| in_field(&self.name(), error))?;
+        self.index += 1;
+        Ok(value)
+    }
+}
+
+impl<'de> SeqAccess<'de> for RowFields<'_> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if self.index == self.columns.len() {
+            return Ok(None);
+        }
+        self.next(seed).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.columns.len() - self.index)
+    }
+}
+
+impl<'de> MapAccess<'de> for RowFields<'_> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        if self.index == self.columns.len() {
+            return Ok(None);
+        }
+        seed.deserialize(self.name().into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        self.next(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.columns.len() - self.index)
+    }
+}
+
+/// Reads the value at `row` of a vector, as the type of the vector
+struct ValueDeserializer<'a> {
+    vector: &'a Vector<()>,
+    typ: &'a LogicalType,
+    row: idx_t,
+}
+
+impl ValueDeserializer<'_> {
+    fn is_null(&self) -> bool {
+        !self.vector.get_validity().row_is_valid(self.row)
+    }
+
+    /// Reads the value as is
+    ///
+    /// # Safety
+    /// The vector must be of `T`'s type
+    unsafe fn get<T: DuckValue>(&self) -> Result<T, Error> {
+        T::read_valid(self.vector, self.row)
+    }
+
+    /// Reads a `VARCHAR` or `ENUM` value
+    fn string(&self) -> Result<String, Error> {
+        match self.typ.type_id() {
+            LogicalTypeId::Varchar => unsafe { self.get() },
+            LogicalTypeId::Enum => {
+                let index = self.vector.get_enum(self.row)?;
+                let values = self.typ.enum_values().expect("enum type");
+                values.get(index as usize).cloned().ok_or_else(|| {
+                    Error::Conversion(format!("{} is outside the values of {}", index, self.typ))
+                })
+            }
+            _ => Err(Error::Conversion(format!(
+                "can't read a string from a column of type {}",
+                self.typ
+            ))),
+        }
+    }
+
+    fn list_entry(&self) -> duckdb_list_entry {
+        unsafe { *value_at(self.vector, self.row) }
+    }
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.is_null() {
+            return visitor.visit_unit();
+        }
+        unsafe {
+            match self.typ.type_id() {
+                LogicalTypeId::Boolean => visitor.visit_bool(self.get()?),
+                LogicalTypeId::Tinyint => visitor.visit_i8(self.get()?),
+                LogicalTypeId::Smallint => visitor.visit_i16(self.get()?),
+                LogicalTypeId::Integer | LogicalTypeId::Date => visitor.visit_i32(self.get()?),
+                LogicalTypeId::Bigint
+                | LogicalTypeId::Time
+                | LogicalTypeId::Timestamp
+                | LogicalTypeId::TimestampS
+                | LogicalTypeId::TimestampMs
+                | LogicalTypeId::TimestampNs => visitor.visit_i64(self.get()?),
+                LogicalTypeId::Hugeint => visitor.visit_i128(self.get()?),
+                LogicalTypeId::Utinyint => visitor.visit_u8(self.get()?),
+                LogicalTypeId::Usmallint => visitor.visit_u16(self.get()?),
+                LogicalTypeId::Uinteger => visitor.visit_u32(self.get()?),
+                LogicalTypeId::Ubigint => visitor.visit_u64(self.get()?),
+                LogicalTypeId::Float => visitor.visit_f32(self.get()?),
+                LogicalTypeId::Double => visitor.visit_f64(self.get()?),
+                LogicalTypeId::Decimal => {
+                    let unscaled = self.vector.get_decimal(self.row)?;
+                    let scale = self.typ.decimal_scale().expect("decimal type");
+                    visitor.visit_f64(unscaled as f64 / 10f64.powi(scale.into()))
+                }
+                LogicalTypeId::Varchar | LogicalTypeId::Enum => {
+                    visitor.visit_string(self.string()?)
+                }
+                LogicalTypeId::Blob => {
+                    visitor.visit_byte_buf(string_bytes(self.vector, self.row).to_vec())
+                }
+                LogicalTypeId::List => {
+                    let entry = self.list_entry();
+                    visitor.visit_seq(ListAccess {
+                        child: self.vector.list_child(),
+                        child_type: self.typ.list_child_type().expect("list type"),
+                        offset: entry.offset,
+                        length: entry.length,
+                        index: 0,
+                    })
+                }
+                LogicalTypeId::Map => {
+                    let entry = self.list_entry();
+                    // a map is stored as a list of key and value structs
+                    let entries = self.vector.list_child();
+                    visitor.visit_map(MapEntries {
+                        keys: entries.struct_child(0),
+                        values: entries.struct_child(1),
+                        key_type: self.typ.map_key_type().expect("map type"),
+                        value_type: self.typ.map_value_type().expect("map type"),
+                        offset: entry.offset,
+                        length: entry.length,
+                        index: 0,
+                    })
+                }
+                LogicalTypeId::Struct => {
+                    let fields = self.typ.struct_fields().expect("struct type");
+                    visitor.visit_map(StructFields {
+                        children: (0..fields.len())
+                            .map(|index| self.vector.struct_child(index as idx_t))
+                            .collect(),
+                        fields,
+                        row: self.row,
+                        index: 0,
+                    })
+                }
+                _ => Err(Error::Conversion(format!(
+                    "reading a column of type {} isn't supported",
+                    self.typ
+                ))),
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.is_null() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        // only unit variants can be stored, as their name
+        visitor.visit_enum(self.string()?.into_deserializer())
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit
+        unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// The elements of a list, which are stored in the list vector's child
+struct ListAccess {
+    child: Vector<()>,
+    child_type: LogicalType,
+    offset: idx_t,
+    length: idx_t,
+    index: idx_t,
+}
+
+impl<'de> SeqAccess<'de> for ListAccess {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if self.index == self.length {
+            return Ok(None);
+        }
+        let value = seed
+            .deserialize(ValueDeserializer {
+                vector: &self.child,
+                typ: &self.child_type,
+                row: self.offset + self.index,
+            })
+            .map_err(|error| in_field(&format!("[{}]", self.index), error))?;
+        self.index += 1;
+        Ok(Some(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some((self.length - self.index) as usize)
+    }
+}
+
+/// The entries of a map, whose keys and values are stored in the fields of the map vector's child
+struct MapEntries {
+    keys: Vector<()>,
+    values: Vector<()>,
+    key_type: LogicalType,
+    value_type: LogicalType,
+    offset: idx_t,
+    length: idx_t,
+    index: idx_t,
+}
+
+impl<'de> MapAccess<'de> for MapEntries {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        if self.index == self.length {
+            return Ok(None);
+        }
+        seed.deserialize(ValueDeserializer {
+            vector: &self.keys,
+            typ: &self.key_type,
+            row: self.offset + self.index,
+        })
+        .map(Some)
+        .map_err(|error| in_field(&format!("[{}]", self.index), error))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let value = seed
+            .deserialize(ValueDeserializer {
+                vector: &self.values,
+                typ: &self.value_type,
+                row: self.offset + self.index,
+            })
+            .map_err(|error| in_field(&format!("[{}]", self.index), error))?;
+        self.index += 1;
+        Ok(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some((self.length - self.index) as usize)
+    }
+}
+
+/// The fields of a struct, by their names in its type
+struct StructFields {
+    fields: Vec<(String, LogicalType)>,
+    children: Vec<Vector<()>>,
+    row: idx_t,
+    index: usize,
+}
+
+impl<'de> MapAccess<'de> for StructFields {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.fields.get(self.index) {
+            Some((name, _)) => seed
+                .deserialize(name.as_str().into_deserializer())
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let (name, typ) = &self.fields[self.index];
+        let value = seed
+            .deserialize(ValueDeserializer {
+                vector: &self.children[self.index],
+                typ,
+                row: self.row,
+            })
+            .map_err(|error| in_field(name, error))?;
+        self.index += 1;
+        Ok(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.fields.len() - self.index)
+    }
+}
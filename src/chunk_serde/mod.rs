@@ -0,0 +1,354 @@
+//! Writes [`serde::Serialize`] values to the rows of a [`DataChunk`] and reads them back as [`serde::Deserialize`]
+//! values, with the `serde` feature
+//!
+//! The fields of a row are the chunk's columns in order, so a struct's first field is written to its first column.
+//! Their values are converted to the type of their column as it was declared in the bind:
+//!
+//! | DuckDB                                            | serde                                              |
+//! |---------------------------------------------------|----------------------------------------------------|
+//! | `BOOLEAN`                                         | `bool`                                             |
+//! | `TINYINT` .. `HUGEINT`, `UTINYINT` .. `UBIGINT`   | any integer that fits                              |
+//! | `FLOAT`, `DOUBLE`                                 | `f32`, `f64` or an integer                         |
+//! | `DECIMAL`                                         | `f64` or an integer, read as `f64`                 |
+//! | `DATE`                                            | days since 1970-01-01 as an integer                |
+//! | `TIME`, `TIMESTAMP`                               | microseconds as an integer                         |
+//! | `TIMESTAMP_S`, `TIMESTAMP_MS`, `TIMESTAMP_NS`     | seconds, milliseconds or nanoseconds as an integer |
+//! | `VARCHAR`                                         | a string, or a unit enum variant                   |
+//! | `BLOB`                                            | bytes, such as `serde_bytes::ByteBuf`              |
+//! | `ENUM`                                            | one of its values, or a unit enum variant          |
+//! | `T[]`                                             | a sequence, such as `Vec<T>`                       |
+//! | `MAP(K, V)`                                       | a map, such as `HashMap<K, V>`                     |
+//! | `STRUCT(..)`                                      | a struct, whose fields are matched by name         |
+//! | `NULL`                                            | `None` or `()`                                     |
+//!
+//! A value that doesn't fit its column fails with an [`Error::Field`] naming the field.
+//!
+//! ```ignore
+//! #[derive(Serialize)]
+//! struct Commit {
+//!     hash: String,
+//!     parents: Vec<String>,
+//! }
+//!
+//! let mut serializer = DataChunkSerializer::new(&output);
+//! for commit in commits {
+//!     serializer.serialize(&commit)?;
+//! }
+//! ```
+
+mod de;
+mod ser;
+
+use crate::duckly::idx_t;
+use crate::{DataChunk, Error, LogicalType, Vector};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Display;
+
+/// Writes values to consecutive rows of a [`DataChunk`], see the [module documentation](self)
+pub struct DataChunkSerializer<'a> {
+    chunk: &'a DataChunk,
+    columns: Vec<(Vector<()>, LogicalType)>,
+    rows: idx_t,
+}
+
+impl<'a> DataChunkSerializer<'a> {
+    /// Starts writing rows to the start of `chunk`
+    pub fn new(chunk: &'a DataChunk) -> Self {
+        Self {
+            chunk,
+            columns: columns(chunk),
+            rows: 0,
+        }
+    }
+
+    /// Writes `value` to the next row of the chunk, and includes it in the chunk's size.
+    ///
+    /// # Arguments
+    /// * `value`: A struct or tuple, with a field for each column
+    /// * `returns`: [`Error::Conversion`] if the chunk is full or `value` doesn't have a field for each column, or
+    ///   [`Error::Field`] if a field doesn't fit its column
+    pub fn serialize<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let capacity = unsafe { crate::duckly::duckdb_vector_size() };
+        if self.rows >= capacity {
+            return Err(Error::Conversion(format!(
+                "the chunk is full with {} rows",
+                capacity
+            )));
+        }
+        value.serialize(ser::RowSerializer {
+            columns: &self.columns,
+            row: self.rows,
+        })?;
+        self.rows += 1;
+        self.chunk.set_size(self.rows);
+        Ok(())
+    }
+
+    /// Returns the number of rows written so far
+    pub fn rows(&self) -> idx_t {
+        self.rows
+    }
+}
+
+/// Reads the rows of a [`DataChunk`] as values, see the [module documentation](self)
+pub struct DataChunkDeserializer<'a> {
+    chunk: &'a DataChunk,
+    columns: Vec<(Vector<()>, LogicalType)>,
+}
+
+impl<'a> DataChunkDeserializer<'a> {
+    /// Starts reading the rows of `chunk`
+    pub fn new(chunk: &'a DataChunk) -> Self {
+        Self {
+            chunk,
+            columns: columns(chunk),
+        }
+    }
+
+    /// Reads `row` of the chunk.
+    ///
+    /// # Arguments
+    /// * `row`: The index of the row, less than the chunk's size
+    /// * `returns`: [`Error::Conversion`] if there's no such row or `T` doesn't have a field for each column, or
+    ///   [`Error::Field`] if a field can't be read from its column
+    pub fn deserialize<T: DeserializeOwned>(&self, row: idx_t) -> Result<T, Error> {
+        let size = self.chunk.get_size();
+        if row >= size {
+            return Err(Error::Conversion(format!(
+                "row {} is out of bounds for {} rows",
+                row, size
+            )));
+        }
+        T::deserialize(de::RowDeserializer {
+            columns: &self.columns,
+            row,
+        })
+    }
+
+    /// Reads every row of the chunk, see [`DataChunkDeserializer::deserialize`]
+    pub fn rows<T: DeserializeOwned>(&self) -> Result<Vec<T>, Error> {
+        (0..self.chunk.get_size())
+            .map(|row| self.deserialize(row))
+            .collect()
+    }
+}
+
+fn columns(chunk: &DataChunk) -> Vec<(Vector<()>, LogicalType)> {
+    (0..chunk.get_column_count())
+        .map(|index| {
+            let vector = chunk.get_untyped_vector(index);
+            let typ = vector.get_column_type();
+            (vector, typ)
+        })
+        .collect()
+}
+
+impl serde::ser::Error for Error {
+    fn custom<T: Display>(message: T) -> Self {
+        Self::Conversion(message.to_string())
+    }
+}
+
+impl serde::de::Error for Error {
+    fn custom<T: Display>(message: T) -> Self {
+        Self::Conversion(message.to_string())
+    }
+}
+
+/// Adds `field` to the start of the path of the field `error` is about, or makes it the path if there isn't one yet
+fn in_field(field: &str, error: Error) -> Error {
+    match error {
+        Error::Field { path, source } => {
+            let separator = if path.starts_with('[') { "" } else { "." };
+            Error::Field {
+                path: format!("{}{}{}", field, separator, path),
+                source,
+            }
+        }
+        error => Error::Field {
+            path: field.to_string(),
+            source: Box::new(error),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::chunk_serde::{DataChunkDeserializer, DataChunkSerializer};
+    use crate::duckly::duckdb_list_entry;
+    use crate::rows::value_at;
+    use crate::{DataChunk, Error, LogicalType};
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Author {
+        name: String,
+        email: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Kind {
+        Merge,
+        Commit,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Commit {
+        hash: String,
+        kind: Kind,
+        parents: Vec<String>,
+        author: Option<Author>,
+        additions: u32,
+        price: f64,
+        labels: BTreeMap<String, i64>,
+    }
+
+    fn chunk() -> Result<DataChunk, Error> {
+        Ok(DataChunk::new(vec![
+            "VARCHAR".parse()?,
+            "ENUM('Merge', 'Commit')".parse()?,
+            "VARCHAR[]".parse()?,
+            "STRUCT(email VARCHAR, name VARCHAR)".parse()?,
+            "BIGINT".parse()?,
+            "DECIMAL(10,2)".parse()?,
+            "MAP(VARCHAR, BIGINT)".parse()?,
+        ]))
+    }
+
+    fn commits() -> Vec<Commit> {
+        vec![
+            Commit {
+                hash: "6a3c0b5d".to_string(),
+                kind: Kind::Commit,
+                parents: vec![
+                    "b5".to_string(),
+                    "a parent hash that isn't inlined".to_string(),
+                ],
+                author: Some(Author {
+                    name: "Ada".to_string(),
+                    email: None,
+                }),
+                additions: 12,
+                price: 10.25,
+                labels: [("size".to_string(), 3), ("risk".to_string(), -1)]
+                    .into_iter()
+                    .collect(),
+            },
+            Commit {
+                hash: "b5".to_string(),
+                kind: Kind::Merge,
+                parents: vec![],
+                author: None,
+                additions: 0,
+                price: -0.5,
+                labels: BTreeMap::new(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<(), Error> {
+        let chunk = chunk()?;
+        let mut serializer = DataChunkSerializer::new(&chunk);
+        for commit in commits() {
+            serializer.serialize(&commit)?;
+        }
+        assert_eq!(serializer.rows(), 2);
+        assert_eq!(chunk.get_size(), 2);
+
+        let deserializer = DataChunkDeserializer::new(&chunk);
+        assert_eq!(deserializer.rows::<Commit>()?, commits());
+        assert!(matches!(
+            deserializer.deserialize::<(String, String)>(0),
+            Err(Error::Conversion(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_null_list() -> Result<(), Error> {
+        let chunk = DataChunk::new(vec!["INTEGER[]".parse()?]);
+        let mut serializer = DataChunkSerializer::new(&chunk);
+        serializer.serialize(&(Some(vec![1, 2]),))?;
+        serializer.serialize(&(None::<Vec<i32>>,))?;
+
+        let entry = unsafe { *value_at::<duckdb_list_entry>(&chunk.get_untyped_vector(0), 1) };
+        assert_eq!((entry.offset, entry.length), (2, 0));
+        let rows: Vec<(Option<Vec<i32>>,)> = DataChunkDeserializer::new(&chunk).rows()?;
+        assert_eq!(rows, [(Some(vec![1, 2]),), (None,)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tuples() -> Result<(), Error> {
+        let chunk = DataChunk::new(vec!["INTEGER".parse()?, "DOUBLE[]".parse()?]);
+        let mut serializer = DataChunkSerializer::new(&chunk);
+        serializer.serialize(&(1u8, [0.5f32, 1.5]))?;
+        serializer.serialize(&(-2i64, Vec::<f64>::new()))?;
+
+        let rows: Vec<(i32, Vec<f64>)> = DataChunkDeserializer::new(&chunk).rows()?;
+        assert_eq!(rows, [(1, vec![0.5, 1.5]), (-2, vec![])]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_mismatches() -> Result<(), Error> {
+        #[derive(Serialize)]
+        struct Row<T> {
+            value: T,
+        }
+
+        fn write<T: Serialize>(typ: &str, value: T) -> Result<String, Error> {
+            let chunk = DataChunk::new(vec![typ.parse::<LogicalType>()?]);
+            let written = DataChunkSerializer::new(&chunk).serialize(&Row { value });
+            Ok(written.map_err(|error| error.to_string()).err().unwrap())
+        }
+
+        assert_eq!(
+            write("VARCHAR", 5)?,
+            "field value: conversion failed: can't write an integer to a column of type VARCHAR"
+        );
+        assert_eq!(
+            write("TINYINT", 300)?,
+            "field value: conversion failed: 300 is out of range for TINYINT"
+        );
+        assert_eq!(
+            write("INTEGER[]", vec!["1"])?,
+            "field value[0]: conversion failed: can't write a string to a column of type INTEGER"
+        );
+        assert_eq!(
+            write("STRUCT(a INTEGER)", Row { value: 1 })?,
+            "field value.value: conversion failed: not a field of STRUCT(a INTEGER)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_mismatches() -> Result<(), Error> {
+        #[derive(Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Row {
+            name: String,
+            tags: Vec<u8>,
+        }
+
+        let chunk = DataChunk::new(vec!["VARCHAR".parse()?, "BIGINT[]".parse()?]);
+        DataChunkSerializer::new(&chunk).serialize(&(Some("a"), [1, 300]))?;
+        let error = DataChunkDeserializer::new(&chunk)
+            .deserialize::<Row>(0)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "field tags[1]: conversion failed: invalid value: integer `300`, expected u8"
+        );
+
+        DataChunkSerializer::new(&chunk).serialize(&(None::<&str>, [1]))?;
+        let error = DataChunkDeserializer::new(&chunk)
+            .deserialize::<Row>(0)
+            .unwrap_err();
+        assert!(matches!(error, Error::Field { path, .. } if path == "name"));
+        Ok(())
+    }
+}
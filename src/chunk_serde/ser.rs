@@ -0,0 +1,760 @@
+use crate::chunk_serde::in_field;
+use crate::duckly::{duckdb_list_entry, idx_t};
+use crate::rows::{value_at, DuckValue};
+use crate::{Error, LogicalType, LogicalTypeId, Vector};
+use serde::ser::{
+    Impossible, SerializeMap, SerializeSeq, SerializeStruct, SerializeTuple, SerializeTupleStruct,
+};
+use serde::{Serialize, Serializer};
+
+fn not_a_row(found: &str) -> Error {
+    Error::Conversion(format!("a row must be a struct or tuple, found {}", found))
+}
+
+macro_rules! not_a_row {
+    ($($method:ident($($ty:ty),*) => $found:literal,)*) => {
+        $(
+            fn $method(self, $(_: $ty),*) -> Result<Self::Ok, Error> {
+                Err(not_a_row($found))
+            }
+        )*
+    };
+}
+
+/// Writes a struct or tuple to a row of the chunk's columns, the first field to the first column
+pub(super) struct RowSerializer<'a> {
+    pub(super) columns: &'a [(Vector<()>, LogicalType)],
+    pub(super) row: idx_t,
+}
+
+impl<'a> RowSerializer<'a> {
+    fn fields(self, len: usize) -> Result<RowFields<'a>, Error> {
+        if len != self.columns.len() {
+            return Err(Error::Conversion(format!(
+                "the row has {} fields, but the chunk has {} columns",
+                len,
+                self.columns.len()
+            )));
+        }
+        Ok(RowFields {
+            columns: self.columns,
+            row: self.row,
+            index: 0,
+        })
+    }
+}
+
+impl<'a> Serializer for RowSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = RowFields<'a>;
+    type SerializeTupleStruct = RowFields<'a>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = RowFields<'a>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    not_a_row! {
+        serialize_bool(bool) => "a boolean",
+        serialize_i8(i8) => "an integer",
+        serialize_i16(i16) => "an integer",
+        serialize_i32(i32) => "an integer",
+        serialize_i64(i64) => "an integer",
+        serialize_i128(i128) => "an integer",
+        serialize_u8(u8) => "an integer",
+        serialize_u16(u16) => "an integer",
+        serialize_u32(u32) => "an integer",
+        serialize_u64(u64) => "an integer",
+        serialize_u128(u128) => "an integer",
+        serialize_f32(f32) => "a float",
+        serialize_f64(f64) => "a float",
+        serialize_char(char) => "a character",
+        serialize_str(&str) => "a string",
+        serialize_bytes(&[u8]) => "bytes",
+        serialize_none() => "None",
+        serialize_unit() => "()",
+        serialize_unit_struct(&'static str) => "a unit struct",
+        serialize_unit_variant(&'static str, u32, &'static str) => "an enum variant",
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), Error> {
+        Err(not_a_row("an enum variant"))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(not_a_row("a sequence"))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Error> {
+        self.fields(len)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        self.fields(len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(not_a_row("an enum variant"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(not_a_row("a map"))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        self.fields(len)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(not_a_row("an enum variant"))
+    }
+}
+
+pub(super) struct RowFields<'a> {
+    columns: &'a [(Vector<()>, LogicalType)],
+    row: idx_t,
+    index: usize,
+}
+
+impl<'a> RowFields<'a> {
+    fn field<T: Serialize + ?Sized>(&mut self, name: &str, value: &T) -> Result<(), Error> {
+        // the length was checked up front, but a hand written implementation could serialize more fields than it said
+        let (vector, typ) = self.columns.get(self.index).ok_or_else(|| {
+            Error::Conversion(format!(
+                "the row has more fields than the chunk's {} columns",
+                self.columns.len()
+            ))
+        })?;
+        value
+            .serialize(ValueSerializer {
+                vector,
+                typ,
+                row: self.row,
+            })
+            .map_err(|error| in_field(name, error))?;
+        self.index += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Error> {
+        if self.index != self.columns.len() {
+            return Err(Error::Conversion(format!(
+                "the row has {} fields, but the chunk has {} columns",
+                self.index,
+                self.columns.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl SerializeStruct for RowFields<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        RowFields::end(self)
+    }
+}
+
+impl SerializeTuple for RowFields<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.field(&self.index.to_string(), value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        RowFields::end(self)
+    }
+}
+
+impl SerializeTupleStruct for RowFields<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.field(&self.index.to_string(), value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        RowFields::end(self)
+    }
+}
+
+/// Writes a value to `row` of a vector, converting it to the vector's type
+struct ValueSerializer<'a> {
+    vector: &'a Vector<()>,
+    typ: &'a LogicalType,
+    row: idx_t,
+}
+
+impl<'a> ValueSerializer<'a> {
+    fn mismatch(&self, found: &str) -> Error {
+        Error::Conversion(format!(
+            "can't write {} to a column of type {}",
+            found, self.typ
+        ))
+    }
+
+    fn out_of_range(&self, value: impl std::fmt::Display) -> Error {
+        Error::Conversion(format!("{} is out of range for {}", value, self.typ))
+    }
+
+    fn narrow<T: TryFrom<i128>>(&self, value: i128) -> Result<T, Error> {
+        T::try_from(value).map_err(|_| self.out_of_range(value))
+    }
+
+    /// Writes `value` as is
+    ///
+    /// # Safety
+    /// The vector must be of `T`'s type
+    unsafe fn put<T: DuckValue>(&self, value: T) -> Result<(), Error> {
+        value.write(self.vector, self.row)
+    }
+
+    /// Copies `value` into a `VARCHAR` or `BLOB` vector
+    fn assign(&self, value: &[u8]) {
+        unsafe {
+            self.vector.assign_string_element_len(
+                self.row,
+                value.as_ptr().cast(),
+                value.len() as idx_t,
+            )
+        }
+    }
+
+    fn scale(&self) -> u8 {
+        self.typ.decimal_scale().expect("decimal type")
+    }
+
+    fn write_integer(self, value: i128) -> Result<(), Error> {
+        unsafe {
+            match self.typ.type_id() {
+                LogicalTypeId::Tinyint => self.put(self.narrow::<i8>(value)?),
+                LogicalTypeId::Smallint => self.put(self.narrow::<i16>(value)?),
+                LogicalTypeId::Integer | LogicalTypeId::Date => {
+                    self.put(self.narrow::<i32>(value)?)
+                }
+                LogicalTypeId::Bigint
+                | LogicalTypeId::Time
+                | LogicalTypeId::Timestamp
+                | LogicalTypeId::TimestampS
+                | LogicalTypeId::TimestampMs
+                | LogicalTypeId::TimestampNs => self.put(self.narrow::<i64>(value)?),
+                LogicalTypeId::Hugeint => self.put(value),
+                LogicalTypeId::Utinyint => self.put(self.narrow::<u8>(value)?),
+                LogicalTypeId::Usmallint => self.put(self.narrow::<u16>(value)?),
+                LogicalTypeId::Uinteger => self.put(self.narrow::<u32>(value)?),
+                LogicalTypeId::Ubigint => self.put(self.narrow::<u64>(value)?),
+                LogicalTypeId::Float => self.put(value as f32),
+                LogicalTypeId::Double => self.put(value as f64),
+                LogicalTypeId::Decimal => {
+                    let unscaled = 10i128
+                        .checked_pow(self.scale().into())
+                        .and_then(|factor| value.checked_mul(factor))
+                        .ok_or_else(|| self.out_of_range(value))?;
+                    self.vector
+                        .set_decimal(self.row, unscaled)
+                        .map_err(|_| self.out_of_range(value))
+                }
+                _ => Err(self.mismatch("an integer")),
+            }
+        }
+    }
+
+    fn write_float(self, value: f64) -> Result<(), Error> {
+        unsafe {
+            match self.typ.type_id() {
+                LogicalTypeId::Float => self.put(value as f32),
+                LogicalTypeId::Double => self.put(value),
+                LogicalTypeId::Decimal => {
+                    let unscaled = (value * 10f64.powi(self.scale().into())).round();
+                    if !unscaled.is_finite() || unscaled.abs() >= 1e38 {
+                        return Err(self.out_of_range(value));
+                    }
+                    self.vector
+                        .set_decimal(self.row, unscaled as i128)
+                        .map_err(|_| self.out_of_range(value))
+                }
+                _ => Err(self.mismatch("a float")),
+            }
+        }
+    }
+}
+
+/// Writes a `NULL` to `row` of a vector, and to the fields of a struct, which DuckDB expects to be `NULL` too
+fn write_null(vector: &Vector<()>, typ: &LogicalType, row: idx_t) {
+    if matches!(typ.type_id(), LogicalTypeId::List | LogicalTypeId::Map) {
+        // DuckDB reads the entries of NULL lists too, so this one must not point past the child vector
+        unsafe {
+            *value_at(vector, row) = duckdb_list_entry {
+                offset: vector.list_size(),
+                length: 0,
+            };
+        }
+    }
+    vector.ensure_validity_writable();
+    vector.get_validity().set_row_invalid(row);
+    if let Some(fields) = typ.struct_fields() {
+        for (index, (_, field)) in fields.iter().enumerate() {
+            write_null(&vector.struct_child(index as idx_t), field, row);
+        }
+    }
+}
+
+impl<'a> Serializer for ValueSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = ListSerializer<'a>;
+    type SerializeTuple = ListSerializer<'a>;
+    type SerializeTupleStruct = ListSerializer<'a>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = MapSerializer<'a>;
+    type SerializeStruct = StructSerializer<'a>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    fn serialize_bool(self, value: bool) -> Result<(), Error> {
+        match self.typ.type_id() {
+            LogicalTypeId::Boolean => unsafe { self.put(value) },
+            _ => Err(self.mismatch("a boolean")),
+        }
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<(), Error> {
+        self.write_integer(value.into())
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<(), Error> {
+        self.write_integer(value.into())
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<(), Error> {
+        self.write_integer(value.into())
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<(), Error> {
+        self.write_integer(value.into())
+    }
+
+    fn serialize_i128(self, value: i128) -> Result<(), Error> {
+        self.write_integer(value)
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<(), Error> {
+        self.write_integer(value.into())
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<(), Error> {
+        self.write_integer(value.into())
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<(), Error> {
+        self.write_integer(value.into())
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<(), Error> {
+        self.write_integer(value.into())
+    }
+
+    fn serialize_u128(self, value: u128) -> Result<(), Error> {
+        let value = i128::try_from(value).map_err(|_| self.out_of_range(value))?;
+        self.write_integer(value)
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<(), Error> {
+        self.write_float(value.into())
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<(), Error> {
+        self.write_float(value)
+    }
+
+    fn serialize_char(self, value: char) -> Result<(), Error> {
+        self.serialize_str(value.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<(), Error> {
+        match self.typ.type_id() {
+            LogicalTypeId::Varchar | LogicalTypeId::Blob => {
+                self.assign(value.as_bytes());
+                Ok(())
+            }
+            LogicalTypeId::Enum => {
+                let values = self.typ.enum_values().expect("enum type");
+                match values.iter().position(|candidate| candidate == value) {
+                    Some(index) => self.vector.set_enum(self.row, index as u32),
+                    None => Err(Error::Conversion(format!(
+                        "{:?} isn't one of the values of {}",
+                        value, self.typ
+                    ))),
+                }
+            }
+            _ => Err(self.mismatch("a string")),
+        }
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<(), Error> {
+        match self.typ.type_id() {
+            LogicalTypeId::Blob => {
+                self.assign(value);
+                Ok(())
+            }
+            _ => Err(self.mismatch("bytes")),
+        }
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        write_null(self.vector, self.typ, self.row);
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.serialize_none()
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        self.serialize_none()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        match self.typ.type_id() {
+            LogicalTypeId::Varchar | LogicalTypeId::Enum => self.serialize_str(variant),
+            _ => Err(self.mismatch("an enum variant")),
+        }
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), Error> {
+        Err(self.mismatch("an enum variant with data"))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        let child_type = match self.typ.list_child_type() {
+            Some(child_type) => child_type,
+            None => return Err(self.mismatch("a sequence")),
+        };
+        Ok(ListSerializer {
+            offset: self.vector.list_size(),
+            length: 0,
+            child: self.vector.list_child(),
+            child_type,
+            vector: self.vector,
+            row: self.row,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(self.mismatch("an enum variant with data"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        let (key_type, value_type) = match (self.typ.map_key_type(), self.typ.map_value_type()) {
+            (Some(key_type), Some(value_type)) => (key_type, value_type),
+            _ => return Err(self.mismatch("a map")),
+        };
+        // a map is stored as a list of key and value structs
+        let entries = self.vector.list_child();
+        Ok(MapSerializer {
+            offset: self.vector.list_size(),
+            length: 0,
+            keys: entries.struct_child(0),
+            values: entries.struct_child(1),
+            key_type,
+            value_type,
+            vector: self.vector,
+            row: self.row,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        let fields = match self.typ.struct_fields() {
+            Some(fields) => fields,
+            None => return Err(self.mismatch("a struct")),
+        };
+        Ok(StructSerializer {
+            children: (0..fields.len())
+                .map(|index| self.vector.struct_child(index as idx_t))
+                .collect(),
+            written: vec![false; fields.len()],
+            fields,
+            typ: self.typ,
+            row: self.row,
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(self.mismatch("an enum variant with data"))
+    }
+}
+
+/// Appends the elements of a list to the list vector's child, and points its row at them
+pub(super) struct ListSerializer<'a> {
+    vector: &'a Vector<()>,
+    row: idx_t,
+    child: Vector<()>,
+    child_type: LogicalType,
+    offset: idx_t,
+    length: idx_t,
+}
+
+impl ListSerializer<'_> {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let index = self.offset + self.length;
+        self.vector.reserve_list(index + 1)?;
+        value
+            .serialize(ValueSerializer {
+                vector: &self.child,
+                typ: &self.child_type,
+                row: index,
+            })
+            .map_err(|error| in_field(&format!("[{}]", self.length), error))?;
+        self.length += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.vector.set_list_size(self.offset + self.length)?;
+        unsafe {
+            *value_at(self.vector, self.row) = duckdb_list_entry {
+                offset: self.offset,
+                length: self.length,
+            };
+        }
+        Ok(())
+    }
+}
+
+impl SerializeSeq for ListSerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        ListSerializer::end(self)
+    }
+}
+
+impl SerializeTuple for ListSerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        ListSerializer::end(self)
+    }
+}
+
+impl SerializeTupleStruct for ListSerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        ListSerializer::end(self)
+    }
+}
+
+/// Appends the entries of a map to the map vector's child, and points its row at them
+pub(super) struct MapSerializer<'a> {
+    vector: &'a Vector<()>,
+    row: idx_t,
+    keys: Vector<()>,
+    values: Vector<()>,
+    key_type: LogicalType,
+    value_type: LogicalType,
+    offset: idx_t,
+    length: idx_t,
+}
+
+impl SerializeMap for MapSerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        let index = self.offset + self.length;
+        self.vector.reserve_list(index + 1)?;
+        key.serialize(ValueSerializer {
+            vector: &self.keys,
+            typ: &self.key_type,
+            row: index,
+        })
+        .map_err(|error| in_field(&format!("[{}]", self.length), error))
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        value
+            .serialize(ValueSerializer {
+                vector: &self.values,
+                typ: &self.value_type,
+                row: self.offset + self.length,
+            })
+            .map_err(|error| in_field(&format!("[{}]", self.length), error))?;
+        self.length += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.vector.set_list_size(self.offset + self.length)?;
+        unsafe {
+            *value_at(self.vector, self.row) = duckdb_list_entry {
+                offset: self.offset,
+                length: self.length,
+            };
+        }
+        Ok(())
+    }
+}
+
+/// Writes the fields of a struct to the struct vector's children with the same names, and `NULL` to the rest
+pub(super) struct StructSerializer<'a> {
+    typ: &'a LogicalType,
+    row: idx_t,
+    fields: Vec<(String, LogicalType)>,
+    children: Vec<Vector<()>>,
+    written: Vec<bool>,
+}
+
+impl SerializeStruct for StructSerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        let index = match self.fields.iter().position(|(name, _)| name == key) {
+            Some(index) => index,
+            None => {
+                let error = Error::Conversion(format!("not a field of {}", self.typ));
+                return Err(in_field(key, error));
+            }
+        };
+        value
+            .serialize(ValueSerializer {
+                vector: &self.children[index],
+                typ: &self.fields[index].1,
+                row: self.row,
+            })
+            .map_err(|error| in_field(key, error))?;
+        self.written[index] = true;
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Error> {
+        for (index, written) in self.written.iter().enumerate() {
+            if !written {
+                write_null(&self.children[index], &self.fields[index].1, self.row);
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,14 +1,15 @@
 use std::{
-    ffi::CString,
-    ptr::{addr_of_mut, null_mut},
+    ffi::{c_char, CStr},
+    ptr::{addr_of_mut, null, null_mut},
 };
 
 use crate::{
-    check,
     duckly::{
         duckdb_config, duckdb_config_count, duckdb_create_config, duckdb_destroy_config,
-        duckdb_get_config_flag, duckdb_set_config,
+        duckdb_get_config_flag, duckdb_set_config, duckdb_state_DuckDBSuccess,
     },
+    strings::with_c_strs,
+    Error,
 };
 
 pub struct Config(pub(crate) duckdb_config);
@@ -33,17 +34,22 @@ impl Iterator for ConfigList {
     }
 }
 impl ConfigList {
-    fn try_next(&mut self) -> Result<ConfigItem, Box<dyn std::error::Error>> {
-        let name = CString::new("")?;
-        let desc = CString::new("")?;
-
-        let mut name_ptr = name.as_ptr();
-        let mut desc_ptr = desc.as_ptr();
-        check!(unsafe {
+    fn try_next(&mut self) -> Result<ConfigItem, Error> {
+        let mut name_ptr: *const c_char = null();
+        let mut desc_ptr: *const c_char = null();
+        let state = unsafe {
             duckdb_get_config_flag(self.idx, addr_of_mut!(name_ptr), addr_of_mut!(desc_ptr))
-        });
+        };
+        if state != duckdb_state_DuckDBSuccess {
+            return Err(Error::Config(format!(
+                "no config flag at index {}",
+                self.idx
+            )));
+        }
         self.idx += 1;
 
+        // both point into DuckDB's static table of settings, so aren't freed
+        let (name, desc) = unsafe { (CStr::from_ptr(name_ptr), CStr::from_ptr(desc_ptr)) };
         Ok(ConfigItem {
             name: name.to_str()?.to_owned(),
             desc: desc.to_str()?.to_owned(),
@@ -60,15 +66,23 @@ impl ConfigList {
 }
 
 impl Config {
-    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn new() -> Result<Self, Error> {
         let mut out_config: duckdb_config = null_mut();
-        check!(unsafe { duckdb_create_config(addr_of_mut!(out_config)) });
+        if unsafe { duckdb_create_config(addr_of_mut!(out_config)) } != duckdb_state_DuckDBSuccess {
+            return Err(Error::Config("duckdb_create_config failed".to_owned()));
+        }
         Ok(Self(out_config))
     }
-    pub fn set_flag(&mut self, name: &str, value: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let name = CString::new(name)?;
-        let value = CString::new(value)?;
-        check!(unsafe { duckdb_set_config(self.0, name.as_ptr(), value.as_ptr()) });
+    pub fn set_flag(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        let state = with_c_strs([name, value], |strs| unsafe {
+            duckdb_set_config(self.0, strs[0], strs[1])
+        })?;
+        if state != duckdb_state_DuckDBSuccess {
+            return Err(Error::Config(format!(
+                "unknown option {} or invalid value {}",
+                name, value
+            )));
+        }
         Ok(())
     }
 }
@@ -92,6 +106,12 @@ mod test {
         let lst: Vec<ConfigItem> = lst.collect();
 
         assert!(lst.len() > 0);
+
+        let access_mode = lst
+            .iter()
+            .find(|item| item.name == "access_mode")
+            .expect("access_mode flag");
+        assert!(!access_mode.desc.is_empty());
     }
 
     #[test]
@@ -1,38 +1,172 @@
-use crate::check;
-use crate::duckly::{duckdb_connection, duckdb_disconnect, duckdb_register_table_function};
+use crate::copy_functions::{CopyFunction, RawCopyFunction};
+use crate::duckly::{
+    duckdb_collation_get_extra_info, duckdb_collation_info, duckdb_collation_set_error,
+    duckdb_connection, duckdb_disconnect, duckdb_extension_register_table_function,
+    duckdb_get_extension_option, duckdb_register_collation, duckdb_register_copy_function,
+    duckdb_register_pragma_function, duckdb_string_t, duckdb_vector, idx_t,
+};
+use crate::error::check_registration;
+use crate::panics::catch_panic;
+use crate::pragma_functions::PragmaFunction;
+use crate::strings::{with_c_str, with_message};
 use crate::table_functions::TableFunction;
+use crate::{Database, Error, Value, Vector};
+use std::ffi::c_void;
+use std::marker::PhantomData;
+
+type Collation = Box<
+    dyn Fn(
+            &Vector<duckdb_string_t>,
+            &Vector<duckdb_string_t>,
+            usize,
+        ) -> Result<(), Box<dyn std::error::Error>>
+        + Send
+        + Sync,
+>;
+
+unsafe extern "C" fn drop_collation(ptr: *mut c_void) {
+    drop(Box::from_raw(ptr.cast::<Collation>()));
+}
+
+unsafe extern "C" fn collate(
+    info: duckdb_collation_info,
+    input: duckdb_vector,
+    output: duckdb_vector,
+    count: idx_t,
+) {
+    let error = match catch_panic(|| {
+        let collation = &*duckdb_collation_get_extra_info(info).cast::<Collation>();
+        // DuckDB collates VARCHAR values into a VARCHAR vector
+        let (input, output) = (Vector::from_raw(input), Vector::from_raw(output));
+        collation(&input, &output, count as usize)
+    }) {
+        Ok(Ok(())) => return,
+        Ok(Err(error)) => error.to_string(),
+        Err(message) => message,
+    };
+    with_message(&error, |error| duckdb_collation_set_error(info, error));
+}
 
 /// A connection to a database. This represents a (client) connection that can
 /// be used to query the database.
+///
+/// The connection borrows the [`Database`](crate::Database) it was opened on, so it can't outlive it.
 #[derive(Debug)]
-pub struct Connection {
+pub struct Connection<'db> {
     ptr: duckdb_connection,
+    _database: PhantomData<&'db Database>,
 }
 
-impl From<duckdb_connection> for Connection {
-    fn from(ptr: duckdb_connection) -> Self {
-        Self { ptr }
+impl<'db> Connection<'db> {
+    /// # Safety
+    /// `ptr` must be a connection to a database that outlives `'db`
+    pub(crate) unsafe fn new(ptr: duckdb_connection) -> Self {
+        Self {
+            ptr,
+            _database: PhantomData,
+        }
     }
-}
 
-impl Connection {
     /// Register the table function object within the given connection.
     ///
     /// The function requires at least a name, a bind function, an init function and a main function.
     ///
-    /// If the function is incomplete or a function with this name already exists DuckDBError is returned.
+    /// If the function is incomplete or a function with this name already exists [`Error::Registration`] is
+    /// returned.
+    ///
+    /// # Arguments
+    ///  * `function`: The function pointer
+    /// returns: Whether or not the registration was successful.
+    pub fn register_table_function(&self, table_function: TableFunction) -> Result<(), Error> {
+        let state =
+            unsafe { duckdb_extension_register_table_function(self.ptr, table_function.ptr) };
+        check_registration(state, "table function")
+    }
+
+    /// Register the pragma function object within the given connection.
+    ///
+    /// The function requires at least a name and either a main function or a query function.
+    ///
+    /// If the function is incomplete or a function with this name already exists [`Error::Registration`] is
+    /// returned. At most 64 pragma functions can be registered with each database.
     ///
     /// # Arguments
     ///  * `function`: The function pointer
     /// returns: Whether or not the registration was successful.
-    pub fn register_table_function(
+    pub fn register_pragma_function(&self, pragma_function: PragmaFunction) -> Result<(), Error> {
+        check_registration(
+            unsafe { duckdb_register_pragma_function(self.ptr, pragma_function.ptr) },
+            "pragma function",
+        )
+    }
+
+    /// Register a copy function within the given connection, making it available as
+    /// `COPY ... TO 'file' (FORMAT name)`.
+    ///
+    /// If a function with this name already exists [`Error::Registration`] is returned.
+    ///
+    /// # Arguments
+    ///  * `name`: The name of the format
+    ///  * `function`: The implementation of the format
+    /// returns: Whether or not the registration was successful.
+    pub fn register_copy_function<T: CopyFunction>(
         &self,
-        table_function: TableFunction,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        unsafe {
-            check!(duckdb_register_table_function(self.ptr, table_function.ptr));
-        }
-        Ok(())
+        name: &str,
+        function: T,
+    ) -> Result<(), Error> {
+        let copy_function = RawCopyFunction::new(name, function)?;
+        check_registration(
+            unsafe { duckdb_register_copy_function(self.ptr, copy_function.ptr) },
+            "copy function",
+        )
+    }
+
+    /// Register a collation within the given connection, making it available as `COLLATE name`.
+    ///
+    /// The callback computes a sort key for each of the `count` strings in the input vector, writing it to the
+    /// same row of the output vector. Strings are then sorted and compared by their sort keys, including in joins
+    /// and `GROUP BY`. Rows that are NULL in the input are NULL in the output and can be skipped. The input can be read
+    /// with [`Vector::get_strings`], given the `count`.
+    ///
+    /// # Arguments
+    ///  * `name`: The name of the collation, in lower case
+    ///  * `callback`: The function computing the sort keys
+    /// returns: Whether or not the registration was successful.
+    pub fn register_collation<F>(&self, name: &str, callback: F) -> Result<(), Error>
+    where
+        F: Fn(
+                &Vector<duckdb_string_t>,
+                &Vector<duckdb_string_t>,
+                usize,
+            ) -> Result<(), Box<dyn std::error::Error>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let collation: Collation = Box::new(callback);
+        let state = with_c_str(name, |name| unsafe {
+            duckdb_register_collation(
+                self.ptr,
+                name,
+                Some(collate),
+                Box::into_raw(Box::new(collation)).cast(),
+                Some(drop_collation),
+            )
+        })?;
+        check_registration(state, "collation")
+    }
+
+    /// Retrieves the current value of a setting, such as one added with [`Database::add_extension_option`], as
+    /// set on this connection or, failing that, globally.
+    ///
+    /// # Arguments
+    ///  * `name`: The name of the setting
+    /// returns: The value of the setting, or `None` if there is no such setting or it has no value
+    pub fn get_extension_option(&self, name: &str) -> Result<Option<Value>, Error> {
+        let value = with_c_str(name, |name| unsafe {
+            duckdb_get_extension_option(self.ptr, name)
+        })?;
+        Ok((!value.is_null()).then(|| Value::from(value)))
     }
 
     /// Returns the internal connection pointer
@@ -41,10 +175,102 @@ impl Connection {
     }
 }
 
-impl Drop for Connection {
+impl Drop for Connection<'_> {
     fn drop(&mut self) {
         unsafe {
             duckdb_disconnect(&mut self.ptr);
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::database::Database;
+    use crate::testing::query;
+    use crate::Error;
+
+    /// Pads every run of digits to the same width, so that `file2` sorts before `file10`
+    fn natural_key(name: &str) -> String {
+        let mut key = String::new();
+        let mut digits = String::new();
+        for c in name.chars().chain(std::iter::once('\0')) {
+            if c.is_ascii_digit() {
+                digits.push(c);
+                continue;
+            }
+            if !digits.is_empty() {
+                let trimmed = digits.trim_start_matches('0');
+                key.push_str(&format!("{:0>20}", trimmed));
+                digits.clear();
+            }
+            if c != '\0' {
+                key.push(c);
+            }
+        }
+        key
+    }
+
+    #[test]
+    fn test_collation() -> Result<(), Box<dyn std::error::Error>> {
+        let db = Database::new()?;
+        let conn = db.connect()?;
+
+        conn.register_collation("natural", |input, output, count| {
+            // DuckDB passes `count` strings, which are left alone until the callback returns
+            let input = unsafe { input.get_strings(count as u64) };
+            for (row, string) in input.iter_str().enumerate() {
+                let key = match string? {
+                    Some(string) => natural_key(string),
+                    None => continue,
+                };
+                unsafe {
+                    output.assign_string_element_len(
+                        row as u64,
+                        key.as_ptr().cast(),
+                        key.len() as u64,
+                    )
+                };
+            }
+            Ok(())
+        })?;
+
+        query(
+            &conn,
+            "CREATE TABLE files AS SELECT * FROM (VALUES ('file10'), ('file2'), ('file02'), ('file1'), (NULL)) t(name)",
+        )??;
+
+        assert_eq!(
+            query(
+                &conn,
+                "SELECT string_agg(name, ',' ORDER BY name COLLATE natural, name) FROM files"
+            )??,
+            [["file1,file02,file2,file10"]]
+        );
+        assert_eq!(
+            query(
+                &conn,
+                "SELECT count(DISTINCT name COLLATE natural) FROM files"
+            )??,
+            [["3"]]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_registration_error() -> Result<(), Error> {
+        let db = Database::new()?;
+        let conn = db.connect()?;
+
+        conn.register_collation("natural", |_, _, _| Ok(()))?;
+        match conn.register_collation("natural", |_, _, _| Ok(())) {
+            Err(Error::Registration { what, message }) => {
+                assert_eq!(what, "collation");
+                assert!(message.contains("natural"), "{}", message);
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        Ok(())
+    }
+}
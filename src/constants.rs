@@ -1,34 +1,195 @@
 use crate::duckly::*;
+use std::hash::{Hash, Hasher};
 
-#[derive(Debug, Eq, PartialEq, num_derive::FromPrimitive)]
+/// The type ids of DuckDB's C API, see [`LogicalType::type_id`](crate::LogicalType::type_id)
+///
+/// DuckDB 0.7.1's C API reports `TIME WITH TIME ZONE` and `TIMESTAMP WITH TIME ZONE` as `TIME` and `TIMESTAMP`, so
+/// [`LogicalTypeId::TimeTz`] and [`LogicalTypeId::TimestampTz`] use the ids later versions gave them. The types later
+/// versions added, such as `UHUGEINT` and `ARRAY`, only have variants with the `duckdb-v1_1` feature. Other ids are
+/// kept as [`LogicalTypeId::Unknown`] rather than rejected. Ids are compared by [`LogicalTypeId::id`], so `Unknown`
+/// with the id of a known type is equal to that type's variant.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
 pub enum LogicalTypeId {
-    Boolean = DUCKDB_TYPE_DUCKDB_TYPE_BOOLEAN as isize,
-    Tinyint = DUCKDB_TYPE_DUCKDB_TYPE_TINYINT as isize,
-    Smallint = DUCKDB_TYPE_DUCKDB_TYPE_SMALLINT as isize,
-    Integer = DUCKDB_TYPE_DUCKDB_TYPE_INTEGER as isize,
-    Bigint = DUCKDB_TYPE_DUCKDB_TYPE_BIGINT as isize,
-    Utinyint = DUCKDB_TYPE_DUCKDB_TYPE_UTINYINT as isize,
-    Usmallint = DUCKDB_TYPE_DUCKDB_TYPE_USMALLINT as isize,
-    Uinteger = DUCKDB_TYPE_DUCKDB_TYPE_UINTEGER as isize,
-    Ubigint = DUCKDB_TYPE_DUCKDB_TYPE_UBIGINT as isize,
-    Float = DUCKDB_TYPE_DUCKDB_TYPE_FLOAT as isize,
-    Double = DUCKDB_TYPE_DUCKDB_TYPE_DOUBLE as isize,
-    Timestamp = DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP as isize,
-    Date = DUCKDB_TYPE_DUCKDB_TYPE_DATE as isize,
-    Time = DUCKDB_TYPE_DUCKDB_TYPE_TIME as isize,
-    Interval = DUCKDB_TYPE_DUCKDB_TYPE_INTERVAL as isize,
-    Hugeint = DUCKDB_TYPE_DUCKDB_TYPE_HUGEINT as isize,
-    Varchar = DUCKDB_TYPE_DUCKDB_TYPE_VARCHAR as isize,
-    Blob = DUCKDB_TYPE_DUCKDB_TYPE_BLOB as isize,
-    Decimal = DUCKDB_TYPE_DUCKDB_TYPE_DECIMAL as isize,
-    TimestampS = DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_S as isize,
-    TimestampMs = DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_MS as isize,
-    TimestampNs = DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_NS as isize,
-    Enum = DUCKDB_TYPE_DUCKDB_TYPE_ENUM as isize,
-    List = DUCKDB_TYPE_DUCKDB_TYPE_LIST as isize,
-    Struct = DUCKDB_TYPE_DUCKDB_TYPE_STRUCT as isize,
-    Map = DUCKDB_TYPE_DUCKDB_TYPE_MAP as isize,
-    Uuid = DUCKDB_TYPE_DUCKDB_TYPE_UUID as isize,
-    Union = DUCKDB_TYPE_DUCKDB_TYPE_UNION as isize,
-    Json = DUCKDB_TYPE_DUCKDB_TYPE_JSON as isize,
+    /// The id DuckDB gives types that have no id of their own in its C API
+    Invalid,
+    Boolean,
+    Tinyint,
+    Smallint,
+    Integer,
+    Bigint,
+    Utinyint,
+    Usmallint,
+    Uinteger,
+    Ubigint,
+    Float,
+    Double,
+    Timestamp,
+    Date,
+    Time,
+    Interval,
+    Hugeint,
+    Varchar,
+    Blob,
+    Decimal,
+    TimestampS,
+    TimestampMs,
+    TimestampNs,
+    Enum,
+    List,
+    Struct,
+    Map,
+    Uuid,
+    Union,
+    Bit,
+    TimeTz,
+    TimestampTz,
+    #[cfg(feature = "duckdb-v1_1")]
+    Uhugeint,
+    #[cfg(feature = "duckdb-v1_1")]
+    Array,
+    #[cfg(feature = "duckdb-v1_1")]
+    Any,
+    #[cfg(feature = "duckdb-v1_1")]
+    Varint,
+    /// An id added by a version of DuckDB newer than the bindings
+    Unknown(u32),
+}
+
+impl LogicalTypeId {
+    /// Returns the id the C API uses for the type
+    pub fn id(self) -> DUCKDB_TYPE {
+        match self {
+            Self::Invalid => DUCKDB_TYPE_DUCKDB_TYPE_INVALID,
+            Self::Boolean => DUCKDB_TYPE_DUCKDB_TYPE_BOOLEAN,
+            Self::Tinyint => DUCKDB_TYPE_DUCKDB_TYPE_TINYINT,
+            Self::Smallint => DUCKDB_TYPE_DUCKDB_TYPE_SMALLINT,
+            Self::Integer => DUCKDB_TYPE_DUCKDB_TYPE_INTEGER,
+            Self::Bigint => DUCKDB_TYPE_DUCKDB_TYPE_BIGINT,
+            Self::Utinyint => DUCKDB_TYPE_DUCKDB_TYPE_UTINYINT,
+            Self::Usmallint => DUCKDB_TYPE_DUCKDB_TYPE_USMALLINT,
+            Self::Uinteger => DUCKDB_TYPE_DUCKDB_TYPE_UINTEGER,
+            Self::Ubigint => DUCKDB_TYPE_DUCKDB_TYPE_UBIGINT,
+            Self::Float => DUCKDB_TYPE_DUCKDB_TYPE_FLOAT,
+            Self::Double => DUCKDB_TYPE_DUCKDB_TYPE_DOUBLE,
+            Self::Timestamp => DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP,
+            Self::Date => DUCKDB_TYPE_DUCKDB_TYPE_DATE,
+            Self::Time => DUCKDB_TYPE_DUCKDB_TYPE_TIME,
+            Self::Interval => DUCKDB_TYPE_DUCKDB_TYPE_INTERVAL,
+            Self::Hugeint => DUCKDB_TYPE_DUCKDB_TYPE_HUGEINT,
+            Self::Varchar => DUCKDB_TYPE_DUCKDB_TYPE_VARCHAR,
+            Self::Blob => DUCKDB_TYPE_DUCKDB_TYPE_BLOB,
+            Self::Decimal => DUCKDB_TYPE_DUCKDB_TYPE_DECIMAL,
+            Self::TimestampS => DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_S,
+            Self::TimestampMs => DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_MS,
+            Self::TimestampNs => DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_NS,
+            Self::Enum => DUCKDB_TYPE_DUCKDB_TYPE_ENUM,
+            Self::List => DUCKDB_TYPE_DUCKDB_TYPE_LIST,
+            Self::Struct => DUCKDB_TYPE_DUCKDB_TYPE_STRUCT,
+            Self::Map => DUCKDB_TYPE_DUCKDB_TYPE_MAP,
+            Self::Uuid => DUCKDB_TYPE_DUCKDB_TYPE_UUID,
+            Self::Union => DUCKDB_TYPE_DUCKDB_TYPE_UNION,
+            Self::Bit => DUCKDB_TYPE_DUCKDB_TYPE_BIT,
+            Self::TimeTz => DUCKDB_EXTENSION_TYPE_TIME_TZ,
+            Self::TimestampTz => DUCKDB_EXTENSION_TYPE_TIMESTAMP_TZ,
+            #[cfg(feature = "duckdb-v1_1")]
+            Self::Uhugeint => DUCKDB_TYPE_DUCKDB_TYPE_UHUGEINT,
+            #[cfg(feature = "duckdb-v1_1")]
+            Self::Array => DUCKDB_TYPE_DUCKDB_TYPE_ARRAY,
+            #[cfg(feature = "duckdb-v1_1")]
+            Self::Any => DUCKDB_TYPE_DUCKDB_TYPE_ANY,
+            #[cfg(feature = "duckdb-v1_1")]
+            Self::Varint => DUCKDB_TYPE_DUCKDB_TYPE_VARINT,
+            Self::Unknown(id) => id,
+        }
+    }
+}
+
+impl PartialEq for LogicalTypeId {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
+impl Eq for LogicalTypeId {}
+
+impl Hash for LogicalTypeId {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
+    }
+}
+
+impl From<DUCKDB_TYPE> for LogicalTypeId {
+    fn from(id: DUCKDB_TYPE) -> Self {
+        match id {
+            DUCKDB_TYPE_DUCKDB_TYPE_INVALID => Self::Invalid,
+            DUCKDB_TYPE_DUCKDB_TYPE_BOOLEAN => Self::Boolean,
+            DUCKDB_TYPE_DUCKDB_TYPE_TINYINT => Self::Tinyint,
+            DUCKDB_TYPE_DUCKDB_TYPE_SMALLINT => Self::Smallint,
+            DUCKDB_TYPE_DUCKDB_TYPE_INTEGER => Self::Integer,
+            DUCKDB_TYPE_DUCKDB_TYPE_BIGINT => Self::Bigint,
+            DUCKDB_TYPE_DUCKDB_TYPE_UTINYINT => Self::Utinyint,
+            DUCKDB_TYPE_DUCKDB_TYPE_USMALLINT => Self::Usmallint,
+            DUCKDB_TYPE_DUCKDB_TYPE_UINTEGER => Self::Uinteger,
+            DUCKDB_TYPE_DUCKDB_TYPE_UBIGINT => Self::Ubigint,
+            DUCKDB_TYPE_DUCKDB_TYPE_FLOAT => Self::Float,
+            DUCKDB_TYPE_DUCKDB_TYPE_DOUBLE => Self::Double,
+            DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP => Self::Timestamp,
+            DUCKDB_TYPE_DUCKDB_TYPE_DATE => Self::Date,
+            DUCKDB_TYPE_DUCKDB_TYPE_TIME => Self::Time,
+            DUCKDB_TYPE_DUCKDB_TYPE_INTERVAL => Self::Interval,
+            DUCKDB_TYPE_DUCKDB_TYPE_HUGEINT => Self::Hugeint,
+            DUCKDB_TYPE_DUCKDB_TYPE_VARCHAR => Self::Varchar,
+            DUCKDB_TYPE_DUCKDB_TYPE_BLOB => Self::Blob,
+            DUCKDB_TYPE_DUCKDB_TYPE_DECIMAL => Self::Decimal,
+            DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_S => Self::TimestampS,
+            DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_MS => Self::TimestampMs,
+            DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_NS => Self::TimestampNs,
+            DUCKDB_TYPE_DUCKDB_TYPE_ENUM => Self::Enum,
+            DUCKDB_TYPE_DUCKDB_TYPE_LIST => Self::List,
+            DUCKDB_TYPE_DUCKDB_TYPE_STRUCT => Self::Struct,
+            DUCKDB_TYPE_DUCKDB_TYPE_MAP => Self::Map,
+            DUCKDB_TYPE_DUCKDB_TYPE_UUID => Self::Uuid,
+            DUCKDB_TYPE_DUCKDB_TYPE_UNION => Self::Union,
+            DUCKDB_TYPE_DUCKDB_TYPE_BIT => Self::Bit,
+            DUCKDB_EXTENSION_TYPE_TIME_TZ => Self::TimeTz,
+            DUCKDB_EXTENSION_TYPE_TIMESTAMP_TZ => Self::TimestampTz,
+            #[cfg(feature = "duckdb-v1_1")]
+            DUCKDB_TYPE_DUCKDB_TYPE_UHUGEINT => Self::Uhugeint,
+            #[cfg(feature = "duckdb-v1_1")]
+            DUCKDB_TYPE_DUCKDB_TYPE_ARRAY => Self::Array,
+            #[cfg(feature = "duckdb-v1_1")]
+            DUCKDB_TYPE_DUCKDB_TYPE_ANY => Self::Any,
+            #[cfg(feature = "duckdb-v1_1")]
+            DUCKDB_TYPE_DUCKDB_TYPE_VARINT => Self::Varint,
+            id => Self::Unknown(id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::LogicalTypeId;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_ids() {
+        for id in 0..64 {
+            let typ = LogicalTypeId::from(id);
+            assert_eq!(typ.id(), id, "{:?}", typ);
+        }
+        assert_eq!(LogicalTypeId::from(0), LogicalTypeId::Invalid);
+        assert_eq!(LogicalTypeId::from(29), LogicalTypeId::Bit);
+        assert_eq!(LogicalTypeId::from(31), LogicalTypeId::TimestampTz);
+        assert!(matches!(
+            LogicalTypeId::from(40),
+            LogicalTypeId::Unknown(40)
+        ));
+        assert_eq!(LogicalTypeId::from(4096), LogicalTypeId::Unknown(4096));
+
+        assert_eq!(LogicalTypeId::Unknown(4), LogicalTypeId::Integer);
+        assert_ne!(LogicalTypeId::Unknown(4), LogicalTypeId::Bigint);
+        let ids: HashSet<_> = [LogicalTypeId::Integer, LogicalTypeId::Unknown(4)].into();
+        assert_eq!(ids.len(), 1);
+    }
 }
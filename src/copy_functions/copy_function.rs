@@ -0,0 +1,196 @@
+use crate::copy_functions::CopyInfo;
+use crate::duckly::{
+    duckdb_copy_function, duckdb_copy_function_set_bind, duckdb_copy_function_set_combine,
+    duckdb_copy_function_set_extra_info, duckdb_copy_function_set_finalize,
+    duckdb_copy_function_set_init_global, duckdb_copy_function_set_init_local,
+    duckdb_copy_function_set_name, duckdb_copy_function_set_sink, duckdb_copy_info,
+    duckdb_create_copy_function, duckdb_data_chunk, duckdb_destroy_copy_function,
+};
+use crate::panics::catch_panic;
+use crate::strings::with_c_str;
+use crate::DataChunk;
+use std::error::Error;
+use std::ffi::c_void;
+
+/// A custom output format for `COPY ... TO 'file' (FORMAT name)`
+///
+/// The bind produces the data that is shared by every stage of the statement. Each thread then gets its own
+/// [`CopyFunction::LocalState`], into which it sinks chunks of rows, before combining it into the
+/// [`CopyFunction::GlobalState`], which is finalized once all threads are done.
+pub trait CopyFunction: Send + Sync + 'static {
+    /// Data produced from the statement's options and columns, shared by the copies DuckDB makes of the plan, e.g.
+    /// for prepared statements
+    type BindData: Send + Sync;
+    /// State shared by every thread writing to the file
+    type GlobalState: Send + Sync;
+    /// State owned by a single thread
+    type LocalState: Send;
+
+    /// Validates the statement's options and columns, which are available through `info`.
+    fn bind(&self, info: &CopyInfo) -> Result<Self::BindData, Box<dyn Error>>;
+
+    /// Creates the state shared by every thread, typically by opening the file at `file_path`.
+    fn init_global(
+        &self,
+        bind_data: &Self::BindData,
+        file_path: &str,
+    ) -> Result<Self::GlobalState, Box<dyn Error>>;
+
+    /// Creates the state of a single thread.
+    fn init_local(&self, bind_data: &Self::BindData) -> Result<Self::LocalState, Box<dyn Error>>;
+
+    /// Writes a chunk of rows.
+    fn sink(
+        &self,
+        bind_data: &Self::BindData,
+        global_state: &Self::GlobalState,
+        local_state: &mut Self::LocalState,
+        input: &DataChunk,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Merges the state of a thread that has finished sinking into the global state.
+    fn combine(
+        &self,
+        _bind_data: &Self::BindData,
+        _global_state: &Self::GlobalState,
+        _local_state: &mut Self::LocalState,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    /// Completes the file once every thread has been combined.
+    fn finalize(
+        &self,
+        _bind_data: &Self::BindData,
+        _global_state: &mut Self::GlobalState,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+}
+
+unsafe extern "C" fn drop_boxed<T>(ptr: *mut c_void) {
+    drop(Box::from_raw(ptr.cast::<T>()));
+}
+
+/// Runs a stage of the statement, reporting its error or panic through `info`
+fn run(info: &CopyInfo, stage: impl FnOnce() -> Result<(), Box<dyn Error>>) {
+    let error = match catch_panic(stage) {
+        Ok(Ok(())) => return,
+        Ok(Err(error)) => error.to_string(),
+        Err(message) => message,
+    };
+    info.set_error(&error);
+}
+
+unsafe extern "C" fn bind<T: CopyFunction>(info: duckdb_copy_info) {
+    let info = CopyInfo::from(info);
+    run(&info, || {
+        let function = &*info.get_extra_info::<T>();
+        let bind_data = function.bind(&info)?;
+        info.set_bind_data(
+            Box::into_raw(Box::new(bind_data)).cast(),
+            Some(drop_boxed::<T::BindData>),
+        );
+        Ok(())
+    });
+}
+
+unsafe extern "C" fn init_global<T: CopyFunction>(info: duckdb_copy_info) {
+    let info = CopyInfo::from(info);
+    run(&info, || {
+        let function = &*info.get_extra_info::<T>();
+        let bind_data = &*info.get_bind_data::<T::BindData>();
+        let global_state = function.init_global(bind_data, &info.get_file_path())?;
+        info.set_global_state(
+            Box::into_raw(Box::new(global_state)).cast(),
+            Some(drop_boxed::<T::GlobalState>),
+        );
+        Ok(())
+    });
+}
+
+unsafe extern "C" fn init_local<T: CopyFunction>(info: duckdb_copy_info) {
+    let info = CopyInfo::from(info);
+    run(&info, || {
+        let function = &*info.get_extra_info::<T>();
+        let bind_data = &*info.get_bind_data::<T::BindData>();
+        let local_state = function.init_local(bind_data)?;
+        info.set_local_state(
+            Box::into_raw(Box::new(local_state)).cast(),
+            Some(drop_boxed::<T::LocalState>),
+        );
+        Ok(())
+    });
+}
+
+unsafe extern "C" fn sink<T: CopyFunction>(info: duckdb_copy_info, input: duckdb_data_chunk) {
+    let info = CopyInfo::from(info);
+    run(&info, || {
+        let function = &*info.get_extra_info::<T>();
+        let bind_data = &*info.get_bind_data::<T::BindData>();
+        let global_state = &*info.get_global_state::<T::GlobalState>();
+        let local_state = &mut *info.get_local_state::<T::LocalState>();
+        let input = DataChunk::from(input);
+        function.sink(bind_data, global_state, local_state, &input)
+    });
+}
+
+unsafe extern "C" fn combine<T: CopyFunction>(info: duckdb_copy_info) {
+    let info = CopyInfo::from(info);
+    run(&info, || {
+        let function = &*info.get_extra_info::<T>();
+        let bind_data = &*info.get_bind_data::<T::BindData>();
+        let global_state = &*info.get_global_state::<T::GlobalState>();
+        let local_state = &mut *info.get_local_state::<T::LocalState>();
+        function.combine(bind_data, global_state, local_state)
+    });
+}
+
+unsafe extern "C" fn finalize<T: CopyFunction>(info: duckdb_copy_info) {
+    let info = CopyInfo::from(info);
+    run(&info, || {
+        let function = &*info.get_extra_info::<T>();
+        let bind_data = &*info.get_bind_data::<T::BindData>();
+        let global_state = &mut *info.get_global_state::<T::GlobalState>();
+        function.finalize(bind_data, global_state)
+    });
+}
+
+/// The C representation of a [`CopyFunction`], wired up to forward to its methods
+#[derive(Debug)]
+pub(crate) struct RawCopyFunction {
+    pub(crate) ptr: duckdb_copy_function,
+}
+
+impl RawCopyFunction {
+    pub(crate) fn new<T: CopyFunction>(name: &str, function: T) -> Result<Self, crate::Error> {
+        let raw = Self {
+            ptr: unsafe { duckdb_create_copy_function() },
+        };
+        with_c_str(name, |name| unsafe {
+            duckdb_copy_function_set_name(raw.ptr, name)
+        })?;
+        unsafe {
+            duckdb_copy_function_set_extra_info(
+                raw.ptr,
+                Box::into_raw(Box::new(function)).cast(),
+                Some(drop_boxed::<T>),
+            );
+            duckdb_copy_function_set_bind(raw.ptr, Some(bind::<T>));
+            duckdb_copy_function_set_init_global(raw.ptr, Some(init_global::<T>));
+            duckdb_copy_function_set_init_local(raw.ptr, Some(init_local::<T>));
+            duckdb_copy_function_set_sink(raw.ptr, Some(sink::<T>));
+            duckdb_copy_function_set_combine(raw.ptr, Some(combine::<T>));
+            duckdb_copy_function_set_finalize(raw.ptr, Some(finalize::<T>));
+        }
+        Ok(raw)
+    }
+}
+
+impl Drop for RawCopyFunction {
+    fn drop(&mut self) {
+        unsafe {
+            duckdb_destroy_copy_function(&mut self.ptr);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use crate::duckly::{
+    duckdb_copy_get_bind_data, duckdb_copy_get_column_count, duckdb_copy_get_column_name,
+    duckdb_copy_get_column_type, duckdb_copy_get_extra_info, duckdb_copy_get_file_path,
+    duckdb_copy_get_global_state, duckdb_copy_get_local_state, duckdb_copy_get_option_count,
+    duckdb_copy_get_option_name, duckdb_copy_get_option_value, duckdb_copy_get_option_value_count,
+    duckdb_copy_info, duckdb_copy_set_bind_data, duckdb_copy_set_error,
+    duckdb_copy_set_global_state, duckdb_copy_set_local_state, duckdb_delete_callback_t, idx_t,
+};
+use crate::strings::with_message;
+use crate::{LogicalType, Value};
+use std::ffi::{c_char, c_void, CStr};
+
+/// An interface to the options, columns and state of a `COPY ... TO` statement
+#[derive(Debug)]
+pub struct CopyInfo(duckdb_copy_info);
+
+impl From<duckdb_copy_info> for CopyInfo {
+    fn from(ptr: duckdb_copy_info) -> Self {
+        Self(ptr)
+    }
+}
+
+/// Copies a string owned by DuckDB, returning an empty string for a null pointer
+unsafe fn to_owned_string(ptr: *const c_char) -> String {
+    if ptr.is_null() {
+        String::new()
+    } else {
+        CStr::from_ptr(ptr).to_string_lossy().into_owned()
+    }
+}
+
+impl CopyInfo {
+    /// Retrieves the number of options passed to the statement, as in `(FORMAT name, OPTION value)`.
+    ///
+    /// Only available during the bind.
+    pub fn get_option_count(&self) -> idx_t {
+        unsafe { duckdb_copy_get_option_count(self.0) }
+    }
+    /// Retrieves the name of the option at the given index.
+    ///
+    /// # Arguments
+    ///  * `index`: The index of the option
+    pub fn get_option_name(&self, index: idx_t) -> String {
+        unsafe { to_owned_string(duckdb_copy_get_option_name(self.0, index)) }
+    }
+    /// Retrieves the values of the option at the given index.
+    ///
+    /// # Arguments
+    ///  * `index`: The index of the option
+    pub fn get_option_values(&self, index: idx_t) -> Vec<Value> {
+        unsafe {
+            (0..duckdb_copy_get_option_value_count(self.0, index))
+                .map(|value_index| {
+                    Value::from(duckdb_copy_get_option_value(self.0, index, value_index))
+                })
+                .collect()
+        }
+    }
+    /// Retrieves the values of the option with the given name, compared case-insensitively.
+    ///
+    /// # Arguments
+    ///  * `name`: The name of the option
+    /// returns: The values of the option, or `None` if the option was not given
+    pub fn get_option(&self, name: &str) -> Option<Vec<Value>> {
+        (0..self.get_option_count())
+            .find(|index| self.get_option_name(*index).eq_ignore_ascii_case(name))
+            .map(|index| self.get_option_values(index))
+    }
+    /// Retrieves the number of columns being copied.
+    ///
+    /// Only available during the bind.
+    pub fn get_column_count(&self) -> idx_t {
+        unsafe { duckdb_copy_get_column_count(self.0) }
+    }
+    /// Retrieves the name of the column at the given index.
+    ///
+    /// # Arguments
+    ///  * `index`: The index of the column
+    pub fn get_column_name(&self, index: idx_t) -> String {
+        unsafe { to_owned_string(duckdb_copy_get_column_name(self.0, index)) }
+    }
+    /// Retrieves the type of the column at the given index.
+    ///
+    /// # Arguments
+    ///  * `index`: The index of the column
+    pub fn get_column_type(&self, index: idx_t) -> LogicalType {
+        unsafe { LogicalType::from(duckdb_copy_get_column_type(self.0, index)) }
+    }
+    /// Retrieves the path of the file being written to.
+    pub fn get_file_path(&self) -> String {
+        unsafe { to_owned_string(duckdb_copy_get_file_path(self.0)) }
+    }
+    /// Report that an error has occurred, which aborts the statement.
+    ///
+    /// # Arguments
+    ///  * `error`: The error message
+    pub fn set_error(&self, error: &str) {
+        with_message(error, |error| unsafe {
+            duckdb_copy_set_error(self.0, error)
+        })
+    }
+    /// Retrieves the extra info of the function
+    ///
+    /// # Arguments
+    /// * `returns`: The extra info
+    pub fn get_extra_info<T>(&self) -> *mut T {
+        unsafe { duckdb_copy_get_extra_info(self.0).cast() }
+    }
+    /// Sets the user-provided bind data. Only valid during the bind.
+    ///
+    /// # Safety
+    pub unsafe fn set_bind_data(&self, data: *mut c_void, destroy: duckdb_delete_callback_t) {
+        duckdb_copy_set_bind_data(self.0, data, destroy);
+    }
+    /// Gets the bind data set by [`CopyInfo::set_bind_data`].
+    pub fn get_bind_data<T>(&self) -> *mut T {
+        unsafe { duckdb_copy_get_bind_data(self.0).cast() }
+    }
+    /// Sets the global state shared by every thread. Only valid during the global init.
+    ///
+    /// # Safety
+    pub unsafe fn set_global_state(&self, data: *mut c_void, destroy: duckdb_delete_callback_t) {
+        duckdb_copy_set_global_state(self.0, data, destroy);
+    }
+    /// Gets the global state set by [`CopyInfo::set_global_state`].
+    pub fn get_global_state<T>(&self) -> *mut T {
+        unsafe { duckdb_copy_get_global_state(self.0).cast() }
+    }
+    /// Sets the thread-local state. Only valid during the local init.
+    ///
+    /// # Safety
+    pub unsafe fn set_local_state(&self, data: *mut c_void, destroy: duckdb_delete_callback_t) {
+        duckdb_copy_set_local_state(self.0, data, destroy);
+    }
+    /// Gets the thread-local state set by [`CopyInfo::set_local_state`].
+    pub fn get_local_state<T>(&self) -> *mut T {
+        unsafe { duckdb_copy_get_local_state(self.0).cast() }
+    }
+}
@@ -0,0 +1,9 @@
+/// A copy function implements a custom output format for `COPY ... TO 'file' (FORMAT name)`
+mod copy_function;
+mod copy_info;
+#[cfg(test)]
+mod test_integration;
+
+pub use self::copy_function::CopyFunction;
+pub(crate) use self::copy_function::RawCopyFunction;
+pub use self::copy_info::CopyInfo;
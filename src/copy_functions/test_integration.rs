@@ -0,0 +1,147 @@
+use crate::copy_functions::{CopyFunction, CopyInfo};
+use crate::database::Database;
+use crate::duckly::{
+    duckdb_destroy_result, duckdb_query, duckdb_result, duckdb_result_error,
+    duckdb_state_DuckDBError,
+};
+use crate::{Connection, DataChunk};
+use std::error::Error;
+use std::ffi::{CStr, CString};
+use std::fs::{self, File};
+use std::io::Write;
+use std::mem;
+use std::sync::Mutex;
+
+/// Writes the first column of every row, joined by a configurable delimiter
+struct Pipes;
+
+struct PipesBindData {
+    delimiter: String,
+}
+
+impl CopyFunction for Pipes {
+    type BindData = PipesBindData;
+    type GlobalState = Mutex<File>;
+    type LocalState = Vec<String>;
+
+    fn bind(&self, info: &CopyInfo) -> Result<Self::BindData, Box<dyn Error>> {
+        if info.get_column_count() != 1 {
+            Err("pipes only supports a single column")?;
+        }
+        let delimiter = match info.get_option("delimiter") {
+            Some(values) => values[0].get_varchar().into_string()?,
+            None => "|".to_owned(),
+        };
+        Ok(PipesBindData { delimiter })
+    }
+
+    fn init_global(
+        &self,
+        _bind_data: &Self::BindData,
+        file_path: &str,
+    ) -> Result<Self::GlobalState, Box<dyn Error>> {
+        Ok(Mutex::new(File::create(file_path)?))
+    }
+
+    fn init_local(&self, _bind_data: &Self::BindData) -> Result<Self::LocalState, Box<dyn Error>> {
+        Ok(Vec::new())
+    }
+
+    fn sink(
+        &self,
+        _bind_data: &Self::BindData,
+        _global_state: &Self::GlobalState,
+        local_state: &mut Self::LocalState,
+        input: &DataChunk,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut vector = input.get_vector::<i64>(0)?;
+        let values = vector.get_data_as_slice();
+        local_state.extend(
+            values[..input.get_size() as usize]
+                .iter()
+                .map(|value| value.to_string()),
+        );
+        Ok(())
+    }
+
+    fn combine(
+        &self,
+        bind_data: &Self::BindData,
+        global_state: &Self::GlobalState,
+        local_state: &mut Self::LocalState,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut file = global_state.lock().map_err(|err| err.to_string())?;
+        write!(file, "{}", local_state.join(&bind_data.delimiter))?;
+        Ok(())
+    }
+}
+
+fn execute(conn: &Connection, sql: &str) -> Result<Result<(), String>, Box<dyn Error>> {
+    let sql = CString::new(sql)?;
+
+    unsafe {
+        let mut result: duckdb_result = mem::zeroed();
+
+        let outcome = if duckdb_query(conn.get_ptr(), sql.as_ptr(), &mut result)
+            == duckdb_state_DuckDBError
+        {
+            let error = CStr::from_ptr(duckdb_result_error(&mut result));
+            Err(error.to_str()?.to_owned())
+        } else {
+            Ok(())
+        };
+
+        duckdb_destroy_result(&mut result);
+
+        Ok(outcome)
+    }
+}
+
+#[test]
+fn test_copy_to() -> Result<(), Box<dyn Error>> {
+    let db = Database::new()?;
+    let conn = db.connect()?;
+
+    conn.register_copy_function("pipes", Pipes)?;
+
+    let path = std::env::temp_dir().join(format!("copy_to_{}.pipes", std::process::id()));
+
+    execute(&conn, "SET threads TO 1")??;
+    execute(
+        &conn,
+        &format!(
+            "COPY (SELECT * FROM range(5)) TO '{}' (FORMAT pipes, DELIMITER ',')",
+            path.display()
+        ),
+    )??;
+
+    assert_eq!(fs::read_to_string(&path)?, "0,1,2,3,4");
+
+    fs::remove_file(&path)?;
+
+    // executing a prepared statement reuses, and may copy, the bind data
+    execute(
+        &conn,
+        &format!(
+            "PREPARE copy_pipes AS COPY (SELECT * FROM range(3)) TO '{}' (FORMAT pipes, DELIMITER ',')",
+            path.display()
+        ),
+    )??;
+    for _ in 0..2 {
+        execute(&conn, "EXECUTE copy_pipes")??;
+        assert_eq!(fs::read_to_string(&path)?, "0,1,2");
+    }
+    fs::remove_file(&path)?;
+
+    let error = execute(
+        &conn,
+        &format!(
+            "COPY (SELECT 1 AS a, 2 AS b) TO '{}' (FORMAT pipes)",
+            path.display()
+        ),
+    )?
+    .unwrap_err();
+    assert!(error.contains("single column"), "{}", error);
+
+    Ok(())
+}
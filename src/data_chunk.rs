@@ -1,9 +1,11 @@
 use crate::duckly::{
     duckdb_create_data_chunk, duckdb_data_chunk, duckdb_data_chunk_get_column_count,
     duckdb_data_chunk_get_size, duckdb_data_chunk_get_vector, duckdb_data_chunk_reset,
-    duckdb_data_chunk_set_size, duckdb_destroy_data_chunk, duckdb_logical_type, idx_t,
+    duckdb_data_chunk_set_size, duckdb_destroy_data_chunk, duckdb_logical_type, duckdb_string_t,
+    idx_t,
 };
-use crate::{LogicalType, Vector};
+use crate::{Error, LogicalType, PhysicalType, StringVector, Vector};
+use std::any::type_name;
 
 /// A Data Chunk represents a set of vectors.
 ///
@@ -44,12 +46,56 @@ impl DataChunk {
         Self { ptr, owned: true }
     }
 
-    /// Retrieves the vector at the specified column index in the data chunk.
+    /// Retrieves the vector at the specified column index in the data chunk, to access its values as `T`.
     ///
     /// The pointer to the vector is valid for as long as the chunk is alive.
     /// It does NOT need to be destroyed.
     ///
-    pub fn get_vector<T>(&self, column_index: idx_t) -> Vector<T> {
+    /// # Arguments
+    /// * `column_index`: The index of the column
+    /// * `returns`: The vector, or [`Error::Conversion`] if there's no such column or its values aren't stored as `T`,
+    ///   see [`PhysicalType::LOGICAL_TYPES`]
+    pub fn get_vector<T: PhysicalType>(&self, column_index: idx_t) -> Result<Vector<T>, Error> {
+        let column_count = self.get_column_count();
+        if column_index >= column_count {
+            return Err(Error::Conversion(format!(
+                "column {} is out of bounds for {} columns",
+                column_index, column_count
+            )));
+        }
+        let vector = self.get_untyped_vector(column_index);
+        let typ = vector.get_column_type();
+        if !T::LOGICAL_TYPES.contains(&typ.type_id()) {
+            return Err(Error::Conversion(format!(
+                "the values of a {} column can't be accessed as {}",
+                typ,
+                type_name::<T>()
+            )));
+        }
+        // the column type was checked above
+        Ok(unsafe { Vector::from_raw(duckdb_data_chunk_get_vector(self.ptr, column_index)) })
+    }
+
+    /// Reads the values of a `VARCHAR` or `BLOB` column of the data chunk, up to its current size.
+    ///
+    /// The view borrows the chunk mutably, so the chunk can't be resized, reset or have its vectors written while
+    /// the view is in use.
+    ///
+    /// # Arguments
+    /// * `column_index`: The column index
+    /// * `returns`: A view of the values, or [`Error::Conversion`] if there's no such column or it isn't a `VARCHAR`
+    ///   or `BLOB` column
+    pub fn get_string_vector(&mut self, column_index: idx_t) -> Result<StringVector<'_>, Error> {
+        let vector = self.get_vector::<duckdb_string_t>(column_index)?;
+        Ok(unsafe { vector.borrow_strings(self.get_size()) })
+    }
+
+    /// Retrieves the vector at the specified column index in the data chunk, for columns whose values are accessed
+    /// through type specific accessors such as [`Vector::get_decimal`] and [`Vector::list_child`].
+    ///
+    /// The pointer to the vector is valid for as long as the chunk is alive.
+    /// It does NOT need to be destroyed.
+    pub fn get_untyped_vector(&self, column_index: idx_t) -> Vector<()> {
         Vector::from(unsafe { duckdb_data_chunk_get_vector(self.ptr, column_index) })
     }
     /// Sets the current number of tuples in a data chunk.
@@ -86,7 +132,8 @@ impl Drop for DataChunk {
 
 #[cfg(test)]
 mod test {
-    use crate::{DataChunk, LogicalType};
+    use crate::duckly::duckdb_string_t;
+    use crate::{DataChunk, Error, LogicalType};
 
     #[test]
     fn test_data_chunk_construction() {
@@ -98,4 +145,17 @@ mod test {
 
         drop(dc);
     }
+
+    #[test]
+    fn test_get_vector_checks_type() -> Result<(), Error> {
+        let dc = DataChunk::new(vec!["BIGINT".parse()?, "VARCHAR".parse()?]);
+
+        dc.get_vector::<i64>(0)?;
+        dc.get_vector::<duckdb_string_t>(1)?;
+        DataChunk::new(vec!["BOOLEAN".parse()?]).get_vector::<u8>(0)?;
+        assert!(matches!(dc.get_vector::<u64>(0), Err(Error::Conversion(_))));
+        assert!(matches!(dc.get_vector::<i64>(1), Err(Error::Conversion(_))));
+        assert!(matches!(dc.get_vector::<i64>(2), Err(Error::Conversion(_))));
+        Ok(())
+    }
 }
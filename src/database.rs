@@ -1,78 +1,151 @@
 use crate::database::DatabaseOwnership::{Borrowed, Owned};
 use crate::duckly::{
-    duckdb_add_replacement_scan, duckdb_close, duckdb_connect, duckdb_connection, duckdb_database,
-    duckdb_delete_callback_t, duckdb_open, duckdb_open_ext, duckdb_replacement_callback_t,
+    duckdb_add_extension_option, duckdb_add_replacement_scan, duckdb_close, duckdb_config,
+    duckdb_connection, duckdb_database, duckdb_database_from_instance, duckdb_delete_callback_t,
+    duckdb_destroy_database_ref, duckdb_extension_connect, duckdb_extension_option_get_extra_info,
+    duckdb_extension_option_info, duckdb_extension_option_set_error, duckdb_free,
+    duckdb_library_version, duckdb_open_ext, duckdb_register_file_system,
+    duckdb_register_storage_extension, duckdb_replacement_callback_t, duckdb_state_DuckDBSuccess,
+    duckdb_unregister_storage_extension, duckdb_value,
 };
-use crate::Connection;
-use crate::{check, Config};
-use std::error::Error;
-use std::ffi::{c_void, CString};
-use std::ptr::{addr_of, addr_of_mut, null_mut};
-
-/// Equivalent of [`DatabaseData`](https://github.com/duckdb/duckdb/blob/50951241de3d9c06fac5719dcb907eb21163dcab/src/include/duckdb/main/capi_internal.hpp#L27), wraps `duckdb::DuckDB`
-#[repr(C)]
-#[derive(Debug)]
-struct Wrapper {
-    instance: *const c_void,
+use crate::error::check_registration;
+use crate::file_systems::{FileSystem, RawFileSystem};
+use crate::panics::catch_panic;
+use crate::storage_extensions::{RawStorageExtension, StorageExtension};
+use crate::strings::{with_c_str, with_c_strs, with_message};
+use crate::version::Version;
+use crate::{Config, Connection, Error, LogicalType, LogicalTypeId, Value};
+use std::ffi::{c_char, c_void, CStr};
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::ptr::{addr_of_mut, null_mut};
+
+type Validator = Box<dyn Fn(&Value) -> Result<(), Box<dyn std::error::Error>> + Send + Sync>;
+
+unsafe extern "C" fn drop_validator(ptr: *mut c_void) {
+    drop(Box::from_raw(ptr.cast::<Validator>()));
+}
+
+unsafe extern "C" fn validate(info: duckdb_extension_option_info, value: duckdb_value) {
+    let value = Value::from(value);
+
+    // a panicking validator rejects the value
+    let error = match catch_panic(|| {
+        let validator = &*duckdb_extension_option_get_extra_info(info).cast::<Validator>();
+        validator(&value)
+    }) {
+        Ok(Ok(())) => return,
+        Ok(Err(error)) => error.to_string(),
+        Err(message) => message,
+    };
+    with_message(&error, |error| {
+        duckdb_extension_option_set_error(info, error)
+    });
+}
+
+/// Takes the error DuckDB set, falling back to `fallback` if it didn't set one
+unsafe fn take_error(error: *mut c_char, fallback: &str) -> String {
+    if error.is_null() {
+        return fallback.to_owned();
+    }
+    let message = CStr::from_ptr(error).to_string_lossy().into_owned();
+    duckdb_free(error.cast());
+    message
 }
 
 #[derive(Debug)]
 enum DatabaseOwnership {
+    /// Opened by this crate, and closed when dropped
     Owned(duckdb_database),
-    Borrowed(Wrapper),
+    /// Opened by DuckDB, only the wrapper created by `duckdb_database_from_instance` is destroyed when dropped
+    Borrowed(duckdb_database),
 }
 
 #[derive(Debug)]
 pub struct Database(DatabaseOwnership);
 
+/// A [`Database`] that was opened by DuckDB rather than this crate, such as the one an extension is loaded into.
+///
+/// The database stays open when this is dropped, and it can't outlive the reference DuckDB handed out.
+#[derive(Debug)]
+pub struct DatabaseRef<'a> {
+    database: Database,
+    _instance: PhantomData<&'a c_void>,
+}
+
+impl Deref for DatabaseRef<'_> {
+    type Target = Database;
+
+    fn deref(&self) -> &Self::Target {
+        &self.database
+    }
+}
+
 impl Database {
-    pub fn new() -> Result<Self, Box<dyn Error>> {
-        let mut db: duckdb_database = null_mut();
+    pub fn new() -> Result<Self, Error> {
+        Self::open(null_mut())
+    }
 
-        let filename = CString::new(":memory:")?;
-        unsafe {
-            check!(duckdb_open(filename.as_ptr(), &mut db));
-        }
-        Ok(Self(Owned(db)))
+    pub fn new_with_config(config: &Config) -> Result<Self, Error> {
+        Self::open(config.0)
     }
 
-    pub fn new_with_config(config: &Config) -> Result<Self, Box<dyn std::error::Error>> {
+    /// Opens an in-memory database, with DuckDB's reason if it fails
+    fn open(config: duckdb_config) -> Result<Self, Error> {
         let mut db: duckdb_database = null_mut();
-        let error = CString::new("")?;
-        let filename = CString::new(":memory:")?;
-        let mut out_error = error.as_ptr().cast_mut();
-        unsafe {
-            check!(duckdb_open_ext(
-                filename.as_ptr(),
-                &mut db,
-                config.0,
-                addr_of_mut!(out_error)
-            ));
+        let mut out_error: *mut c_char = null_mut();
+        let state = with_c_str(":memory:", |filename| unsafe {
+            duckdb_open_ext(filename, &mut db, config, addr_of_mut!(out_error))
+        })?;
+        if state != duckdb_state_DuckDBSuccess {
+            return Err(Error::Open(unsafe {
+                take_error(out_error, "duckdb_open_ext failed")
+            }));
         }
         Ok(Self(Owned(db)))
     }
 
-    /// Construct a [`Database`] instance from a pointer passed to an extensions `init` function
-    pub fn from_cpp_duckdb(ptr: *mut c_void) -> Self {
-        Self(Borrowed(Wrapper { instance: ptr }))
+    /// The version of the DuckDB library the process is running, which may differ from the version of the
+    /// headers the bindings were generated from, see [`VersionPolicy`](crate::version::VersionPolicy)
+    pub fn library_version() -> Result<Version, Error> {
+        let version = unsafe { CStr::from_ptr(duckdb_library_version()) };
+        version.to_str()?.parse()
     }
 
-    pub fn connect(&self) -> Result<Connection, Box<dyn Error>> {
+    /// Construct a [`DatabaseRef`] from the pointer passed to an extension's `init` function
+    ///
+    /// # Safety
+    /// `ptr` must point to a `duckdb::DatabaseInstance` that stays open for `'a`
+    pub unsafe fn from_cpp_duckdb<'a>(ptr: *mut c_void) -> DatabaseRef<'a> {
+        DatabaseRef {
+            database: Self(Borrowed(duckdb_database_from_instance(ptr))),
+            _instance: PhantomData,
+        }
+    }
+
+    /// Opens a connection to the database, which can't outlive it
+    pub fn connect(&self) -> Result<Connection<'_>, Error> {
         let mut connection: duckdb_connection = null_mut();
 
         let db = self.get_ptr();
 
         unsafe {
-            check!(duckdb_connect(db, &mut connection));
+            let mut out_error: *mut c_char = null_mut();
+            if duckdb_extension_connect(db, &mut connection, addr_of_mut!(out_error))
+                != duckdb_state_DuckDBSuccess
+            {
+                return Err(Error::Connect(take_error(
+                    out_error,
+                    "duckdb_connect failed",
+                )));
+            }
+            Ok(Connection::new(connection))
         }
-
-        Ok(Connection::from(connection))
     }
 
     fn get_ptr(&self) -> duckdb_database {
-        match &self.0 {
-            Borrowed(wrapper) => addr_of!(wrapper) as duckdb_database,
-            Owned(ptr) => *ptr,
+        match self.0 {
+            Borrowed(ptr) | Owned(ptr) => ptr,
         }
     }
 
@@ -93,12 +166,116 @@ impl Database {
     ) {
         duckdb_add_replacement_scan(self.get_ptr(), replacement, extra_data, delete_callback);
     }
+
+    /// Register a file system with the specified database, making every path that starts with `prefix`
+    /// resolve through it, including those passed to DuckDB's built-in readers and writers.
+    ///
+    /// # Arguments
+    /// * `prefix`: The prefix of the paths handled by the file system, e.g. `blobstore://`
+    /// * `file_system`: The implementation of the file system
+    /// returns: Whether or not the registration was successful.
+    pub fn register_file_system<T: FileSystem>(
+        &self,
+        prefix: &str,
+        file_system: T,
+    ) -> Result<(), Error> {
+        let file_system = RawFileSystem::new(prefix, file_system)?;
+        check_registration(
+            unsafe { duckdb_register_file_system(self.get_ptr(), file_system.ptr) },
+            "file system",
+        )
+    }
+
+    /// Register a storage extension with the specified database, making it available as
+    /// `ATTACH 'path' AS name (TYPE type)`.
+    ///
+    /// The extension's scan is registered as a table function named `<type>_scan`.
+    ///
+    /// # Arguments
+    /// * `name`: The type of database served by the extension
+    /// * `extension`: The implementation of the extension
+    /// returns: Whether or not the registration was successful.
+    pub fn register_storage_extension<T: StorageExtension>(
+        &self,
+        name: &str,
+        extension: T,
+    ) -> Result<(), Error> {
+        let scan_name = format!("{}_scan", name);
+
+        let varchar = LogicalType::new(LogicalTypeId::Varchar);
+        let scan = extension.scan();
+        scan.set_name(&scan_name)?
+            .add_parameter(&varchar)
+            .add_parameter(&varchar)
+            .add_parameter(&varchar);
+
+        let extension = RawStorageExtension::new(name, &scan_name, extension)?;
+        check_registration(
+            unsafe { duckdb_register_storage_extension(self.get_ptr(), extension.ptr) },
+            "storage extension",
+        )?;
+
+        // Without its scan the extension couldn't read any table, so it's removed again
+        let scan = self
+            .connect()
+            .and_then(|connection| connection.register_table_function(scan));
+        if scan.is_err() {
+            with_c_str(name, |name| unsafe {
+                duckdb_unregister_storage_extension(self.get_ptr(), name)
+            })?;
+        }
+        scan
+    }
+
+    /// Add a setting that users can change with `SET name = value`, for example `SET my_ext.batch_size = 4096`.
+    ///
+    /// Settings take effect per connection, or for every connection with `SET GLOBAL`, and are read through
+    /// [`Connection::get_extension_option`] or `current_setting('name')`. The C API of this version of DuckDB
+    /// doesn't give table functions the connection running them, so a table function that depends on a setting
+    /// should take it as a parameter, as in `my_scan(batch_size := current_setting('my_ext.batch_size'))`.
+    ///
+    /// The default is the setting's global value. At most 64 options can be added to each database.
+    ///
+    /// # Arguments
+    /// * `name`: The name of the setting, in lower case
+    /// * `description`: The description shown in `duckdb_settings()`
+    /// * `logical_type`: The type that values are cast to before being validated
+    /// * `default`: The value of the setting until it is first set
+    /// * `validator`: Called with every new value, rejecting the value by returning an error
+    /// returns: Whether or not the setting was added.
+    pub fn add_extension_option<F>(
+        &self,
+        name: &str,
+        description: &str,
+        logical_type: &LogicalType,
+        default: Value,
+        validator: F,
+    ) -> Result<(), Error>
+    where
+        F: Fn(&Value) -> Result<(), Box<dyn std::error::Error>> + Send + Sync + 'static,
+    {
+        let validator: Validator = Box::new(validator);
+        let state = with_c_strs([name, description], |strs| unsafe {
+            duckdb_add_extension_option(
+                self.get_ptr(),
+                strs[0],
+                strs[1],
+                logical_type.typ,
+                default.0,
+                Some(validate),
+                Box::into_raw(Box::new(validator)).cast(),
+                Some(drop_validator),
+            )
+        })?;
+        check_registration(state, "extension option")
+    }
 }
 
 impl Drop for Database {
     fn drop(&mut self) {
-        if let Owned(mut ptr) = self.0 {
-            unsafe { duckdb_close(&mut ptr) }
+        match self.0 {
+            Owned(mut ptr) => unsafe { duckdb_close(&mut ptr) },
+            Borrowed(mut ptr) => unsafe { duckdb_destroy_database_ref(&mut ptr) },
         }
     }
 }
@@ -106,8 +283,8 @@ impl Drop for Database {
 #[cfg(test)]
 mod test {
     use crate::database::Database;
-    use crate::{Config, Connection};
-    use std::any::{Any, TypeId};
+    use crate::version::VersionPolicy;
+    use crate::Config;
     use std::error::Error;
     use std::ptr::null_mut;
 
@@ -116,11 +293,11 @@ mod test {
         let db = Database::new()?;
         let conn = db.connect()?;
 
-        drop(db);
-
-        assert_eq!(conn.type_id(), TypeId::of::<Connection>());
+        assert_ne!(conn.get_ptr(), null_mut());
 
+        // The connection borrows the database, so must be dropped first
         drop(conn);
+        drop(db);
 
         Ok(())
     }
@@ -135,4 +312,13 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_library_version() -> Result<(), Box<dyn Error>> {
+        let version = Database::library_version()?;
+
+        VersionPolicy::MinorCompatible.check(&version)?;
+
+        Ok(())
+    }
 }
@@ -6,4 +6,11 @@
 #![allow(clippy::upper_case_acronyms)]
 #![allow(rustdoc::broken_intra_doc_links)]
 
+#[cfg(feature = "buildtime_bindgen")]
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+#[cfg(all(not(feature = "buildtime_bindgen"), feature = "duckdb-v0_7"))]
+include!("bindings/duckdb-v0_7.rs");
+
+#[cfg(not(any(feature = "buildtime_bindgen", feature = "duckdb-v0_7")))]
+compile_error!("enable one of the pinned DuckDB versions, e.g. the `duckdb-v0_7` feature, or `buildtime_bindgen`");
@@ -0,0 +1,101 @@
+//! Runtime support for the entry points generated by [`duckdb_extension`](crate::duckdb_extension)
+
+use crate::duckly::{duckdb_extension_init_t, duckdb_malloc};
+use crate::panics::catch_panic;
+use crate::version::VersionPolicy;
+use crate::Database;
+use std::error::Error;
+use std::ffi::{c_char, c_void};
+use std::mem::ManuallyDrop;
+use std::ptr::{self, addr_of_mut};
+
+extern "C-unwind" {
+    // Declared here rather than generated, as bindgen can't be told that it throws. A failed load is reported by
+    // the exception it throws once `init` has returned, which the `C-unwind` ABI lets unwind through Rust frames.
+    fn duckdb_extension_load(
+        instance: *mut c_void,
+        init: duckdb_extension_init_t,
+        state: *mut c_void,
+    );
+}
+
+/// Checks the version of DuckDB the extension is being loaded into against `policy`, then calls the extension's
+/// `init` function with the database.
+///
+/// An incompatible version, error or panic is reported as the error of the `LOAD` statement, rather than aborting
+/// the process.
+///
+/// # Safety
+/// `db` must be the pointer DuckDB passes to the extension's `<name>_init` function, which must be declared
+/// `extern "C-unwind"`
+pub unsafe fn init<F>(db: *mut c_void, policy: VersionPolicy, init: F)
+where
+    F: FnOnce(&Database) -> Result<(), Box<dyn Error>>,
+{
+    // DuckDB only learns that loading failed from an exception, which `duckdb_extension_load` throws once `load` has
+    // returned. It then unwinds through this frame and the generated `<name>_init`, so neither owns anything that
+    // needs dropping.
+    let mut state = ManuallyDrop::new((policy, init));
+    duckdb_extension_load(db, Some(load::<F>), addr_of_mut!(state).cast());
+}
+
+unsafe extern "C" fn load<F>(db: *mut c_void, state: *mut c_void) -> *mut c_char
+where
+    F: FnOnce(&Database) -> Result<(), Box<dyn Error>>,
+{
+    let (policy, init) = ManuallyDrop::take(&mut *state.cast::<ManuallyDrop<(VersionPolicy, F)>>());
+    let error = {
+        let database = Database::from_cpp_duckdb(db);
+        let load = || {
+            policy.check(&Database::library_version()?)?;
+            init(&database)
+        };
+        match catch_panic(load) {
+            Ok(Ok(())) => return ptr::null_mut(),
+            Ok(Err(error)) => error.to_string(),
+            Err(message) => message,
+        }
+    };
+
+    let message = duckdb_malloc(error.len() + 1).cast::<u8>();
+    ptr::copy_nonoverlapping(error.as_ptr(), message, error.len());
+    *message.add(error.len()) = 0;
+    message.cast()
+}
+
+/// Returns the version of the DuckDB headers the extension was built against, as `<name>_version` must return.
+///
+/// DuckDB refuses to load an extension whose version differs from its own, before `<name>_init` is called. The
+/// [`VersionPolicy`] passed to [`init`] is checked in addition to that, against the version DuckDB reports.
+pub fn version() -> *const c_char {
+    concat!(env!("DUCKDB_HEADERS_VERSION"), "\0")
+        .as_ptr()
+        .cast()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::version::{VersionPolicy, HEADERS_VERSION};
+    use crate::{duckdb_extension, Database};
+    use std::error::Error;
+    use std::ffi::CStr;
+
+    #[duckdb_extension(name = "test_extension")]
+    fn init(_db: &Database) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    #[duckdb_extension(name = "relaxed_extension", version_policy = VersionPolicy::MinorCompatible)]
+    fn relaxed_init(_db: &Database) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    #[test]
+    fn test_version() {
+        let version = unsafe { CStr::from_ptr(test_extension_version()) };
+        let relaxed = unsafe { CStr::from_ptr(relaxed_extension_version()) };
+
+        assert_eq!(version.to_str().unwrap(), HEADERS_VERSION);
+        assert_eq!(version, relaxed);
+    }
+}
@@ -1,4 +1,132 @@
-/// Asserts that the given expression returns DuckDBSuccess, else panics and prints the expression
+use crate::duckly::{duckdb_extension_last_error, duckdb_state, duckdb_state_DuckDBSuccess};
+use crate::version::Version;
+use std::ffi::{CStr, NulError};
+use std::str::Utf8Error;
+use std::{error, fmt, io};
+
+/// An error returned by this crate
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Opening a database failed, with DuckDB's reason if it gave one
+    Open(String),
+    /// Connecting to a database failed
+    Connect(String),
+    /// Running a query failed, with DuckDB's error message
+    Query(String),
+    /// Registering a function, file system, setting or other extension point failed
+    Registration {
+        /// What was being registered, e.g. `table function`
+        what: &'static str,
+        /// Why DuckDB rejected it
+        message: String,
+    },
+    /// Creating or changing a database's configuration failed
+    Config(String),
+    /// A value couldn't be converted to or from a DuckDB type
+    Conversion(String),
+    /// A field of a row couldn't be converted to or from its column's type
+    Field {
+        /// The path to the field, such as `author.name`, or `parents[2]` for an element of a list
+        path: String,
+        /// Why it couldn't be converted
+        source: Box<Error>,
+    },
+    /// The extension was built against a version of DuckDB that isn't compatible with the one loading it
+    IncompatibleVersion {
+        /// The version of the headers the bindings were generated from
+        headers: Version,
+        /// The version of DuckDB the process is running
+        library: Version,
+    },
+    /// A string passed to DuckDB contained a NUL byte
+    Nul(NulError),
+    /// A string returned by DuckDB wasn't valid UTF-8
+    Utf8(Utf8Error),
+    /// Reading or writing a file failed
+    Io(io::Error),
+    /// An error from outside this crate: returned by the extension's own code, such as its init function, or a
+    /// failure found in it by the `testing` feature's sqllogictest runner
+    Extension(Box<dyn error::Error>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open(message) => write!(f, "could not open database: {}", message),
+            Self::Connect(message) => write!(f, "could not connect to database: {}", message),
+            Self::Query(message) => write!(f, "query failed: {}", message),
+            Self::Registration { what, message } => {
+                write!(f, "could not register {}: {}", what, message)
+            }
+            Self::Config(message) => write!(f, "invalid configuration: {}", message),
+            Self::Conversion(message) => write!(f, "conversion failed: {}", message),
+            Self::Field { path, source } => write!(f, "field {}: {}", path, source),
+            Self::IncompatibleVersion { headers, library } => write!(
+                f,
+                "extension was built against DuckDB {} and cannot be loaded into DuckDB {}",
+                headers, library
+            ),
+            Self::Nul(error) => write!(f, "string contains a NUL byte: {}", error),
+            Self::Utf8(error) => write!(f, "string is not valid UTF-8: {}", error),
+            Self::Io(error) => error.fmt(f),
+            Self::Extension(error) => error.fmt(f),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Nul(error) => Some(error),
+            Self::Utf8(error) => Some(error),
+            Self::Io(error) => Some(error),
+            Self::Extension(error) => Some(error.as_ref()),
+            Self::Field { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<NulError> for Error {
+    fn from(error: NulError) -> Self {
+        Self::Nul(error)
+    }
+}
+
+impl From<Utf8Error> for Error {
+    fn from(error: Utf8Error) -> Self {
+        Self::Utf8(error)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Turns the state returned by one of the registration functions in `wrapper.cpp` into an [`Error::Registration`],
+/// with the reason the function recorded.
+pub(crate) fn check_registration(state: duckdb_state, what: &'static str) -> Result<(), Error> {
+    if state == duckdb_state_DuckDBSuccess {
+        return Ok(());
+    }
+    let message = unsafe {
+        let error = duckdb_extension_last_error();
+        if error.is_null() {
+            "unknown error".to_owned()
+        } else {
+            CStr::from_ptr(error).to_string_lossy().into_owned()
+        }
+    };
+    Err(Error::Registration { what, message })
+}
+
+/// Asserts that the given expression returns DuckDBSuccess, else returns an error naming the expression
+#[deprecated(
+    note = "DuckDB's reason for the failure is lost: use the safe wrappers, which return an `Error`"
+)]
 #[macro_export]
 macro_rules! check {
     ($x:expr) => {{
@@ -9,12 +137,15 @@ macro_rules! check {
 }
 
 /// Returns a `*const c_char` pointer to the given string
+#[deprecated(
+    note = "the pointer dangles once the statement it's used in ends: use a `CString` that outlives the call"
+)]
 #[macro_export]
 macro_rules! as_string {
     ($x:expr) => {
         std::ffi::CString::new($x)
             .expect("c string")
             .as_ptr()
-            .cast::<c_char>()
+            .cast::<std::ffi::c_char>()
     };
 }
@@ -0,0 +1,281 @@
+use crate::duckly::{
+    duckdb_create_file_system, duckdb_destroy_file_system, duckdb_file_stat, duckdb_file_system,
+    duckdb_file_system_get_extra_info, duckdb_file_system_info, duckdb_file_system_set_close,
+    duckdb_file_system_set_error, duckdb_file_system_set_extra_info, duckdb_file_system_set_glob,
+    duckdb_file_system_set_open, duckdb_file_system_set_prefix, duckdb_file_system_set_read,
+    duckdb_file_system_set_remove, duckdb_file_system_set_stat, duckdb_file_system_set_write,
+    duckdb_glob_result, duckdb_glob_result_add, idx_t,
+};
+use crate::panics::catch_panic;
+use crate::strings::{with_c_str, with_c_strs, with_message};
+use std::error::Error;
+use std::ffi::{c_char, c_void, CStr};
+use std::ptr::null_mut;
+use std::slice;
+
+const FILE_FLAGS_READ: u8 = 1 << 0;
+const FILE_FLAGS_WRITE: u8 = 1 << 1;
+const FILE_FLAGS_FILE_CREATE: u8 = 1 << 3;
+const FILE_FLAGS_FILE_CREATE_NEW: u8 = 1 << 4;
+const FILE_FLAGS_APPEND: u8 = 1 << 5;
+
+/// The mode in which DuckDB opens a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpenFlags(u8);
+
+impl OpenFlags {
+    /// Whether the file is opened for reading
+    pub fn read(&self) -> bool {
+        self.0 & FILE_FLAGS_READ != 0
+    }
+    /// Whether the file is opened for writing
+    pub fn write(&self) -> bool {
+        self.0 & FILE_FLAGS_WRITE != 0
+    }
+    /// Whether the file should be created if it does not exist
+    pub fn create(&self) -> bool {
+        self.0 & (FILE_FLAGS_FILE_CREATE | FILE_FLAGS_FILE_CREATE_NEW) != 0
+    }
+    /// Whether an existing file should be truncated
+    pub fn truncate(&self) -> bool {
+        self.0 & FILE_FLAGS_FILE_CREATE_NEW != 0
+    }
+    /// Whether writes should be appended to the end of the file
+    pub fn append(&self) -> bool {
+        self.0 & FILE_FLAGS_APPEND != 0
+    }
+}
+
+impl From<u8> for OpenFlags {
+    fn from(flags: u8) -> Self {
+        Self(flags)
+    }
+}
+
+/// Metadata about a file or directory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FileStat {
+    /// The size of the file in bytes
+    pub size: u64,
+    /// The time of the last modification, in seconds since the unix epoch
+    pub last_modified: i64,
+    /// Whether the path refers to a directory
+    pub is_directory: bool,
+}
+
+/// A virtual file system, which DuckDB uses for every path that starts with the prefix it was registered with
+///
+/// Paths are always passed including that prefix. A single handle can be read from several threads at once,
+/// which is why reads and writes are positional and only borrow the handle immutably.
+pub trait FileSystem: Send + Sync + 'static {
+    /// An open file
+    type Handle: Send + Sync;
+
+    /// Opens the file at `path`.
+    fn open(&self, path: &str, flags: OpenFlags) -> Result<Self::Handle, Box<dyn Error>>;
+
+    /// Reads up to `buffer.len()` bytes starting at `offset`, returning the number of bytes read.
+    ///
+    /// Returning `0` signals the end of the file.
+    fn read_at(
+        &self,
+        handle: &Self::Handle,
+        buffer: &mut [u8],
+        offset: u64,
+    ) -> Result<usize, Box<dyn Error>>;
+
+    /// Writes up to `buffer.len()` bytes starting at `offset`, returning the number of bytes written.
+    fn write_at(
+        &self,
+        _handle: &Self::Handle,
+        _buffer: &[u8],
+        _offset: u64,
+    ) -> Result<usize, Box<dyn Error>> {
+        Err("writing is not supported by this file system")?
+    }
+
+    /// Retrieves the metadata of `path`, or `None` if it does not exist.
+    fn stat(&self, path: &str) -> Result<Option<FileStat>, Box<dyn Error>>;
+
+    /// Expands `pattern`, which may contain wildcards, to the paths of the files it matches.
+    ///
+    /// By default a pattern only matches itself, if it exists.
+    fn glob(&self, pattern: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        Ok(match self.stat(pattern)? {
+            Some(stat) if !stat.is_directory => vec![pattern.to_owned()],
+            _ => vec![],
+        })
+    }
+
+    /// Removes the file at `path`.
+    fn remove(&self, _path: &str) -> Result<(), Box<dyn Error>> {
+        Err("removing files is not supported by this file system")?
+    }
+}
+
+unsafe extern "C" fn drop_boxed<T>(ptr: *mut c_void) {
+    drop(Box::from_raw(ptr.cast::<T>()));
+}
+
+unsafe fn get_file_system<'a, T: FileSystem>(info: duckdb_file_system_info) -> &'a T {
+    &*duckdb_file_system_get_extra_info(info).cast::<T>()
+}
+
+unsafe fn set_error(info: duckdb_file_system_info, error: &str) {
+    with_message(error, |error| duckdb_file_system_set_error(info, error));
+}
+
+/// Runs a call to the file system, reporting its error or panic through `info` and returning `failed` instead
+unsafe fn run<R>(
+    info: duckdb_file_system_info,
+    failed: R,
+    call: impl FnOnce() -> Result<R, Box<dyn Error>>,
+) -> R {
+    let error = match catch_panic(call) {
+        Ok(Ok(result)) => return result,
+        Ok(Err(error)) => error.to_string(),
+        Err(message) => message,
+    };
+    set_error(info, &error);
+    failed
+}
+
+unsafe extern "C" fn open<T: FileSystem>(
+    info: duckdb_file_system_info,
+    path: *const c_char,
+    flags: u8,
+) -> *mut c_void {
+    run(info, null_mut(), || {
+        let file_system = get_file_system::<T>(info);
+        let path = CStr::from_ptr(path).to_string_lossy();
+        let handle = file_system.open(&path, OpenFlags::from(flags))?;
+        Ok(Box::into_raw(Box::new(handle)).cast())
+    })
+}
+
+unsafe extern "C" fn close<T: FileSystem>(info: duckdb_file_system_info, handle: *mut c_void) {
+    run(info, (), || {
+        drop_boxed::<T::Handle>(handle);
+        Ok(())
+    })
+}
+
+unsafe extern "C" fn read<T: FileSystem>(
+    info: duckdb_file_system_info,
+    handle: *mut c_void,
+    buffer: *mut c_void,
+    nr_bytes: i64,
+    location: idx_t,
+) -> i64 {
+    run(info, -1, || {
+        let file_system = get_file_system::<T>(info);
+        let handle = &*handle.cast::<T::Handle>();
+        let buffer = slice::from_raw_parts_mut(buffer.cast::<u8>(), nr_bytes as usize);
+        let bytes_read = file_system.read_at(handle, buffer, location)?;
+        Ok(bytes_read as i64)
+    })
+}
+
+unsafe extern "C" fn write<T: FileSystem>(
+    info: duckdb_file_system_info,
+    handle: *mut c_void,
+    buffer: *const c_void,
+    nr_bytes: i64,
+    location: idx_t,
+) -> i64 {
+    run(info, -1, || {
+        let file_system = get_file_system::<T>(info);
+        let handle = &*handle.cast::<T::Handle>();
+        let buffer = slice::from_raw_parts(buffer.cast::<u8>(), nr_bytes as usize);
+        let bytes_written = file_system.write_at(handle, buffer, location)?;
+        Ok(bytes_written as i64)
+    })
+}
+
+unsafe extern "C" fn stat<T: FileSystem>(
+    info: duckdb_file_system_info,
+    path: *const c_char,
+    out_stat: *mut duckdb_file_stat,
+) -> bool {
+    run(info, false, || {
+        let file_system = get_file_system::<T>(info);
+        let path = CStr::from_ptr(path).to_string_lossy();
+        Ok(match file_system.stat(&path)? {
+            Some(stat) => {
+                *out_stat = duckdb_file_stat {
+                    size: stat.size,
+                    last_modified: stat.last_modified,
+                    is_directory: stat.is_directory,
+                };
+                true
+            }
+            None => false,
+        })
+    })
+}
+
+unsafe extern "C" fn glob<T: FileSystem>(
+    info: duckdb_file_system_info,
+    pattern: *const c_char,
+    result: duckdb_glob_result,
+) {
+    run(info, (), || {
+        let file_system = get_file_system::<T>(info);
+        let pattern = CStr::from_ptr(pattern).to_string_lossy();
+        let paths = file_system.glob(&pattern)?;
+        with_c_strs(paths.iter().map(String::as_str), |paths| {
+            for path in paths {
+                duckdb_glob_result_add(result, *path);
+            }
+        })?;
+        Ok(())
+    })
+}
+
+unsafe extern "C" fn remove<T: FileSystem>(info: duckdb_file_system_info, path: *const c_char) {
+    run(info, (), || {
+        let file_system = get_file_system::<T>(info);
+        let path = CStr::from_ptr(path).to_string_lossy();
+        file_system.remove(&path)
+    })
+}
+
+/// The C representation of a [`FileSystem`], wired up to forward to its methods
+#[derive(Debug)]
+pub(crate) struct RawFileSystem {
+    pub(crate) ptr: duckdb_file_system,
+}
+
+impl RawFileSystem {
+    pub(crate) fn new<T: FileSystem>(prefix: &str, file_system: T) -> Result<Self, crate::Error> {
+        let raw = Self {
+            ptr: unsafe { duckdb_create_file_system() },
+        };
+        with_c_str(prefix, |prefix| unsafe {
+            duckdb_file_system_set_prefix(raw.ptr, prefix)
+        })?;
+        unsafe {
+            duckdb_file_system_set_extra_info(
+                raw.ptr,
+                Box::into_raw(Box::new(file_system)).cast(),
+                Some(drop_boxed::<T>),
+            );
+            duckdb_file_system_set_open(raw.ptr, Some(open::<T>));
+            duckdb_file_system_set_close(raw.ptr, Some(close::<T>));
+            duckdb_file_system_set_read(raw.ptr, Some(read::<T>));
+            duckdb_file_system_set_write(raw.ptr, Some(write::<T>));
+            duckdb_file_system_set_stat(raw.ptr, Some(stat::<T>));
+            duckdb_file_system_set_glob(raw.ptr, Some(glob::<T>));
+            duckdb_file_system_set_remove(raw.ptr, Some(remove::<T>));
+        }
+        Ok(raw)
+    }
+}
+
+impl Drop for RawFileSystem {
+    fn drop(&mut self) {
+        unsafe {
+            duckdb_destroy_file_system(&mut self.ptr);
+        }
+    }
+}
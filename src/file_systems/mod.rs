@@ -0,0 +1,7 @@
+/// A file system lets DuckDB's readers and writers resolve paths with a custom prefix, such as `blobstore://`
+mod file_system;
+#[cfg(test)]
+mod test_integration;
+
+pub(crate) use self::file_system::RawFileSystem;
+pub use self::file_system::{FileStat, FileSystem, OpenFlags};
@@ -0,0 +1,152 @@
+use crate::database::Database;
+use crate::file_systems::{FileStat, FileSystem, OpenFlags};
+use crate::testing::query;
+use std::error::Error;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+const PREFIX: &str = "blobstore://";
+
+/// Serves `blobstore://bucket/key` from `<root>/bucket/key`
+struct LocalDirectory {
+    root: PathBuf,
+}
+
+impl LocalDirectory {
+    fn resolve(&self, path: &str) -> Result<PathBuf, Box<dyn Error>> {
+        let key = path.strip_prefix(PREFIX).ok_or("not a blobstore path")?;
+        Ok(self.root.join(key))
+    }
+}
+
+/// Matches `name` against a pattern in which `*` matches any run of characters and `?` a single one
+fn matches(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (None, None) => true,
+        (Some((b'*', rest)), _) => {
+            matches(rest, name) || (!name.is_empty() && matches(pattern, &name[1..]))
+        }
+        (Some((b'?', rest)), Some((_, name_rest))) => matches(rest, name_rest),
+        (Some((p, rest)), Some((n, name_rest))) => p == n && matches(rest, name_rest),
+        _ => false,
+    }
+}
+
+impl FileSystem for LocalDirectory {
+    type Handle = Mutex<File>;
+
+    fn open(&self, path: &str, flags: OpenFlags) -> Result<Self::Handle, Box<dyn Error>> {
+        let file = OpenOptions::new()
+            .read(flags.read())
+            .write(flags.write())
+            .create(flags.create())
+            .truncate(flags.truncate())
+            .append(flags.append())
+            .open(self.resolve(path)?)?;
+        Ok(Mutex::new(file))
+    }
+
+    fn read_at(
+        &self,
+        handle: &Self::Handle,
+        buffer: &mut [u8],
+        offset: u64,
+    ) -> Result<usize, Box<dyn Error>> {
+        let mut file = handle.lock().map_err(|err| err.to_string())?;
+        file.seek(SeekFrom::Start(offset))?;
+        Ok(file.read(buffer)?)
+    }
+
+    fn write_at(
+        &self,
+        handle: &Self::Handle,
+        buffer: &[u8],
+        offset: u64,
+    ) -> Result<usize, Box<dyn Error>> {
+        let mut file = handle.lock().map_err(|err| err.to_string())?;
+        file.seek(SeekFrom::Start(offset))?;
+        Ok(file.write(buffer)?)
+    }
+
+    fn stat(&self, path: &str) -> Result<Option<FileStat>, Box<dyn Error>> {
+        let metadata = match fs::metadata(self.resolve(path)?) {
+            Ok(metadata) => metadata,
+            Err(_) => return Ok(None),
+        };
+        Ok(Some(FileStat {
+            size: metadata.len(),
+            last_modified: metadata.modified()?.duration_since(UNIX_EPOCH)?.as_secs() as i64,
+            is_directory: metadata.is_dir(),
+        }))
+    }
+
+    fn glob(&self, pattern: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        let (directory, file_pattern) = pattern.rsplit_once('/').ok_or("invalid pattern")?;
+        let mut paths = vec![];
+        for entry in fs::read_dir(self.resolve(directory)?)? {
+            let name = entry?.file_name().to_string_lossy().into_owned();
+            if matches(file_pattern.as_bytes(), name.as_bytes()) {
+                paths.push(format!("{}/{}", directory, name));
+            }
+        }
+        paths.sort();
+        Ok(paths)
+    }
+
+    fn remove(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        Ok(fs::remove_file(self.resolve(path)?)?)
+    }
+}
+
+#[test]
+fn test_file_system() -> Result<(), Box<dyn Error>> {
+    let root = std::env::temp_dir().join(format!("blobstore_{}", std::process::id()));
+    fs::create_dir_all(root.join("bucket"))?;
+    fs::write(root.join("bucket/first.csv"), "a,b\n1,x\n2,y\n")?;
+    fs::write(root.join("bucket/second.csv"), "a,b\n3,z\n")?;
+    fs::write(root.join("bucket/ignored.txt"), "not,csv\n")?;
+
+    let db = Database::new()?;
+    db.register_file_system(PREFIX, LocalDirectory { root: root.clone() })?;
+    let conn = db.connect()?;
+
+    assert_eq!(
+        query(
+            &conn,
+            "SELECT count(*) FROM read_csv_auto('blobstore://bucket/first.csv')"
+        )??,
+        [["2"]]
+    );
+    assert_eq!(
+        query(
+            &conn,
+            "SELECT sum(a) FROM read_csv_auto('blobstore://bucket/*.csv')"
+        )??,
+        [["6"]]
+    );
+
+    query(
+        &conn,
+        "COPY (SELECT 42 AS answer) TO 'blobstore://bucket/out.csv' (HEADER)",
+    )??;
+    assert_eq!(
+        fs::read_to_string(root.join("bucket/out.csv"))?,
+        "answer\n42\n"
+    );
+
+    let error = query(
+        &conn,
+        "SELECT * FROM read_csv_auto('blobstore://bucket/missing.csv')",
+    )?
+    .unwrap_err();
+    assert!(error.contains("missing.csv"), "{}", error);
+
+    drop(conn);
+    drop(db);
+    fs::remove_dir_all(&root)?;
+
+    Ok(())
+}
@@ -8,17 +8,34 @@
 
 //! This crate facilitates development of DuckDB extensions using Rust
 
+extern crate self as duckdb_extension_framework;
+
+#[cfg(feature = "serde")]
+pub mod chunk_serde;
 mod config;
 mod connection;
 mod constants;
+pub mod copy_functions;
 mod data_chunk;
 mod database;
 pub mod duckly;
+pub mod entrypoint;
 mod error;
+pub mod file_systems;
 mod logical_type;
+mod panics;
+pub mod pragma_functions;
+pub mod rows;
+pub mod storage_extensions;
+mod strings;
 pub mod table_functions;
+// Also built for the crate's own tests, which run their SQL through its `query`
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+mod type_string;
 mod value;
 mod vector;
+pub mod version;
 
 use std::mem::size_of;
 
@@ -26,10 +43,13 @@ pub use crate::config::{get_configs, Config, ConfigItem, ConfigList};
 pub use crate::connection::Connection;
 pub use crate::constants::LogicalTypeId;
 pub use crate::data_chunk::DataChunk;
-pub use crate::database::Database;
-pub use crate::logical_type::LogicalType;
+pub use crate::database::{Database, DatabaseRef};
+pub use crate::error::Error;
+pub use crate::logical_type::{LogicalType, LogicalTypeShape};
+pub use crate::rows::{DuckRow, DuckValue};
 pub use crate::value::Value;
-pub use crate::vector::Vector;
+pub use crate::vector::{PhysicalType, StringVector, Vector};
+pub use duckdb_extension_framework_macros::{duckdb_extension, DuckRow};
 
 use crate::duckly::duckdb_malloc;
 
@@ -1,13 +1,66 @@
 use crate::constants::LogicalTypeId;
+#[cfg(feature = "duckdb-v1_1")]
 use crate::duckly::{
-    duckdb_create_list_type, duckdb_create_logical_type, duckdb_create_map_type,
-    duckdb_create_struct_type, duckdb_create_union, duckdb_destroy_logical_type,
-    duckdb_get_type_id, duckdb_logical_type, idx_t,
+    duckdb_array_type_array_size, duckdb_array_type_child_type, duckdb_create_array_type,
 };
-use num_traits::FromPrimitive;
-use std::collections::HashMap;
-use std::ffi::{c_char, CString};
-use std::ops::Deref;
+use crate::duckly::{
+    duckdb_copy_logical_type, duckdb_create_decimal_type, duckdb_create_enum_type,
+    duckdb_create_list_type, duckdb_create_map_type, duckdb_create_struct_type,
+    duckdb_create_union, duckdb_decimal_scale, duckdb_decimal_width, duckdb_destroy_logical_type,
+    duckdb_enum_dictionary_size, duckdb_enum_dictionary_value,
+    duckdb_extension_create_logical_type, duckdb_extension_get_type_id, duckdb_free,
+    duckdb_list_type_child_type, duckdb_logical_type, duckdb_logical_type_equals,
+    duckdb_map_type_key_type, duckdb_map_type_value_type, duckdb_struct_type_child_count,
+    duckdb_struct_type_child_name, duckdb_struct_type_child_type, duckdb_union_type_member_count,
+    duckdb_union_type_member_name, duckdb_union_type_member_type, idx_t,
+};
+use crate::strings::with_c_strs;
+use crate::Error;
+use std::collections::HashSet;
+use std::ffi::{c_char, CStr};
+use std::hash::{Hash, Hasher};
+
+/// The full structure of a [`LogicalType`], including the types nested in it, as returned by
+/// [`LogicalType::shape`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LogicalTypeShape {
+    /// A type without parameters, such as `INTEGER` or `VARCHAR`
+    Primitive(LogicalTypeId),
+    /// `DECIMAL(width, scale)`
+    Decimal { width: u8, scale: u8 },
+    /// An `ENUM` with the given dictionary values, in order
+    Enum(Vec<String>),
+    /// A `LIST` of the child type
+    List(Box<LogicalTypeShape>),
+    /// A fixed-size `ARRAY` of the child type
+    #[cfg(feature = "duckdb-v1_1")]
+    Array {
+        child: Box<LogicalTypeShape>,
+        size: u64,
+    },
+    /// A `MAP` from the key type to the value type
+    Map {
+        key: Box<LogicalTypeShape>,
+        value: Box<LogicalTypeShape>,
+    },
+    /// A `STRUCT` with the given field names and types, in order
+    Struct(Vec<(String, LogicalTypeShape)>),
+    /// A `UNION` with the given member tags and types, in order
+    Union(Vec<(String, LogicalTypeShape)>),
+}
+
+/// Returns the first name that appears more than once
+fn find_repeated<'a>(mut names: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let mut seen = HashSet::new();
+    names.find(|name| !seen.insert(*name))
+}
+
+/// Copies a string allocated by DuckDB, then frees it
+unsafe fn take_string(ptr: *mut c_char) -> String {
+    let string = CStr::from_ptr(ptr).to_string_lossy().into_owned();
+    duckdb_free(ptr.cast());
+    string
+}
 
 /// Represents a logical type in the database - the underlying physical type can differ depending on the implementation
 #[derive(Debug)]
@@ -19,7 +72,7 @@ impl LogicalType {
     pub fn new(typ: LogicalTypeId) -> Self {
         unsafe {
             Self {
-                typ: duckdb_create_logical_type(typ as u32),
+                typ: duckdb_extension_create_logical_type(typ.id()),
             }
         }
     }
@@ -37,6 +90,8 @@ impl LogicalType {
     }
     /// Creates a list type from its child type.
     ///
+    /// See [`LogicalType::array`] for the fixed-size counterpart, which needs the `duckdb-v1_1` feature.
+    ///
     /// # Arguments
     /// * `type`: The child type of list type to create.
     /// * `returns`: The logical type.
@@ -47,48 +102,108 @@ impl LogicalType {
             }
         }
     }
+    /// Creates a fixed-size `ARRAY` type, such as `INTEGER[3]`, from its child type and size.
+    ///
+    /// Needs the `duckdb-v1_1` feature, as DuckDB 0.7.1 has no `ARRAY` type.
+    ///
+    /// # Arguments
+    /// * `child`: The type of the elements
+    /// * `size`: The number of elements in every value
+    /// * `returns`: The logical type
+    #[cfg(feature = "duckdb-v1_1")]
+    pub fn array(child: &LogicalType, size: u64) -> Self {
+        unsafe {
+            Self {
+                typ: duckdb_create_array_type(child.typ, size as idx_t),
+            }
+        }
+    }
     /// Make `LogicalType` for `struct`
     ///
     /// # Argument
-    /// `shape` should be the fields and types in the `struct`
-    pub fn new_struct_type(shape: HashMap<&str, LogicalType>) -> Self {
-        Self::make_meta_type(shape, duckdb_create_struct_type)
+    /// `shape` should be the fields and types in the `struct`, in order
+    /// * `returns`: The logical type, or an error if a field name is repeated or contains a NUL byte
+    pub fn new_struct_type(shape: Vec<(&str, LogicalType)>) -> Result<Self, Error> {
+        Self::make_meta_type("struct field", shape, duckdb_create_struct_type)
     }
     /// Make `LogicalType` for `union`
     ///
     /// # Argument
-    /// `shape` should be the variants in the `union`
-    pub fn new_union_type(shape: HashMap<&str, LogicalType>) -> Self {
-        Self::make_meta_type(shape, duckdb_create_union)
+    /// `shape` should be the variants in the `union`, in order
+    /// * `returns`: The logical type, or an error if a variant name is repeated or contains a NUL byte
+    pub fn new_union_type(shape: Vec<(&str, LogicalType)>) -> Result<Self, Error> {
+        Self::make_meta_type("union variant", shape, duckdb_create_union)
+    }
+
+    /// Creates a `DECIMAL(width, scale)` type.
+    ///
+    /// # Arguments
+    /// * `width`: The total number of digits, from 1 to 38
+    /// * `scale`: The number of digits after the decimal point, at most `width`
+    /// * `returns`: The logical type, or [`Error::Conversion`] if the width or scale is out of range
+    pub fn decimal(width: u8, scale: u8) -> Result<Self, Error> {
+        if !(1..=38).contains(&width) || scale > width {
+            return Err(Error::Conversion(format!(
+                "invalid decimal width {} and scale {}",
+                width, scale
+            )));
+        }
+        unsafe {
+            Ok(Self {
+                typ: duckdb_create_decimal_type(width, scale),
+            })
+        }
+    }
+    /// Creates an `ENUM` type from its dictionary values.
+    ///
+    /// # Arguments
+    /// * `values`: The values of the enum, in order
+    /// * `returns`: The logical type, or an error if the values are repeated or contain a NUL byte
+    pub fn enum_type(values: &[&str]) -> Result<Self, Error> {
+        if let Some(value) = find_repeated(values.iter().copied()) {
+            return Err(Error::Conversion(format!(
+                "enum value \"{}\" is repeated",
+                value
+            )));
+        }
+
+        let typ = with_c_strs(values.iter().copied(), |values| unsafe {
+            duckdb_create_enum_type(values.as_ptr().cast_mut(), values.len() as idx_t)
+        })?;
+        if typ.is_null() {
+            return Err(Error::Conversion("invalid enum values".to_owned()));
+        }
+        Ok(Self { typ })
     }
 
     fn make_meta_type(
-        shape: HashMap<&str, LogicalType>,
+        what: &str,
+        shape: Vec<(&str, LogicalType)>,
         x: unsafe extern "C" fn(
             nmembers: idx_t,
             names: *mut *const c_char,
             types: *const duckdb_logical_type,
         ) -> duckdb_logical_type,
-    ) -> LogicalType {
-        let keys: Vec<CString> = shape
-            .keys()
-            .map(|it| CString::new(it.deref()).unwrap())
-            .collect();
-        let values: Vec<duckdb_logical_type> = shape.values().map(|it| it.typ).collect();
-        let name_ptrs = keys
-            .iter()
-            .map(|it| it.as_ptr())
-            .collect::<Vec<*const c_char>>();
+    ) -> Result<LogicalType, Error> {
+        if let Some(name) = find_repeated(shape.iter().map(|(name, _)| *name)) {
+            return Err(Error::Conversion(format!(
+                "{} \"{}\" is repeated",
+                what, name
+            )));
+        }
 
-        unsafe {
-            Self {
-                typ: x(
-                    shape.len().try_into().unwrap(),
-                    name_ptrs.as_slice().as_ptr().cast_mut(),
-                    values.as_slice().as_ptr(),
-                ),
-            }
+        let values: Vec<duckdb_logical_type> = shape.iter().map(|(_, typ)| typ.typ).collect();
+        let typ = with_c_strs(shape.iter().map(|(name, _)| *name), |names| unsafe {
+            x(
+                shape.len() as idx_t,
+                names.as_ptr().cast_mut(),
+                values.as_slice().as_ptr(),
+            )
+        })?;
+        if typ.is_null() {
+            return Err(Error::Conversion(format!("could not create {} type", what)));
         }
+        Ok(Self { typ })
     }
 
     /// Retrieves the type class of a `duckdb_logical_type`.
@@ -96,16 +211,190 @@ impl LogicalType {
     /// # Arguments
     /// * `returns`: The type id
     pub fn type_id(&self) -> LogicalTypeId {
-        let id = unsafe { duckdb_get_type_id(self.typ) };
+        LogicalTypeId::from(unsafe { duckdb_extension_get_type_id(self.typ) })
+    }
+
+    /// Retrieves the child type of a list type.
+    ///
+    /// # Arguments
+    /// * `returns`: The child type, or `None` if this isn't a list type
+    pub fn list_child_type(&self) -> Option<LogicalType> {
+        (self.type_id() == LogicalTypeId::List)
+            .then(|| unsafe { Self::from(duckdb_list_type_child_type(self.typ)) })
+    }
+
+    /// Retrieves the child type of a fixed-size array type.
+    ///
+    /// # Arguments
+    /// * `returns`: The child type, or `None` if this isn't an array type
+    #[cfg(feature = "duckdb-v1_1")]
+    pub fn array_child_type(&self) -> Option<LogicalType> {
+        (self.type_id() == LogicalTypeId::Array)
+            .then(|| unsafe { Self::from(duckdb_array_type_child_type(self.typ)) })
+    }
+
+    /// Retrieves the size of a fixed-size array type.
+    ///
+    /// # Arguments
+    /// * `returns`: The number of elements, or `None` if this isn't an array type
+    #[cfg(feature = "duckdb-v1_1")]
+    pub fn array_size(&self) -> Option<u64> {
+        (self.type_id() == LogicalTypeId::Array)
+            .then(|| unsafe { duckdb_array_type_array_size(self.typ) })
+    }
+
+    /// Retrieves the key type of a map type.
+    ///
+    /// # Arguments
+    /// * `returns`: The key type, or `None` if this isn't a map type
+    pub fn map_key_type(&self) -> Option<LogicalType> {
+        (self.type_id() == LogicalTypeId::Map)
+            .then(|| unsafe { Self::from(duckdb_map_type_key_type(self.typ)) })
+    }
+
+    /// Retrieves the value type of a map type.
+    ///
+    /// # Arguments
+    /// * `returns`: The value type, or `None` if this isn't a map type
+    pub fn map_value_type(&self) -> Option<LogicalType> {
+        (self.type_id() == LogicalTypeId::Map)
+            .then(|| unsafe { Self::from(duckdb_map_type_value_type(self.typ)) })
+    }
+
+    /// Retrieves the names and types of the fields of a struct type.
+    ///
+    /// # Arguments
+    /// * `returns`: The fields, in order, or `None` if this isn't a struct type
+    pub fn struct_fields(&self) -> Option<Vec<(String, LogicalType)>> {
+        if self.type_id() != LogicalTypeId::Struct {
+            return None;
+        }
+        let count = unsafe { duckdb_struct_type_child_count(self.typ) };
+        let fields = (0..count)
+            .map(|i| unsafe {
+                (
+                    take_string(duckdb_struct_type_child_name(self.typ, i)),
+                    Self::from(duckdb_struct_type_child_type(self.typ, i)),
+                )
+            })
+            .collect();
+        Some(fields)
+    }
+
+    /// Retrieves the tags and types of the members of a union type.
+    ///
+    /// # Arguments
+    /// * `returns`: The members, in order, or `None` if this isn't a union type
+    pub fn union_members(&self) -> Option<Vec<(String, LogicalType)>> {
+        if self.type_id() != LogicalTypeId::Union {
+            return None;
+        }
+        let count = unsafe { duckdb_union_type_member_count(self.typ) };
+        let members = (0..count)
+            .map(|i| unsafe {
+                (
+                    take_string(duckdb_union_type_member_name(self.typ, i)),
+                    Self::from(duckdb_union_type_member_type(self.typ, i)),
+                )
+            })
+            .collect();
+        Some(members)
+    }
+
+    /// Retrieves the width of a decimal type.
+    ///
+    /// # Arguments
+    /// * `returns`: The width, or `None` if this isn't a decimal type
+    pub fn decimal_width(&self) -> Option<u8> {
+        (self.type_id() == LogicalTypeId::Decimal)
+            .then(|| unsafe { duckdb_decimal_width(self.typ) })
+    }
 
-        FromPrimitive::from_u32(id).unwrap()
+    /// Retrieves the scale of a decimal type.
+    ///
+    /// # Arguments
+    /// * `returns`: The scale, or `None` if this isn't a decimal type
+    pub fn decimal_scale(&self) -> Option<u8> {
+        (self.type_id() == LogicalTypeId::Decimal)
+            .then(|| unsafe { duckdb_decimal_scale(self.typ) })
+    }
+
+    /// Retrieves the dictionary values of an enum type.
+    ///
+    /// # Arguments
+    /// * `returns`: The values, in order, or `None` if this isn't an enum type
+    pub fn enum_values(&self) -> Option<Vec<String>> {
+        if self.type_id() != LogicalTypeId::Enum {
+            return None;
+        }
+        let size = unsafe { duckdb_enum_dictionary_size(self.typ) };
+        let values = (0..size as idx_t)
+            .map(|i| unsafe { take_string(duckdb_enum_dictionary_value(self.typ, i)) })
+            .collect();
+        Some(values)
+    }
+
+    /// Describes the full structure of the type, recursing into any types nested in it.
+    pub fn shape(&self) -> LogicalTypeShape {
+        fn shapes(children: Vec<(String, LogicalType)>) -> Vec<(String, LogicalTypeShape)> {
+            children
+                .into_iter()
+                .map(|(name, typ)| (name, typ.shape()))
+                .collect()
+        }
+
+        match self.type_id() {
+            LogicalTypeId::Decimal => LogicalTypeShape::Decimal {
+                width: self.decimal_width().expect("decimal type"),
+                scale: self.decimal_scale().expect("decimal type"),
+            },
+            LogicalTypeId::Enum => LogicalTypeShape::Enum(self.enum_values().expect("enum type")),
+            LogicalTypeId::List => {
+                LogicalTypeShape::List(Box::new(self.list_child_type().expect("list type").shape()))
+            }
+            #[cfg(feature = "duckdb-v1_1")]
+            LogicalTypeId::Array => LogicalTypeShape::Array {
+                child: Box::new(self.array_child_type().expect("array type").shape()),
+                size: self.array_size().expect("array type"),
+            },
+            LogicalTypeId::Map => LogicalTypeShape::Map {
+                key: Box::new(self.map_key_type().expect("map type").shape()),
+                value: Box::new(self.map_value_type().expect("map type").shape()),
+            },
+            LogicalTypeId::Struct => {
+                LogicalTypeShape::Struct(shapes(self.struct_fields().expect("struct type")))
+            }
+            LogicalTypeId::Union => {
+                LogicalTypeShape::Union(shapes(self.union_members().expect("union type")))
+            }
+            id => LogicalTypeShape::Primitive(id),
+        }
     }
 }
 impl Clone for LogicalType {
+    /// Copies the type, including its children, alias and other metadata
     fn clone(&self) -> Self {
-        let type_id = self.type_id();
+        unsafe {
+            Self {
+                typ: duckdb_copy_logical_type(self.typ),
+            }
+        }
+    }
+}
 
-        Self::new(type_id)
+impl PartialEq for LogicalType {
+    /// Compares the types, including their children, alias and other metadata
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { duckdb_logical_type_equals(self.typ, other.typ) }
+    }
+}
+
+impl Eq for LogicalType {}
+
+impl Hash for LogicalType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // equal types always have the same shape, only their aliases may differ
+        self.shape().hash(state);
     }
 }
 
@@ -126,8 +415,14 @@ impl Drop for LogicalType {
 #[cfg(test)]
 mod test {
     use crate::constants::LogicalTypeId;
-    use crate::LogicalType;
-    use std::collections::HashMap;
+    use crate::duckly::{
+        duckdb_column_logical_type, duckdb_destroy_result, duckdb_query, duckdb_result,
+        duckdb_state_DuckDBSuccess,
+    };
+    use crate::{Database, Error, LogicalType, LogicalTypeShape};
+    use std::collections::HashSet;
+    use std::ffi::CString;
+    use std::mem;
     #[test]
     fn test_logi() {
         let key = LogicalType::new(LogicalTypeId::Varchar);
@@ -138,16 +433,214 @@ mod test {
 
         assert_eq!(map.type_id(), LogicalTypeId::Map);
 
-        let union_ = LogicalType::new_union_type(HashMap::from([
+        let union_ = LogicalType::new_union_type(vec![
             ("number", LogicalType::new(LogicalTypeId::Bigint)),
             ("string", LogicalType::new(LogicalTypeId::Varchar)),
-        ]));
+        ])
+        .unwrap();
         assert_eq!(union_.type_id(), LogicalTypeId::Union);
 
-        let struct_ = LogicalType::new_struct_type(HashMap::from([
+        let struct_ = LogicalType::new_struct_type(vec![
             ("number", LogicalType::new(LogicalTypeId::Bigint)),
             ("string", LogicalType::new(LogicalTypeId::Varchar)),
-        ]));
+        ])
+        .unwrap();
         assert_eq!(struct_.type_id(), LogicalTypeId::Struct);
     }
+
+    #[test]
+    fn test_field_order() -> Result<(), Error> {
+        let names = ["zebra", "apple", "mango", "kiwi", "banana", "cherry"];
+        let struct_ = LogicalType::new_struct_type(
+            names
+                .iter()
+                .map(|name| (*name, LogicalType::new(LogicalTypeId::Integer)))
+                .collect(),
+        )?;
+
+        let fields = struct_.struct_fields().unwrap();
+        assert_eq!(
+            fields
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>(),
+            names
+        );
+
+        let repeated = LogicalType::new_union_type(vec![
+            ("number", LogicalType::new(LogicalTypeId::Bigint)),
+            ("number", LogicalType::new(LogicalTypeId::Varchar)),
+        ]);
+        match repeated {
+            Err(Error::Conversion(message)) => assert!(message.contains("number"), "{}", message),
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        let nul =
+            LogicalType::new_struct_type(vec![("a\0b", LogicalType::new(LogicalTypeId::Bigint))]);
+        assert!(matches!(nul, Err(Error::Nul(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_shape() {
+        let decimal = LogicalType::decimal(18, 3).unwrap();
+        assert_eq!(decimal.decimal_width(), Some(18));
+        assert_eq!(decimal.decimal_scale(), Some(3));
+
+        let map = LogicalType::new_map_type(&LogicalType::new(LogicalTypeId::Varchar), &decimal);
+        let list = LogicalType::new_list_type(&map);
+        let struct_ = LogicalType::new_struct_type(vec![("entries", list)]).unwrap();
+        let union_ = LogicalType::new_union_type(vec![
+            ("missing", LogicalType::new(LogicalTypeId::Boolean)),
+            ("record", struct_),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            union_.shape(),
+            LogicalTypeShape::Union(vec![
+                (
+                    "missing".to_owned(),
+                    LogicalTypeShape::Primitive(LogicalTypeId::Boolean)
+                ),
+                (
+                    "record".to_owned(),
+                    LogicalTypeShape::Struct(vec![(
+                        "entries".to_owned(),
+                        LogicalTypeShape::List(Box::new(LogicalTypeShape::Map {
+                            key: Box::new(LogicalTypeShape::Primitive(LogicalTypeId::Varchar)),
+                            value: Box::new(LogicalTypeShape::Decimal {
+                                width: 18,
+                                scale: 3
+                            }),
+                        }))
+                    )])
+                ),
+            ])
+        );
+
+        assert!(decimal.list_child_type().is_none());
+        assert!(decimal.struct_fields().is_none());
+        assert!(decimal.union_members().is_none());
+        assert!(decimal.enum_values().is_none());
+    }
+
+    #[test]
+    fn test_enum_values() -> Result<(), Box<dyn std::error::Error>> {
+        let db = Database::new()?;
+        let conn = db.connect()?;
+        let sql = CString::new("SELECT 'b'::ENUM('a', 'b', 'c')")?;
+
+        let typ = unsafe {
+            let mut result: duckdb_result = mem::zeroed();
+            assert_eq!(
+                duckdb_query(conn.get_ptr(), sql.as_ptr(), &mut result),
+                duckdb_state_DuckDBSuccess
+            );
+            let typ = LogicalType::from(duckdb_column_logical_type(&mut result, 0));
+            duckdb_destroy_result(&mut result);
+            typ
+        };
+
+        assert_eq!(
+            typ.shape(),
+            LogicalTypeShape::Enum(vec!["a".to_owned(), "b".to_owned(), "c".to_owned()])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parameterised_types() {
+        let enum_ = LogicalType::enum_type(&["low", "medium", "high"]).unwrap();
+        assert_eq!(
+            enum_.shape(),
+            LogicalTypeShape::Enum(vec![
+                "low".to_owned(),
+                "medium".to_owned(),
+                "high".to_owned()
+            ])
+        );
+
+        assert_eq!(
+            LogicalType::decimal(38, 10).unwrap().shape(),
+            LogicalTypeShape::Decimal {
+                width: 38,
+                scale: 10
+            }
+        );
+
+        assert!(matches!(
+            LogicalType::decimal(39, 0),
+            Err(Error::Conversion(_))
+        ));
+        assert!(matches!(
+            LogicalType::decimal(4, 5),
+            Err(Error::Conversion(_))
+        ));
+        assert!(matches!(
+            LogicalType::enum_type(&["a", "a"]),
+            Err(Error::Conversion(_))
+        ));
+        assert!(matches!(
+            LogicalType::enum_type(&["a\0"]),
+            Err(Error::Nul(_))
+        ));
+    }
+
+    #[cfg(feature = "duckdb-v1_1")]
+    #[test]
+    fn test_array() {
+        let array = LogicalType::array(&LogicalType::new(LogicalTypeId::Integer), 3);
+        assert_eq!(array.type_id(), LogicalTypeId::Array);
+        assert_eq!(array.array_size(), Some(3));
+        assert_eq!(
+            array.shape(),
+            LogicalTypeShape::Array {
+                child: Box::new(LogicalTypeShape::Primitive(LogicalTypeId::Integer)),
+                size: 3
+            }
+        );
+        assert_eq!(array.list_child_type(), None);
+        assert_eq!(LogicalType::new(LogicalTypeId::Integer).array_size(), None);
+    }
+
+    #[test]
+    fn test_clone_and_eq() -> Result<(), Error> {
+        let map = LogicalType::new_map_type(
+            &LogicalType::new(LogicalTypeId::Varchar),
+            &LogicalType::new(LogicalTypeId::Integer),
+        );
+        let struct_ = LogicalType::new_struct_type(vec![
+            ("amount", LogicalType::decimal(18, 3)?),
+            ("level", LogicalType::enum_type(&["low", "high"])?),
+            ("tags", map.clone()),
+        ])?;
+
+        for typ in [&map, &struct_] {
+            let clone = typ.clone();
+            assert_eq!(&clone, typ);
+            assert_eq!(clone.shape(), typ.shape());
+        }
+
+        assert_ne!(map, LogicalType::new(LogicalTypeId::Map));
+        assert_ne!(LogicalType::decimal(18, 3)?, LogicalType::decimal(18, 2)?);
+        assert_ne!(
+            LogicalType::enum_type(&["low", "high"])?,
+            LogicalType::enum_type(&["high", "low"])?
+        );
+
+        let types = HashSet::from([
+            map.clone(),
+            struct_.clone(),
+            map,
+            struct_,
+            LogicalType::decimal(18, 3)?,
+        ]);
+        assert_eq!(types.len(), 3);
+
+        Ok(())
+    }
 }
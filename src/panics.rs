@@ -0,0 +1,39 @@
+//! Keeps panics in extension code from unwinding into DuckDB
+//!
+//! Unwinding out of an `extern "C"` function is undefined behaviour, so every callback DuckDB calls runs the
+//! extension's code through [`catch_panic`] and reports a panic the same way as an error.
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Describes the payload of a panic, as reported to DuckDB.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        format!("extension panicked: {}", message)
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        format!("extension panicked: {}", message)
+    } else {
+        "extension panicked".to_owned()
+    }
+}
+
+/// Calls `f`, returning the message of its panic if it panics.
+///
+/// Whatever `f` captures is dropped or left as it was when it panicked, and isn't used by DuckDB afterwards.
+pub(crate) fn catch_panic<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| panic_message(payload.as_ref()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::panics::catch_panic;
+
+    #[test]
+    fn test_catch_panic() {
+        assert_eq!(catch_panic(|| 1), Ok(1));
+        assert_eq!(
+            catch_panic(|| panic!("no {}", "tables")),
+            Err::<(), _>("extension panicked: no tables".to_owned())
+        );
+    }
+}
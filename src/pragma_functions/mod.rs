@@ -0,0 +1,9 @@
+/// A pragma function is a function that is invoked through `PRAGMA` or `CALL`, either to run side effects or to
+/// produce a query that DuckDB executes in its place
+mod pragma_function;
+mod pragma_info;
+#[cfg(test)]
+mod test_integration;
+
+pub use self::pragma_function::PragmaFunction;
+pub use self::pragma_info::PragmaInfo;
@@ -0,0 +1,110 @@
+use crate::duckly::{
+    duckdb_create_pragma_function, duckdb_delete_callback_t, duckdb_destroy_pragma_function,
+    duckdb_pragma_function, duckdb_pragma_function_add_parameter,
+    duckdb_pragma_function_set_extra_info, duckdb_pragma_function_set_function,
+    duckdb_pragma_function_set_name, duckdb_pragma_function_set_query, duckdb_pragma_function_t,
+};
+use crate::logical_type::LogicalType;
+#[allow(unused)]
+use crate::pragma_functions::PragmaInfo;
+use crate::strings::with_c_str;
+use crate::Error;
+use std::ffi::c_void;
+
+/// A function that is invoked through `PRAGMA` or `CALL`
+#[derive(Debug)]
+pub struct PragmaFunction {
+    pub(crate) ptr: duckdb_pragma_function,
+}
+
+impl Drop for PragmaFunction {
+    fn drop(&mut self) {
+        unsafe {
+            duckdb_destroy_pragma_function(&mut self.ptr);
+        }
+    }
+}
+
+impl PragmaFunction {
+    /// Creates a new empty pragma function that is invoked with arguments,
+    /// as in `PRAGMA my_pragma('arg')` or `CALL my_pragma('arg')`.
+    pub fn new_call() -> Self {
+        Self {
+            ptr: unsafe { duckdb_create_pragma_function(false) },
+        }
+    }
+
+    /// Creates a new empty pragma function that is invoked without arguments, as in `PRAGMA my_pragma`.
+    pub fn new_statement() -> Self {
+        Self {
+            ptr: unsafe { duckdb_create_pragma_function(true) },
+        }
+    }
+
+    /// Sets the name of the given pragma function.
+    ///
+    /// # Arguments
+    ///  * `name`: The name of the pragma function
+    ///
+    /// returns: [`Error::Nul`] if the name contains a NUL byte, leaving it unset
+    pub fn set_name(&self, name: &str) -> Result<&Self, Error> {
+        with_c_str(name, |name| unsafe {
+            duckdb_pragma_function_set_name(self.ptr, name)
+        })?;
+        Ok(self)
+    }
+
+    /// Adds a parameter to the pragma function.
+    ///
+    /// Only functions created through [`PragmaFunction::new_call`] accept parameters.
+    ///
+    /// # Arguments
+    ///  * `logical_type`: The type of the parameter to add.
+    pub fn add_parameter(&self, logical_type: &LogicalType) -> &Self {
+        unsafe {
+            duckdb_pragma_function_add_parameter(self.ptr, logical_type.typ);
+        }
+        self
+    }
+
+    /// Sets the main function of the pragma function, which is run for its side effects.
+    ///
+    /// Any query set through [`PragmaInfo::set_query`] is ignored.
+    ///
+    /// # Arguments
+    ///  * `function`: The function
+    pub fn set_function(&self, func: duckdb_pragma_function_t) -> &Self {
+        unsafe {
+            duckdb_pragma_function_set_function(self.ptr, func);
+        }
+        self
+    }
+
+    /// Sets the query function of the pragma function.
+    ///
+    /// The function should produce the SQL that DuckDB executes in place of the pragma through [`PragmaInfo::set_query`].
+    ///
+    /// # Arguments
+    ///  * `function`: The query function
+    pub fn set_query(&self, func: duckdb_pragma_function_t) -> &Self {
+        unsafe {
+            duckdb_pragma_function_set_query(self.ptr, func);
+        }
+        self
+    }
+
+    /// Assigns extra information to the pragma function that can be fetched during execution.
+    ///
+    /// # Arguments
+    /// * `extra_info`: The extra information
+    /// * `destroy`: The callback that will be called to destroy the extra information (if any)
+    ///
+    /// # Safety
+    pub unsafe fn set_extra_info(
+        &self,
+        extra_info: *mut c_void,
+        destroy: duckdb_delete_callback_t,
+    ) {
+        duckdb_pragma_function_set_extra_info(self.ptr, extra_info, destroy);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::duckly::{
+    duckdb_pragma_get_extra_info, duckdb_pragma_get_parameter, duckdb_pragma_get_parameter_count,
+    duckdb_pragma_info, duckdb_pragma_set_error, duckdb_pragma_set_query, idx_t,
+};
+#[allow(unused)]
+use crate::pragma_functions::PragmaFunction;
+use crate::strings::{with_c_str, with_message};
+use crate::Value;
+
+/// An interface to retrieve the parameters of, and report the outcome of, a pragma function invocation
+#[derive(Debug)]
+pub struct PragmaInfo(duckdb_pragma_info);
+
+impl From<duckdb_pragma_info> for PragmaInfo {
+    fn from(ptr: duckdb_pragma_info) -> Self {
+        Self(ptr)
+    }
+}
+
+impl PragmaInfo {
+    /// Retrieves the number of parameters passed to the function.
+    pub fn get_parameter_count(&self) -> idx_t {
+        unsafe { duckdb_pragma_get_parameter_count(self.0) }
+    }
+    /// Retrieves the parameter at the given index.
+    ///
+    /// # Arguments
+    ///  * `index`: The index of the parameter to get
+    ///
+    /// returns: The value of the parameter
+    pub fn get_parameter(&self, param_index: idx_t) -> Value {
+        unsafe { Value::from(duckdb_pragma_get_parameter(self.0, param_index)) }
+    }
+    /// Sets the SQL that DuckDB executes in place of the pragma.
+    ///
+    /// Only used by functions registered through [`PragmaFunction::set_query`]. SQL containing a NUL byte fails
+    /// the pragma, as if [`PragmaInfo::set_error`] had been called.
+    ///
+    /// # Arguments
+    ///  * `query`: The SQL to execute
+    pub fn set_query(&self, query: &str) {
+        let set = with_c_str(query, |query| unsafe {
+            duckdb_pragma_set_query(self.0, query)
+        });
+        if let Err(error) = set {
+            self.set_error(&format!("invalid pragma query: {}", error));
+        }
+    }
+    /// Report that an error has occurred while executing the function.
+    ///
+    /// # Arguments
+    ///  * `error`: The error message
+    pub fn set_error(&self, error: &str) {
+        with_message(error, |error| unsafe {
+            duckdb_pragma_set_error(self.0, error)
+        })
+    }
+    /// Retrieves the extra info of the function as set in [`PragmaFunction::set_extra_info`]
+    ///
+    /// # Arguments
+    /// * `returns`: The extra info
+    pub fn get_extra_info<T>(&self) -> *mut T {
+        unsafe { duckdb_pragma_get_extra_info(self.0).cast() }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::constants::LogicalTypeId;
+use crate::database::Database;
+use crate::duckly::duckdb_pragma_info;
+use crate::pragma_functions::{PragmaFunction, PragmaInfo};
+use crate::testing::query;
+use crate::LogicalType;
+use std::error::Error;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+unsafe extern "C" fn echo(info: duckdb_pragma_info) {
+    let info = PragmaInfo::from(info);
+
+    let param = info.get_parameter(0).get_varchar();
+
+    info.set_query(&format!("SELECT '{}' AS echoed", param.to_str().unwrap()));
+}
+
+unsafe extern "C" fn flip(info: duckdb_pragma_info) {
+    let info = PragmaInfo::from(info);
+
+    let flag = info.get_extra_info::<AtomicBool>();
+
+    (*flag).store(true, Ordering::SeqCst);
+}
+
+unsafe extern "C" fn fail(info: duckdb_pragma_info) {
+    let info = PragmaInfo::from(info);
+
+    info.set_error("no caches to clear");
+}
+
+#[test]
+fn test_pragma_query() -> Result<(), Box<dyn Error>> {
+    let db = Database::new()?;
+    let conn = db.connect()?;
+
+    let pragma_function = PragmaFunction::new_call();
+    pragma_function
+        .set_name("echo")?
+        .add_parameter(&LogicalType::new(LogicalTypeId::Varchar))
+        .set_query(Some(echo));
+    conn.register_pragma_function(pragma_function)?;
+
+    assert_eq!(query(&conn, "PRAGMA echo('hello')")??, [["hello"]]);
+    assert_eq!(query(&conn, "CALL echo('world')")??, [["world"]]);
+
+    Ok(())
+}
+
+#[test]
+fn test_pragma_side_effects() -> Result<(), Box<dyn Error>> {
+    let db = Database::new()?;
+    let conn = db.connect()?;
+
+    let flag = Box::new(AtomicBool::new(false));
+    let flag_ptr: *const AtomicBool = &*flag;
+
+    let pragma_function = PragmaFunction::new_statement();
+    pragma_function.set_name("flip")?.set_function(Some(flip));
+    unsafe {
+        pragma_function.set_extra_info(Box::into_raw(flag).cast(), Some(drop_flag));
+    }
+    conn.register_pragma_function(pragma_function)?;
+
+    assert_eq!(query(&conn, "PRAGMA flip")?, Ok(vec![]));
+
+    assert!(unsafe { (*flag_ptr).load(Ordering::SeqCst) });
+
+    Ok(())
+}
+
+unsafe extern "C" fn drop_flag(ptr: *mut std::ffi::c_void) {
+    drop(Box::from_raw(ptr.cast::<AtomicBool>()));
+}
+
+#[test]
+fn test_pragma_error() -> Result<(), Box<dyn Error>> {
+    let db = Database::new()?;
+    let conn = db.connect()?;
+
+    let pragma_function = PragmaFunction::new_statement();
+    pragma_function
+        .set_name("clear_cache")?
+        .set_function(Some(fail));
+    conn.register_pragma_function(pragma_function)?;
+
+    let error = query(&conn, "PRAGMA clear_cache")?.unwrap_err();
+    assert!(error.contains("no caches to clear"), "{}", error);
+
+    Ok(())
+}
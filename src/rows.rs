@@ -0,0 +1,503 @@
+//! Maps Rust structs to the columns of a table function and the rows of a [`DataChunk`]
+//!
+//! Deriving [`DuckRow`](derive@crate::DuckRow) on a struct with named fields makes each field a column, named after
+//! the field, whose type comes from the field's [`DuckValue`] implementation:
+//!
+//! | Rust                                  | DuckDB                       |
+//! |---------------------------------------|------------------------------|
+//! | `bool`                                | `BOOLEAN`                    |
+//! | `i8`, `i16`, `i32`, `i64`, `i128`     | `TINYINT` .. `HUGEINT`       |
+//! | `u8`, `u16`, `u32`, `u64`             | `UTINYINT` .. `UBIGINT`      |
+//! | `f32`, `f64`                          | `FLOAT`, `DOUBLE`            |
+//! | `String`                              | `VARCHAR`                    |
+//! | `Vec<T>`                              | `T[]`                        |
+//! | `Option<T>`                           | `T`, with `None` as `NULL`   |
+//! | a struct deriving `DuckRow`           | `STRUCT` of its fields       |
+//!
+//! ```ignore
+//! #[derive(DuckRow)]
+//! struct Commit {
+//!     hash: String,
+//!     parents: Vec<String>,
+//!     author: Author,
+//!     message: Option<String>,
+//! }
+//!
+//! // in the bind function
+//! Commit::add_result_columns(&bind)?;
+//!
+//! // in the table function, writing only the columns the query uses
+//! Commit::write_chunk(&commits, &output, &init.get_column_indices())?;
+//! ```
+
+use crate::duckly::{
+    duckdb_hugeint, duckdb_list_entry, duckdb_string_t, duckdb_vector_size, idx_t,
+};
+use crate::table_functions::BindInfo;
+use crate::{DataChunk, Error, LogicalType, LogicalTypeId, Vector};
+
+/// A struct whose fields are the columns of a table, usually implemented with `#[derive(DuckRow)]`
+pub trait DuckRow: Sized {
+    /// The name and type of each column, in the order of the struct's fields
+    fn columns() -> Result<Vec<(&'static str, LogicalType)>, Error>;
+
+    /// Writes the field at `column` of each of `rows` to the rows of `vector` with the same index.
+    ///
+    /// # Safety
+    /// `vector` must be of the column's type, with room for every one of `rows`
+    unsafe fn write_column(rows: &[Self], column: usize, vector: &Vector<()>) -> Result<(), Error>;
+
+    /// Reads `row` from `vectors`, which hold each of the columns in order.
+    ///
+    /// # Safety
+    /// `vectors` must hold a vector of each column's type, all with more than `row` rows
+    unsafe fn read_row(vectors: &[Vector<()>], row: idx_t) -> Result<Self, Error>;
+
+    /// Adds a result column for each of the struct's fields, for use in a table function's bind
+    ///
+    /// returns: An error if one of the columns' types can't be created or a field name contains a NUL byte
+    fn add_result_columns(bind: &BindInfo) -> Result<(), Error> {
+        for (name, typ) in Self::columns()? {
+            bind.add_result_column(name, typ)?;
+        }
+        Ok(())
+    }
+
+    /// Writes `rows` to `chunk` and sets its size.
+    ///
+    /// # Arguments
+    /// * `rows`: The rows to write, no more than the chunk's capacity
+    /// * `chunk`: The chunk to write to, such as a table function's output
+    /// * `projection`: The column written to each of the chunk's vectors, as returned by
+    ///   [`InitInfo::get_column_indices`](crate::table_functions::InitInfo::get_column_indices). Indices past the last
+    ///   column, such as the row id DuckDB asks for when a query doesn't need any, are left unwritten.
+    /// * `returns`: [`Error::Conversion`] if there are too many rows, or a vector isn't of its column's type
+    fn write_chunk(rows: &[Self], chunk: &DataChunk, projection: &[idx_t]) -> Result<(), Error> {
+        let capacity = unsafe { duckdb_vector_size() };
+        if rows.len() as idx_t > capacity {
+            return Err(Error::Conversion(format!(
+                "{} rows don't fit in a chunk of {}",
+                rows.len(),
+                capacity
+            )));
+        }
+        let columns = Self::columns()?;
+        for (index, &column) in projection.iter().enumerate() {
+            let (name, typ) = match columns.get(column as usize) {
+                Some(column) => column,
+                None => continue,
+            };
+            let vector = checked_vector(chunk, index as idx_t, name, typ)?;
+            unsafe { Self::write_column(rows, column as usize, &vector)? };
+        }
+        chunk.set_size(rows.len() as idx_t);
+        Ok(())
+    }
+
+    /// Reads every row of `chunk`, which must hold each of the columns in order.
+    ///
+    /// returns: [`Error::Conversion`] if the chunk's vectors aren't of the columns' types, or a column that isn't an
+    /// `Option` holds a `NULL`
+    fn read_chunk(chunk: &DataChunk) -> Result<Vec<Self>, Error> {
+        let columns = Self::columns()?;
+        if chunk.get_column_count() != columns.len() as idx_t {
+            return Err(Error::Conversion(format!(
+                "expected {} columns, found {}",
+                columns.len(),
+                chunk.get_column_count()
+            )));
+        }
+        let vectors = columns
+            .iter()
+            .enumerate()
+            .map(|(index, (name, typ))| checked_vector(chunk, index as idx_t, name, typ))
+            .collect::<Result<Vec<_>, _>>()?;
+        (0..chunk.get_size())
+            .map(|row| unsafe { Self::read_row(&vectors, row) })
+            .collect()
+    }
+}
+
+/// Returns the vector at `index` of `chunk`, if it's of the column's type
+fn checked_vector(
+    chunk: &DataChunk,
+    index: idx_t,
+    name: &str,
+    typ: &LogicalType,
+) -> Result<Vector<()>, Error> {
+    if index >= chunk.get_column_count() {
+        return Err(Error::Conversion(format!(
+            "no vector for column {:?} at index {}",
+            name, index
+        )));
+    }
+    let vector = chunk.get_untyped_vector(index);
+    let actual = vector.get_column_type();
+    if actual != *typ {
+        return Err(Error::Conversion(format!(
+            "expected column {:?} to be {}, found {}",
+            name, typ, actual
+        )));
+    }
+    Ok(vector)
+}
+
+/// A value that can be stored in a DuckDB vector, as a field of a [`DuckRow`]
+pub trait DuckValue: Sized {
+    /// The type of the vectors holding this value
+    fn logical_type() -> Result<LogicalType, Error>;
+
+    /// Writes the value to `row` of `vector`.
+    ///
+    /// # Safety
+    /// `vector` must be of [`DuckValue::logical_type`], with more than `row` rows
+    unsafe fn write(&self, vector: &Vector<()>, row: idx_t) -> Result<(), Error>;
+
+    /// Writes a `NULL` to `row` of `vector`.
+    ///
+    /// # Safety
+    /// `vector` must be of [`DuckValue::logical_type`], with more than `row` rows
+    unsafe fn write_null(vector: &Vector<()>, row: idx_t) {
+        vector.ensure_validity_writable();
+        vector.get_validity().set_row_invalid(row);
+    }
+
+    /// Reads the value at `row` of `vector`, which is known not to be `NULL`.
+    ///
+    /// # Safety
+    /// `vector` must be of [`DuckValue::logical_type`], with more than `row` rows
+    unsafe fn read_valid(vector: &Vector<()>, row: idx_t) -> Result<Self, Error>;
+
+    /// Reads the value at `row` of `vector`.
+    ///
+    /// # Safety
+    /// `vector` must be of [`DuckValue::logical_type`], with more than `row` rows
+    unsafe fn read(vector: &Vector<()>, row: idx_t) -> Result<Self, Error> {
+        if !vector.get_validity().row_is_valid(row) {
+            return Err(Error::Conversion(format!(
+                "found a NULL {} where a value was expected",
+                vector.get_column_type()
+            )));
+        }
+        Self::read_valid(vector, row)
+    }
+}
+
+/// Returns a pointer to `row` of a vector whose values are stored as `T`
+pub(crate) unsafe fn value_at<T>(vector: &Vector<()>, row: idx_t) -> *mut T {
+    vector.raw_data().cast::<T>().add(row as usize)
+}
+
+macro_rules! primitive {
+    ($($ty:ty => $id:ident),* $(,)?) => {
+        $(
+            impl DuckValue for $ty {
+                fn logical_type() -> Result<LogicalType, Error> {
+                    Ok(LogicalType::new(LogicalTypeId::$id))
+                }
+
+                unsafe fn write(&self, vector: &Vector<()>, row: idx_t) -> Result<(), Error> {
+                    *value_at(vector, row) = *self;
+                    Ok(())
+                }
+
+                unsafe fn read_valid(vector: &Vector<()>, row: idx_t) -> Result<Self, Error> {
+                    Ok(*value_at(vector, row))
+                }
+            }
+        )*
+    };
+}
+
+primitive!(
+    i8 => Tinyint,
+    i16 => Smallint,
+    i32 => Integer,
+    i64 => Bigint,
+    u8 => Utinyint,
+    u16 => Usmallint,
+    u32 => Uinteger,
+    u64 => Ubigint,
+    f32 => Float,
+    f64 => Double,
+);
+
+impl DuckValue for bool {
+    fn logical_type() -> Result<LogicalType, Error> {
+        Ok(LogicalType::new(LogicalTypeId::Boolean))
+    }
+
+    unsafe fn write(&self, vector: &Vector<()>, row: idx_t) -> Result<(), Error> {
+        *value_at(vector, row) = u8::from(*self);
+        Ok(())
+    }
+
+    unsafe fn read_valid(vector: &Vector<()>, row: idx_t) -> Result<Self, Error> {
+        Ok(*value_at::<u8>(vector, row) != 0)
+    }
+}
+
+impl DuckValue for i128 {
+    fn logical_type() -> Result<LogicalType, Error> {
+        Ok(LogicalType::new(LogicalTypeId::Hugeint))
+    }
+
+    unsafe fn write(&self, vector: &Vector<()>, row: idx_t) -> Result<(), Error> {
+        *value_at(vector, row) = duckdb_hugeint {
+            lower: *self as u64,
+            upper: (*self >> 64) as i64,
+        };
+        Ok(())
+    }
+
+    unsafe fn read_valid(vector: &Vector<()>, row: idx_t) -> Result<Self, Error> {
+        let value = *value_at::<duckdb_hugeint>(vector, row);
+        Ok(i128::from(value.upper) << 64 | i128::from(value.lower))
+    }
+}
+
+impl DuckValue for String {
+    fn logical_type() -> Result<LogicalType, Error> {
+        Ok(LogicalType::new(LogicalTypeId::Varchar))
+    }
+
+    unsafe fn write(&self, vector: &Vector<()>, row: idx_t) -> Result<(), Error> {
+        vector.assign_string_element_len(row, self.as_ptr().cast(), self.len() as idx_t);
+        Ok(())
+    }
+
+    unsafe fn read_valid(vector: &Vector<()>, row: idx_t) -> Result<Self, Error> {
+        Ok(std::str::from_utf8(string_bytes(vector, row))?.to_owned())
+    }
+}
+
+/// Returns the bytes of the `VARCHAR` or `BLOB` at `row` of `vector`
+///
+/// # Safety
+/// `vector` must be a `VARCHAR` or `BLOB` vector with more than `row` rows, and outlive the bytes
+pub(crate) unsafe fn string_bytes<'a>(vector: &Vector<()>, row: idx_t) -> &'a [u8] {
+    crate::vector::string_bytes(&*value_at::<duckdb_string_t>(vector, row))
+}
+
+impl<T: DuckValue> DuckValue for Option<T> {
+    fn logical_type() -> Result<LogicalType, Error> {
+        T::logical_type()
+    }
+
+    unsafe fn write(&self, vector: &Vector<()>, row: idx_t) -> Result<(), Error> {
+        match self {
+            Some(value) => value.write(vector, row),
+            None => {
+                T::write_null(vector, row);
+                Ok(())
+            }
+        }
+    }
+
+    unsafe fn write_null(vector: &Vector<()>, row: idx_t) {
+        T::write_null(vector, row)
+    }
+
+    unsafe fn read_valid(vector: &Vector<()>, row: idx_t) -> Result<Self, Error> {
+        T::read_valid(vector, row).map(Some)
+    }
+
+    unsafe fn read(vector: &Vector<()>, row: idx_t) -> Result<Self, Error> {
+        if vector.get_validity().row_is_valid(row) {
+            Self::read_valid(vector, row)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<T: DuckValue> DuckValue for Vec<T> {
+    fn logical_type() -> Result<LogicalType, Error> {
+        Ok(LogicalType::new_list_type(&T::logical_type()?))
+    }
+
+    unsafe fn write(&self, vector: &Vector<()>, row: idx_t) -> Result<(), Error> {
+        // the elements of every row are appended to the one child vector
+        let offset = vector.list_size();
+        let length = self.len() as idx_t;
+        vector.reserve_list(offset + length)?;
+        let child = vector.list_child();
+        for (index, value) in self.iter().enumerate() {
+            value.write(&child, offset + index as idx_t)?;
+        }
+        vector.set_list_size(offset + length)?;
+        *value_at(vector, row) = duckdb_list_entry { offset, length };
+        Ok(())
+    }
+
+    unsafe fn write_null(vector: &Vector<()>, row: idx_t) {
+        // DuckDB reads the entries of NULL lists too, so this one must not point past the child vector
+        *value_at(vector, row) = duckdb_list_entry {
+            offset: vector.list_size(),
+            length: 0,
+        };
+        vector.ensure_validity_writable();
+        vector.get_validity().set_row_invalid(row);
+    }
+
+    unsafe fn read_valid(vector: &Vector<()>, row: idx_t) -> Result<Self, Error> {
+        let entry = *value_at::<duckdb_list_entry>(vector, row);
+        let child = vector.list_child();
+        (entry.offset..entry.offset + entry.length)
+            .map(|index| T::read(&child, index))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::duckly::duckdb_list_entry;
+    use crate::rows::{value_at, DuckRow};
+    use crate::{DataChunk, Error, LogicalType};
+
+    #[derive(crate::DuckRow, Debug, Clone, PartialEq)]
+    struct Author {
+        name: String,
+        email: Option<String>,
+    }
+
+    #[derive(crate::DuckRow, Debug, Clone, PartialEq)]
+    struct Commit {
+        hash: String,
+        parents: Vec<String>,
+        author: Option<Author>,
+        r#type: i32,
+        additions: u64,
+        signed: bool,
+        weights: Vec<Option<f64>>,
+    }
+
+    fn commits() -> Vec<Commit> {
+        vec![
+            Commit {
+                hash: "6a3c0b5d9e2f41a7b8c3d4e5f60718293a4b5c6d".to_string(),
+                parents: vec![],
+                author: Some(Author {
+                    name: "Ada".to_string(),
+                    email: None,
+                }),
+                r#type: 1,
+                additions: 12,
+                signed: true,
+                weights: vec![Some(0.5), None],
+            },
+            Commit {
+                hash: "b5".to_string(),
+                parents: vec![
+                    "6a3c".to_string(),
+                    "a parent hash that isn't inlined".to_string(),
+                ],
+                author: None,
+                r#type: -1,
+                additions: u64::MAX,
+                signed: false,
+                weights: vec![],
+            },
+        ]
+    }
+
+    fn chunk() -> Result<DataChunk, Error> {
+        Ok(DataChunk::new(
+            Commit::columns()?.into_iter().map(|(_, typ)| typ).collect(),
+        ))
+    }
+
+    #[test]
+    fn test_columns() -> Result<(), Error> {
+        let columns = Commit::columns()?
+            .into_iter()
+            .map(|(name, typ)| format!("{} {}", name, typ))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            columns,
+            [
+                "hash VARCHAR",
+                "parents VARCHAR[]",
+                "author STRUCT(name VARCHAR, email VARCHAR)",
+                "type INTEGER",
+                "additions UBIGINT",
+                "signed BOOLEAN",
+                "weights DOUBLE[]",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<(), Error> {
+        let commits = commits();
+        let chunk = chunk()?;
+        Commit::write_chunk(&commits, &chunk, &[0, 1, 2, 3, 4, 5, 6])?;
+        assert_eq!(chunk.get_size(), 2);
+        assert_eq!(Commit::read_chunk(&chunk)?, commits);
+        Ok(())
+    }
+
+    #[test]
+    fn test_projection() -> Result<(), Error> {
+        let chunk = DataChunk::new(vec![
+            "UBIGINT".parse::<LogicalType>()?,
+            "VARCHAR".parse()?,
+            "BIGINT".parse()?,
+        ]);
+        Commit::write_chunk(&commits(), &chunk, &[4, 0, u64::MAX])?;
+        assert_eq!(chunk.get_size(), 2);
+        assert_eq!(chunk.get_vector::<u64>(0)?.get_data_as_slice()[1], u64::MAX);
+
+        assert!(matches!(
+            Commit::write_chunk(&commits(), &chunk, &[0]),
+            Err(Error::Conversion(_))
+        ));
+        assert!(matches!(
+            Commit::read_chunk(&chunk),
+            Err(Error::Conversion(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_null_in_required_column() -> Result<(), Error> {
+        let chunk = chunk()?;
+        Commit::write_chunk(&commits(), &chunk, &[0, 1, 2, 3, 4, 5, 6])?;
+        chunk.get_untyped_vector(0).ensure_validity_writable();
+        chunk
+            .get_untyped_vector(0)
+            .get_validity()
+            .set_row_invalid(1);
+        assert!(matches!(
+            Commit::read_chunk(&chunk),
+            Err(Error::Conversion(_))
+        ));
+        Ok(())
+    }
+
+    #[derive(crate::DuckRow, Debug, Clone, PartialEq)]
+    struct Labels {
+        tags: Option<Vec<String>>,
+    }
+
+    #[test]
+    fn test_null_list() -> Result<(), Error> {
+        let labels = vec![
+            Labels {
+                tags: Some(vec!["a".to_string(), "b".to_string()]),
+            },
+            Labels { tags: None },
+            Labels {
+                tags: Some(vec!["c".to_string()]),
+            },
+        ];
+        let chunk = DataChunk::new(vec!["VARCHAR[]".parse()?]);
+        Labels::write_chunk(&labels, &chunk, &[0])?;
+
+        let entry = unsafe { *value_at::<duckdb_list_entry>(&chunk.get_untyped_vector(0), 1) };
+        assert_eq!((entry.offset, entry.length), (2, 0));
+        assert_eq!(Labels::read_chunk(&chunk)?, labels);
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+/// A storage extension serves the tables of a database attached with `ATTACH 'path' AS name (TYPE type)`
+mod storage_extension;
+#[cfg(test)]
+mod test_integration;
+
+pub(crate) use self::storage_extension::RawStorageExtension;
+pub use self::storage_extension::StorageExtension;
@@ -0,0 +1,133 @@
+use crate::duckly::{
+    duckdb_create_storage_extension, duckdb_destroy_storage_extension, duckdb_logical_type,
+    duckdb_storage_add_schema, duckdb_storage_add_table, duckdb_storage_extension,
+    duckdb_storage_extension_set_attach, duckdb_storage_extension_set_extra_info,
+    duckdb_storage_extension_set_name, duckdb_storage_extension_set_scan_function,
+    duckdb_storage_get_extra_info, duckdb_storage_get_path, duckdb_storage_info,
+    duckdb_storage_set_error,
+};
+use crate::panics::catch_panic;
+use crate::strings::{with_c_str, with_c_strs, with_message};
+use crate::table_functions::TableFunction;
+use crate::LogicalType;
+use std::error::Error;
+use std::ffi::{c_void, CStr};
+
+/// A backend for `ATTACH 'path' AS name (TYPE type)`, exposing the tables it serves as a catalog
+///
+/// When a database is attached, the schemas, tables and columns it contains are listed once. Every table is then
+/// read through the [`StorageExtension::scan`] table function, which is called with three `VARCHAR` parameters:
+/// the path of the attached database, the schema and the name of the table.
+pub trait StorageExtension: Send + Sync + 'static {
+    /// Lists the schemas of the database at `path`.
+    ///
+    /// The `main` schema always exists, whether or not it is listed.
+    fn schemas(&self, path: &str) -> Result<Vec<String>, Box<dyn Error>>;
+
+    /// Lists the tables in `schema` of the database at `path`.
+    fn tables(&self, path: &str, schema: &str) -> Result<Vec<String>, Box<dyn Error>>;
+
+    /// Lists the names and types of the columns of `table`.
+    ///
+    /// The scan must produce exactly these columns for the table.
+    fn columns(
+        &self,
+        path: &str,
+        schema: &str,
+        table: &str,
+    ) -> Result<Vec<(String, LogicalType)>, Box<dyn Error>>;
+
+    /// Creates the table function that reads the rows of a table.
+    ///
+    /// Its name and parameters are set when the extension is registered, so only the callbacks need to be set.
+    fn scan(&self) -> TableFunction;
+}
+
+unsafe extern "C" fn drop_boxed<T>(ptr: *mut c_void) {
+    drop(Box::from_raw(ptr.cast::<T>()));
+}
+
+unsafe fn describe<T: StorageExtension>(info: duckdb_storage_info) -> Result<(), Box<dyn Error>> {
+    let extension = &*duckdb_storage_get_extra_info(info).cast::<T>();
+    let path = CStr::from_ptr(duckdb_storage_get_path(info)).to_str()?;
+
+    for schema in extension.schemas(path)? {
+        with_c_str(&schema, |schema_name| -> Result<(), Box<dyn Error>> {
+            duckdb_storage_add_schema(info, schema_name);
+
+            for table in extension.tables(path, &schema)? {
+                let columns = extension.columns(path, &schema, &table)?;
+                let names = columns.iter().map(|(name, _)| name.as_str());
+                let types = columns
+                    .iter()
+                    .map(|(_, typ)| typ.typ)
+                    .collect::<Vec<duckdb_logical_type>>();
+
+                with_c_str(&table, |table_name| {
+                    with_c_strs(names, |names| {
+                        duckdb_storage_add_table(
+                            info,
+                            schema_name,
+                            table_name,
+                            columns.len() as _,
+                            names.as_ptr().cast_mut(),
+                            types.as_ptr(),
+                        )
+                    })
+                })??;
+            }
+            Ok(())
+        })??;
+    }
+    Ok(())
+}
+
+unsafe extern "C" fn attach<T: StorageExtension>(info: duckdb_storage_info) {
+    let error = match catch_panic(|| describe::<T>(info)) {
+        Ok(Ok(())) => return,
+        Ok(Err(error)) => error.to_string(),
+        Err(message) => message,
+    };
+    with_message(&error, |error| duckdb_storage_set_error(info, error));
+}
+
+/// The C representation of a [`StorageExtension`], wired up to forward to its methods
+#[derive(Debug)]
+pub(crate) struct RawStorageExtension {
+    pub(crate) ptr: duckdb_storage_extension,
+}
+
+impl RawStorageExtension {
+    pub(crate) fn new<T: StorageExtension>(
+        name: &str,
+        scan_function: &str,
+        extension: T,
+    ) -> Result<Self, crate::Error> {
+        let raw = Self {
+            ptr: unsafe { duckdb_create_storage_extension() },
+        };
+        with_c_str(name, |name| unsafe {
+            duckdb_storage_extension_set_name(raw.ptr, name)
+        })?;
+        with_c_str(scan_function, |scan_function| unsafe {
+            duckdb_storage_extension_set_scan_function(raw.ptr, scan_function)
+        })?;
+        unsafe {
+            duckdb_storage_extension_set_extra_info(
+                raw.ptr,
+                Box::into_raw(Box::new(extension)).cast(),
+                Some(drop_boxed::<T>),
+            );
+            duckdb_storage_extension_set_attach(raw.ptr, Some(attach::<T>));
+        }
+        Ok(raw)
+    }
+}
+
+impl Drop for RawStorageExtension {
+    fn drop(&mut self) {
+        unsafe {
+            duckdb_destroy_storage_extension(&mut self.ptr);
+        }
+    }
+}
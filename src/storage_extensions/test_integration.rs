@@ -0,0 +1,150 @@
+use crate::constants::LogicalTypeId;
+use crate::database::Database;
+use crate::duckly::{
+    duckdb_bind_info, duckdb_data_chunk, duckdb_free, duckdb_function_info, duckdb_init_info,
+};
+use crate::storage_extensions::StorageExtension;
+use crate::table_functions::{BindInfo, FunctionInfo, InitInfo, TableFunction};
+use crate::testing::query;
+use crate::{malloc_struct, DataChunk, LogicalType};
+use std::error::Error;
+
+/// Serves a table of the first five powers of each number, with the exponent picked by the table's name
+struct Powers;
+
+impl StorageExtension for Powers {
+    fn schemas(&self, _path: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        Ok(vec!["main".to_owned(), "extra".to_owned()])
+    }
+
+    fn tables(&self, _path: &str, schema: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        Ok(match schema {
+            "main" => vec!["squares".to_owned()],
+            _ => vec!["cubes".to_owned()],
+        })
+    }
+
+    fn columns(
+        &self,
+        _path: &str,
+        _schema: &str,
+        _table: &str,
+    ) -> Result<Vec<(String, LogicalType)>, Box<dyn Error>> {
+        Ok(vec![
+            ("n".to_owned(), LogicalType::new(LogicalTypeId::Bigint)),
+            ("value".to_owned(), LogicalType::new(LogicalTypeId::Bigint)),
+        ])
+    }
+
+    fn scan(&self) -> TableFunction {
+        let scan = TableFunction::default();
+        scan.set_bind(Some(bind))
+            .set_init(Some(init))
+            .set_function(Some(func));
+        scan
+    }
+}
+
+#[repr(C)]
+struct PowersBindData {
+    exponent: u32,
+}
+
+#[repr(C)]
+struct PowersInitData {
+    done: bool,
+}
+
+unsafe extern "C" fn bind(info: duckdb_bind_info) {
+    let info = BindInfo::from(info);
+
+    info.add_result_column("n", LogicalType::new(LogicalTypeId::Bigint))
+        .unwrap();
+    info.add_result_column("value", LogicalType::new(LogicalTypeId::Bigint))
+        .unwrap();
+
+    let data = malloc_struct::<PowersBindData>();
+    (*data).exponent = match info.get_parameter(2).get_varchar().to_str().unwrap() {
+        "squares" => 2,
+        _ => 3,
+    };
+    info.set_bind_data(data.cast(), Some(duckdb_free));
+}
+
+unsafe extern "C" fn init(info: duckdb_init_info) {
+    let info = InitInfo::from(info);
+
+    let data = malloc_struct::<PowersInitData>();
+    (*data).done = false;
+    info.set_init_data(data.cast(), Some(duckdb_free));
+}
+
+unsafe extern "C" fn func(info: duckdb_function_info, output: duckdb_data_chunk) {
+    let info = FunctionInfo::from(info);
+    let output = DataChunk::from(output);
+
+    let bind_data = info.get_bind_data::<PowersBindData>();
+    let init_data = info.get_init_data::<PowersInitData>();
+
+    if (*init_data).done {
+        output.set_size(0);
+        return;
+    }
+    (*init_data).done = true;
+
+    let mut n = output.get_vector::<i64>(0).expect("BIGINT column");
+    let mut value = output.get_vector::<i64>(1).expect("BIGINT column");
+    let n = n.get_data_as_slice();
+    let value = value.get_data_as_slice();
+    for i in 0..5 {
+        n[i] = i as i64;
+        value[i] = (i as i64).pow((*bind_data).exponent);
+    }
+    output.set_size(5);
+}
+
+#[test]
+fn test_attach() -> Result<(), Box<dyn Error>> {
+    let db = Database::new()?;
+    db.register_storage_extension("powers", Powers)?;
+    let conn = db.connect()?;
+
+    query(&conn, "ATTACH 'anywhere' AS p (TYPE powers)")??;
+
+    assert_eq!(
+        query(&conn, "SELECT sum(value) FROM p.main.squares")??,
+        [["30"]]
+    );
+    assert_eq!(
+        query(&conn, "SELECT sum(value) FROM p.extra.cubes")??,
+        [["100"]]
+    );
+    assert_eq!(
+        query(
+            &conn,
+            "SELECT count(*) FROM information_schema.tables WHERE table_catalog = 'p'"
+        )??,
+        [["2"]]
+    );
+
+    let error = query(&conn, "SELECT * FROM p.main.cubes")?.unwrap_err();
+    assert!(error.contains("cubes"), "{}", error);
+
+    Ok(())
+}
+
+#[test]
+fn test_register_without_scan() -> Result<(), Box<dyn Error>> {
+    let db = Database::new()?;
+    let conn = db.connect()?;
+    let scan = Powers.scan();
+    scan.set_name("powers_scan")?;
+    conn.register_table_function(scan)?;
+
+    assert!(db.register_storage_extension("powers", Powers).is_err());
+
+    let error = query(&conn, "ATTACH 'anywhere' AS p (TYPE powers)")?.unwrap_err();
+    assert!(error.contains("powers"), "{}", error);
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+//! Passes Rust strings to the C API, which needs them NUL terminated and alive for the whole call
+//!
+//! DuckDB copies every string it's given before the call returns, so a string only has to outlive the call. The
+//! pointers handed to the closures below must not be kept past it.
+
+use crate::Error;
+use std::ffi::CString;
+use std::os::raw::c_char;
+
+/// Calls `f` with a NUL terminated copy of `s`, which is freed once `f` returns.
+///
+/// returns: [`Error::Nul`] if `s` contains a NUL byte, as DuckDB would only see the part before it
+pub(crate) fn with_c_str<R>(s: &str, f: impl FnOnce(*const c_char) -> R) -> Result<R, Error> {
+    let s = CString::new(s)?;
+    Ok(f(s.as_ptr()))
+}
+
+/// Calls `f` with NUL terminated copies of `strs`, in order, which are freed once `f` returns.
+///
+/// returns: [`Error::Nul`] if any of `strs` contains a NUL byte, without calling `f`
+pub(crate) fn with_c_strs<'a, R>(
+    strs: impl IntoIterator<Item = &'a str>,
+    f: impl FnOnce(&[*const c_char]) -> R,
+) -> Result<R, Error> {
+    let strs = strs
+        .into_iter()
+        .map(CString::new)
+        .collect::<Result<Vec<_>, _>>()?;
+    let ptrs = strs.iter().map(|s| s.as_ptr()).collect::<Vec<_>>();
+    Ok(f(&ptrs))
+}
+
+/// Calls `f` with a NUL terminated copy of an error message, which is freed once `f` returns.
+///
+/// Unlike [`with_c_str`] this can't fail, as there is no way left to report a failure: any NUL bytes in the message
+/// are escaped as `\0`.
+pub(crate) fn with_message<R>(message: &str, f: impl FnOnce(*const c_char) -> R) -> R {
+    let message = CString::new(message.replace('\0', "\\0")).expect("NUL bytes are escaped");
+    f(message.as_ptr())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::strings::{with_c_str, with_c_strs, with_message};
+    use crate::Error;
+    use std::ffi::CStr;
+
+    #[test]
+    fn test_with_c_str() {
+        let name = "a column name long enough to be allocated on the heap";
+        let copied = with_c_str(name, |ptr| unsafe { CStr::from_ptr(ptr) }.to_owned()).unwrap();
+        assert_eq!(copied.to_str().unwrap(), name);
+
+        assert!(matches!(with_c_str("a\0b", |_| ()), Err(Error::Nul(_))));
+    }
+
+    #[test]
+    fn test_with_c_str_outlives_the_call() {
+        // Reads each copy after allocating more, which would reuse a copy that was freed too early. Under Miri
+        // (`cargo miri test strings`) that is reported as a use after free.
+        let names = ["first name", "second name", "third name"];
+        let copied = with_c_strs(names, |ptrs| {
+            let filler = (0..64).map(|i| vec![i as u8; 32]).collect::<Vec<_>>();
+            let copied = ptrs
+                .iter()
+                .map(|ptr| unsafe { CStr::from_ptr(*ptr) }.to_str().unwrap().to_owned())
+                .collect::<Vec<_>>();
+            drop(filler);
+            copied
+        })
+        .unwrap();
+        assert_eq!(copied, names);
+
+        let copied = with_c_str("a name", |ptr| {
+            let filler = vec![0xffu8; 16];
+            let name = unsafe { CStr::from_ptr(ptr) }.to_owned();
+            drop(filler);
+            name
+        })
+        .unwrap();
+        assert_eq!(copied.to_str().unwrap(), "a name");
+
+        assert!(matches!(
+            with_c_strs(["a", "b\0c"], |_| unreachable!()),
+            Err(Error::Nul(_))
+        ));
+    }
+
+    #[test]
+    fn test_with_message() {
+        let copied = with_message("bad\0input", |ptr| {
+            unsafe { CStr::from_ptr(ptr) }.to_owned()
+        });
+        assert_eq!(copied.to_str().unwrap(), "bad\\0input");
+    }
+}
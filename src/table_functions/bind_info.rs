@@ -1,13 +1,15 @@
 use crate::duckly::{
-    duckdb_bind_add_result_column, duckdb_bind_get_extra_info, duckdb_bind_get_parameter,
-    duckdb_bind_get_parameter_count, duckdb_bind_info, duckdb_bind_set_bind_data,
-    duckdb_bind_set_cardinality, duckdb_bind_set_error, idx_t,
+    duckdb_bind_add_result_column, duckdb_bind_get_extension_option, duckdb_bind_get_extra_info,
+    duckdb_bind_get_parameter, duckdb_bind_get_parameter_count, duckdb_bind_info,
+    duckdb_bind_set_bind_data, duckdb_bind_set_cardinality, duckdb_bind_set_error, idx_t,
 };
+use crate::strings::{with_c_str, with_message};
 #[allow(unused)]
-use crate::table_functions::TableFunction;
-use crate::{as_string, LogicalType, Value};
+use crate::table_functions::{FunctionInfo, InitInfo, TableFunction};
+#[allow(unused)]
+use crate::Database;
+use crate::{Error, LogicalType, Value};
 use std::ffi::c_void;
-use std::os::raw::c_char;
 
 /// An interface to store and retrieve data during the function bind stage
 #[derive(Debug)]
@@ -21,19 +23,25 @@ impl BindInfo {
     /// # Arguments
     ///  * `name`: The name of the column
     ///  * `type`: The logical type of the column
-    pub fn add_result_column(&self, column_name: &str, column_type: LogicalType) {
-        unsafe {
-            duckdb_bind_add_result_column(self.ptr, as_string!(column_name), column_type.typ);
-        }
+    ///
+    /// returns: [`Error::Nul`] if the name contains a NUL byte, leaving the column out
+    pub fn add_result_column(
+        &self,
+        column_name: &str,
+        column_type: LogicalType,
+    ) -> Result<(), Error> {
+        with_c_str(column_name, |name| unsafe {
+            duckdb_bind_add_result_column(self.ptr, name, column_type.typ)
+        })
     }
     /// Report that an error has occurred while calling bind.
     ///
     /// # Arguments
     ///  * `error`: The error message
     pub fn set_error(&self, error: &str) {
-        unsafe {
-            duckdb_bind_set_error(self.ptr, as_string!(error));
-        }
+        with_message(error, |error| unsafe {
+            duckdb_bind_set_error(self.ptr, error)
+        });
     }
     /// Sets the user-provided bind data in the bind object. This object can be retrieved again during execution.
     ///
@@ -79,6 +87,21 @@ impl BindInfo {
     pub fn get_extra_info<T>(&self) -> *const T {
         unsafe { duckdb_bind_get_extra_info(self.ptr).cast() }
     }
+    /// Retrieves the current value of a setting, such as one added with [`Database::add_extension_option`], as
+    /// set on the connection running the function or, failing that, globally.
+    ///
+    /// Settings can only be read during bind. To use one in [`InitInfo`] or [`FunctionInfo`], store its value in
+    /// the bind data with [`BindInfo::set_bind_data`].
+    ///
+    /// # Arguments
+    ///  * `name`: The name of the setting
+    /// returns: The value of the setting, or `None` if there is no such setting or it has no value
+    pub fn get_extension_option(&self, name: &str) -> Result<Option<Value>, Error> {
+        let value = with_c_str(name, |name| unsafe {
+            duckdb_bind_get_extension_option(self.ptr, name)
+        })?;
+        Ok((!value.is_null()).then(|| Value::from(value)))
+    }
 }
 
 impl From<duckdb_bind_info> for BindInfo {
@@ -1,11 +1,10 @@
-use crate::as_string;
 use crate::duckly::{
     duckdb_function_get_bind_data, duckdb_function_get_extra_info, duckdb_function_get_init_data,
     duckdb_function_get_local_init_data, duckdb_function_info, duckdb_function_set_error,
 };
+use crate::strings::with_message;
 #[allow(unused)]
 use crate::table_functions::{BindInfo, InitInfo, TableFunction};
-use std::os::raw::c_char;
 
 /// An interface to store and retrieve data during the function execution stage
 #[derive(Debug)]
@@ -17,9 +16,9 @@ impl FunctionInfo {
     /// # Arguments
     ///  * `error`: The error message
     pub fn set_error(&self, error: &str) {
-        unsafe {
-            duckdb_function_set_error(self.0, as_string!(error));
-        }
+        with_message(error, |error| unsafe {
+            duckdb_function_set_error(self.0, error)
+        });
     }
     /// Gets the bind data set by [`BindInfo::set_bind_data`] during the bind.
     ///
@@ -9,17 +9,21 @@ use crate::{
         duckdb_replacement_scan_add_parameter, duckdb_replacement_scan_info,
         duckdb_replacement_scan_set_error, duckdb_replacement_scan_set_function_name,
     },
+    strings::{with_c_str, with_message},
     Value,
 };
-use std::ffi::CString;
 pub struct ReplacementScanInfo(pub(crate) duckdb_replacement_scan_info);
 
 impl ReplacementScanInfo {
     /// Sets the replacement function name to use. If this function is called in the replacement callback, the replacement scan is performed. If it is not called, the replacement callback is not performed.
+    ///
+    /// A name containing a NUL byte fails the query, as if [`ReplacementScanInfo::set_error`] had been called.
     pub fn set_function_name(&mut self, function_name: &str) {
-        unsafe {
-            let function_name = CString::new(function_name).unwrap();
-            duckdb_replacement_scan_set_function_name(self.0, function_name.as_ptr());
+        let set = with_c_str(function_name, |name| unsafe {
+            duckdb_replacement_scan_set_function_name(self.0, name)
+        });
+        if let Err(error) = set {
+            self.set_error(&format!("invalid function name: {}", error));
         }
     }
     /// Adds a parameter to the replacement scan function.
@@ -30,10 +34,9 @@ impl ReplacementScanInfo {
     }
     /// Report that an error has occurred while executing the replacement scan.
     pub fn set_error(&mut self, error: &str) {
-        unsafe {
-            let error = CString::new(error).unwrap();
-            duckdb_replacement_scan_set_error(self.0, error.as_ptr());
-        }
+        with_message(error, |error| unsafe {
+            duckdb_replacement_scan_set_error(self.0, error)
+        });
     }
 }
 
@@ -7,9 +7,11 @@ use crate::duckly::{
     duckdb_table_function_supports_projection_pushdown,
 };
 use crate::logical_type::LogicalType;
+use crate::strings::with_c_str;
 #[allow(unused)]
 use crate::table_functions::InitInfo;
-use std::ffi::{c_void, CString};
+use crate::Error;
+use std::ffi::c_void;
 
 /// A function that returns a queryable table
 #[derive(Debug)]
@@ -99,12 +101,13 @@ impl TableFunction {
     ///
     /// # Arguments
     ///  * `name`: The name of the table function
-    pub fn set_name(&self, name: &str) -> &TableFunction {
-        unsafe {
-            let string = CString::from_vec_unchecked(name.as_bytes().into());
-            duckdb_table_function_set_name(self.ptr, string.as_ptr());
-        }
-        self
+    ///
+    /// returns: [`Error::Nul`] if the name contains a NUL byte, leaving it unset
+    pub fn set_name(&self, name: &str) -> Result<&TableFunction, Error> {
+        with_c_str(name, |name| unsafe {
+            duckdb_table_function_set_name(self.ptr, name)
+        })?;
+        Ok(self)
     }
 
     /// Assigns extra information to the table function that can be fetched during binding, etc.
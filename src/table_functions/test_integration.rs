@@ -5,10 +5,11 @@ use crate::duckly::{
 };
 use crate::duckly::{
     duckdb_destroy_result, duckdb_free, duckdb_result, duckdb_result_error,
-    duckdb_state_DuckDBError, duckdb_value_varchar,
+    duckdb_state_DuckDBError, duckdb_string_t, duckdb_value_varchar,
 };
 use crate::table_functions::{BindInfo, FunctionInfo, InitInfo, TableFunction};
-use crate::{malloc_struct, DataChunk, LogicalType};
+use crate::testing::query;
+use crate::{malloc_struct, DataChunk, LogicalType, Value};
 use std::error::Error;
 use std::ffi::{CStr, CString};
 use std::mem;
@@ -30,7 +31,9 @@ unsafe extern "C" fn func(info: duckdb_function_info, output: duckdb_data_chunk)
     } else {
         (*init_info).done = true;
 
-        let vector = output.get_vector::<&str>(0);
+        let vector = output
+            .get_vector::<duckdb_string_t>(0)
+            .expect("VARCHAR column");
 
         let string = CString::new("hello world").expect("unable to build string");
         vector.assign_string_element(0, string.as_ptr());
@@ -52,7 +55,8 @@ unsafe extern "C" fn init(info: duckdb_init_info) {
 unsafe extern "C" fn bind(info: duckdb_bind_info) {
     let info = BindInfo::from(info);
 
-    info.add_result_column("column0", LogicalType::new(LogicalTypeId::Varchar));
+    info.add_result_column("column0", LogicalType::new(LogicalTypeId::Varchar))
+        .unwrap();
 
     let param = info.get_parameter(0).get_varchar();
 
@@ -66,8 +70,8 @@ fn test_database_creation() -> Result<(), Box<dyn Error>> {
 
     let table_function = TableFunction::default();
     table_function
-        .add_parameter(&LogicalType::new(LogicalTypeId::Json))
-        .set_name("read_json")
+        .add_parameter(&LogicalType::new(LogicalTypeId::Varchar))
+        .set_name("read_json")?
         .supports_pushdown(false)
         .set_function(Some(func))
         .set_init(Some(init))
@@ -107,3 +111,210 @@ fn test_database_creation() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[repr(C)]
+struct BatchSizeBindData {
+    batch_size: i64,
+}
+
+unsafe extern "C" fn batch_size_func(info: duckdb_function_info, output: duckdb_data_chunk) {
+    let info = FunctionInfo::from(info);
+    let output = DataChunk::from(output);
+
+    let init_info = info.get_init_data::<TestInitInfo>();
+
+    if (*init_info).done {
+        output.set_size(0);
+    } else {
+        (*init_info).done = true;
+
+        let bind_data = info.get_bind_data::<BatchSizeBindData>();
+        let mut vector = output.get_vector::<i64>(0).expect("BIGINT column");
+        vector.get_data_as_slice()[0] = (*bind_data).batch_size;
+
+        output.set_size(1);
+    }
+}
+
+unsafe extern "C" fn batch_size_bind(info: duckdb_bind_info) {
+    let info = BindInfo::from(info);
+
+    info.add_result_column("batch_size", LogicalType::new(LogicalTypeId::Bigint))
+        .unwrap();
+
+    assert!(info
+        .get_extension_option("test_ext.missing")
+        .unwrap()
+        .is_none());
+    let batch_size = info
+        .get_extension_option("test_ext.batch_size")
+        .unwrap()
+        .expect("option not found");
+
+    // settings can't be read after bind, so the value is carried into the function through the bind data
+    let data = malloc_struct::<BatchSizeBindData>();
+    (*data).batch_size = batch_size.get_int64();
+    info.set_bind_data(data.cast(), Some(duckdb_free));
+}
+
+#[test]
+fn test_extension_option() -> Result<(), Box<dyn Error>> {
+    let db = Database::new()?;
+    db.add_extension_option(
+        "test_ext.batch_size",
+        "The number of rows per batch",
+        &LogicalType::new(LogicalTypeId::Bigint),
+        Value::new_int64(1024),
+        |value| {
+            let batch_size = value.get_int64();
+            if batch_size <= 0 {
+                Err("batch size must be positive")?;
+            }
+            assert!(
+                batch_size <= 1 << 20,
+                "batch size {} is too large",
+                batch_size
+            );
+            Ok(())
+        },
+    )?;
+
+    let conn = db.connect()?;
+    let table_function = TableFunction::default();
+    table_function
+        .set_name("batch_size")?
+        .set_function(Some(batch_size_func))
+        .set_init(Some(init))
+        .set_bind(Some(batch_size_bind));
+    conn.register_table_function(table_function)?;
+
+    let other = db.connect()?;
+
+    assert_eq!(query(&conn, "SELECT * FROM batch_size()")??, [["1024"]]);
+
+    query(&conn, "SET test_ext.batch_size = 4096")??;
+
+    assert_eq!(query(&conn, "SELECT * FROM batch_size()")??, [["4096"]]);
+    assert_eq!(query(&other, "SELECT * FROM batch_size()")??, [["1024"]]);
+    assert_eq!(
+        query(&conn, "SELECT current_setting('test_ext.batch_size')")??,
+        [["4096"]]
+    );
+    let batch_size = conn.get_extension_option("test_ext.batch_size")?;
+    assert_eq!(batch_size.map(|value| value.get_int64()), Some(4096));
+    let batch_size = other.get_extension_option("test_ext.batch_size")?;
+    assert_eq!(batch_size.map(|value| value.get_int64()), Some(1024));
+    assert!(conn.get_extension_option("test_ext.missing")?.is_none());
+
+    let error = query(&conn, "SET test_ext.batch_size = 0")?.unwrap_err();
+    assert!(error.contains("must be positive"), "{}", error);
+    let error = query(&conn, "SET test_ext.batch_size = 2000000")?.unwrap_err();
+    assert!(error.contains("too large"), "{}", error);
+
+    Ok(())
+}
+
+unsafe extern "C" fn failing_func(info: duckdb_function_info, output: duckdb_data_chunk) {
+    let info = FunctionInfo::from(info);
+    // built at runtime, so the message is freed as soon as set_error returns
+    let error = format!("{} failed while scanning row {}", "failing", 42);
+    info.set_error(&error);
+    DataChunk::from(output).set_size(0);
+}
+
+unsafe extern "C" fn failing_bind(info: duckdb_bind_info) {
+    let info = BindInfo::from(info);
+    let stage = info.get_parameter(0).get_varchar();
+
+    match stage.to_str().unwrap() {
+        "bind" => info.set_error(&format!("{} failed while binding", "failing")),
+        "nul" => {
+            if let Err(error) =
+                info.add_result_column("bad\0name", LogicalType::new(LogicalTypeId::Bigint))
+            {
+                info.set_error(&format!("invalid column name: {}", error));
+            }
+        }
+        _ => {
+            let name = format!("a column name too long to be inlined number {}", 1);
+            info.add_result_column(&name, LogicalType::new(LogicalTypeId::Bigint))
+                .unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_strings_passed_to_duckdb() -> Result<(), Box<dyn Error>> {
+    let db = Database::new()?;
+    let conn = db.connect()?;
+
+    assert!(matches!(
+        TableFunction::default().set_name("bad\0name"),
+        Err(crate::Error::Nul(_))
+    ));
+
+    let table_function = TableFunction::default();
+    table_function
+        .add_parameter(&LogicalType::new(LogicalTypeId::Varchar))
+        .set_name("failing")?
+        .set_function(Some(failing_func))
+        .set_init(Some(init))
+        .set_bind(Some(failing_bind));
+    conn.register_table_function(table_function)?;
+
+    assert_eq!(
+        query(&conn, "DESCRIBE SELECT * FROM failing('function')")??[0][0],
+        "a column name too long to be inlined number 1"
+    );
+
+    let error = query(&conn, "SELECT * FROM failing('function')")?.unwrap_err();
+    assert!(
+        error.contains("failing failed while scanning row 42"),
+        "{}",
+        error
+    );
+
+    let error = query(&conn, "SELECT * FROM failing('bind')")?.unwrap_err();
+    assert!(error.contains("failing failed while binding"), "{}", error);
+
+    let error = query(&conn, "SELECT * FROM failing('nul')")?.unwrap_err();
+    assert!(error.contains("invalid column name"), "{}", error);
+
+    Ok(())
+}
+
+#[test]
+fn test_registration_error() -> Result<(), Box<dyn Error>> {
+    let db = Database::new()?;
+    let conn = db.connect()?;
+
+    let failing = || -> Result<TableFunction, crate::Error> {
+        let table_function = TableFunction::default();
+        table_function
+            .add_parameter(&LogicalType::new(LogicalTypeId::Varchar))
+            .set_name("failing")?
+            .set_function(Some(failing_func))
+            .set_init(Some(init))
+            .set_bind(Some(failing_bind));
+        Ok(table_function)
+    };
+    conn.register_table_function(failing()?)?;
+    match conn.register_table_function(failing()?) {
+        Err(crate::Error::Registration { what, message }) => {
+            assert_eq!(what, "table function");
+            assert!(message.contains("already exists"), "{}", message);
+        }
+        other => panic!("expected a registration error, got {:?}", other),
+    }
+
+    let incomplete = TableFunction::default();
+    incomplete.set_name("incomplete")?;
+    match conn.register_table_function(incomplete) {
+        Err(crate::Error::Registration { message, .. }) => {
+            assert!(message.contains("bind function"), "{}", message);
+        }
+        other => panic!("expected a registration error, got {:?}", other),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,308 @@
+//! Runs DuckDB's sqllogictest `.test` files from `cargo test`, against a database with the extension loaded
+//!
+//! ```ignore
+//! #[test]
+//! fn test_sql() -> Result<(), Error> {
+//!     duckdb_extension_framework::testing::run_file("test/sql/delta.test", init)
+//! }
+//! ```
+//!
+//! Supported are `statement ok`, `statement error`, `query` with `I`, `T` and `R` column types, the `nosort`,
+//! `rowsort` and `valuesort` sort modes and result labels. `require` is ignored and `halt` ends the file early.
+//! Results are written one row per line with tab separated values, or one value per line. NULL is written as
+//! `NULL` and the empty string as `(empty)`.
+
+mod parser;
+#[cfg(test)]
+mod test_integration;
+
+pub use self::parser::{parse, ColumnType, ParseError, Record, SortMode};
+
+use crate::duckly::{
+    duckdb_column_count, duckdb_destroy_result, duckdb_free, duckdb_query, duckdb_result,
+    duckdb_result_error, duckdb_row_count, duckdb_state_DuckDBError, duckdb_value_is_null,
+    duckdb_value_varchar,
+};
+use crate::{Connection, Database, Error};
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::path::Path;
+use std::{error, fmt, fs, io, mem};
+
+/// A record whose outcome didn't match the file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.file, self.line, self.message)
+    }
+}
+
+impl error::Error for Mismatch {}
+
+/// Every mismatch found while running a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatches(pub Vec<Mismatch>);
+
+impl fmt::Display for Mismatches {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} record(s) failed", self.0.len())?;
+        for mismatch in &self.0 {
+            write!(f, "\n{}", mismatch)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for Mismatches {}
+
+/// The values of a result, rendered as sqllogictest does
+pub(crate) type Rows = Vec<Vec<String>>;
+
+/// Runs the given query, returning either every value of the result or the error message
+pub(crate) fn query(conn: &Connection, sql: &str) -> Result<Result<Rows, String>, Error> {
+    let sql = CString::new(sql)?;
+
+    unsafe {
+        let mut result: duckdb_result = mem::zeroed();
+
+        let outcome = if duckdb_query(conn.get_ptr(), sql.as_ptr(), &mut result)
+            == duckdb_state_DuckDBError
+        {
+            let error = CStr::from_ptr(duckdb_result_error(&mut result));
+            Err(error.to_string_lossy().into_owned())
+        } else {
+            let columns = duckdb_column_count(&mut result);
+            let rows = (0..duckdb_row_count(&mut result))
+                .map(|row| {
+                    (0..columns)
+                        .map(|col| {
+                            if duckdb_value_is_null(&mut result, col, row) {
+                                return "NULL".to_owned();
+                            }
+                            let ptr = duckdb_value_varchar(&mut result, col, row);
+                            let value = CStr::from_ptr(ptr).to_string_lossy().into_owned();
+                            duckdb_free(ptr.cast());
+                            if value.is_empty() {
+                                "(empty)".to_owned()
+                            } else {
+                                value
+                            }
+                        })
+                        .collect()
+                })
+                .collect();
+            Ok(rows)
+        };
+
+        duckdb_destroy_result(&mut result);
+
+        Ok(outcome)
+    }
+}
+
+fn sort(rows: &mut Rows, sort_mode: SortMode) {
+    match sort_mode {
+        SortMode::NoSort => {}
+        SortMode::RowSort => rows.sort(),
+        SortMode::ValueSort => {
+            let mut values: Vec<String> = rows.drain(..).flatten().collect();
+            values.sort();
+            rows.extend(values.into_iter().map(|value| vec![value]));
+        }
+    }
+}
+
+fn values_match(typ: ColumnType, actual: &str, expected: &str) -> bool {
+    if actual == expected {
+        return true;
+    }
+    match (typ, actual.parse::<f64>(), expected.parse::<f64>()) {
+        (ColumnType::Real, Ok(actual), Ok(expected)) => {
+            (actual - expected).abs() <= 1e-6 * actual.abs().max(expected.abs()).max(1.0)
+        }
+        _ => false,
+    }
+}
+
+fn results_match(types: &[ColumnType], actual: &[Vec<String>], expected: &[Vec<String>]) -> bool {
+    actual.len() == expected.len()
+        && actual.iter().zip(expected).all(|(actual, expected)| {
+            actual.len() == expected.len()
+                && actual
+                    .iter()
+                    .zip(expected)
+                    .enumerate()
+                    .all(|(i, (actual, expected))| {
+                        // value sorted results lose their columns, so are compared as text
+                        let typ = if types.len() == expected.len() {
+                            types[i]
+                        } else {
+                            ColumnType::Text
+                        };
+                        values_match(typ, actual, expected)
+                    })
+        })
+}
+
+fn render(rows: &[Vec<String>]) -> String {
+    rows.iter()
+        .map(|row| row.join("\t"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Runs each record, returning a description of each one that didn't behave as expected
+fn run_records(conn: &Connection, records: Vec<Record>) -> Result<Vec<(usize, String)>, Error> {
+    let mut mismatches = vec![];
+    let mut labels: HashMap<String, Rows> = HashMap::new();
+
+    for record in records {
+        match record {
+            Record::Statement {
+                line,
+                sql,
+                expect_error,
+                error_message,
+            } => match (query(conn, &sql)?, expect_error) {
+                (Ok(_), false) => {}
+                (Ok(_), true) => mismatches.push((
+                    line,
+                    format!("statement succeeded, but should have failed:\n{}", sql),
+                )),
+                (Err(error), false) => {
+                    mismatches.push((line, format!("statement failed: {}\n{}", error, sql)))
+                }
+                (Err(error), true) => {
+                    if let Some(message) = error_message {
+                        if !error.contains(&message) {
+                            mismatches.push((
+                                line,
+                                format!(
+                                    "expected error containing \"{}\", got: {}",
+                                    message, error
+                                ),
+                            ));
+                        }
+                    }
+                }
+            },
+            Record::Query {
+                line,
+                sql,
+                types,
+                sort_mode,
+                label,
+                mut expected,
+            } => {
+                let mut actual = match query(conn, &sql)? {
+                    Ok(actual) => actual,
+                    Err(error) => {
+                        mismatches.push((line, format!("query failed: {}\n{}", error, sql)));
+                        continue;
+                    }
+                };
+
+                if let Some(row) = actual.first() {
+                    if row.len() != types.len() {
+                        mismatches.push((
+                            line,
+                            format!(
+                                "query returned {} columns, but {} types were given",
+                                row.len(),
+                                types.len()
+                            ),
+                        ));
+                        continue;
+                    }
+                }
+
+                sort(&mut actual, sort_mode);
+                sort(&mut expected, sort_mode);
+
+                // A labelled query with no results of its own is checked against the first with that label
+                if let Some(label) = label {
+                    let labelled = labels.entry(label).or_insert_with(|| expected.clone());
+                    if expected.is_empty() {
+                        expected = labelled.clone();
+                    }
+                }
+
+                if !results_match(&types, &actual, &expected) {
+                    mismatches.push((
+                        line,
+                        format!(
+                            "wrong results for:\n{}\nexpected:\n{}\nactual:\n{}",
+                            sql,
+                            render(&expected),
+                            render(&actual)
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(mismatches)
+}
+
+/// Runs a sqllogictest script against a new in-memory database, after applying the extension's `init` to it.
+///
+/// # Arguments
+/// * `name`: The name used for the script in mismatches, usually its file name
+/// * `script`: The contents of the script
+/// * `init`: The extension's init function, as passed to [`duckdb_extension`](crate::duckdb_extension)
+/// returns: [`Error::Extension`] holding a [`Mismatch`] if the script is invalid, [`Mismatches`] listing every
+/// record that failed, or the error returned by `init`
+pub fn run_script<F>(name: &str, script: &str, init: F) -> Result<(), Error>
+where
+    F: FnOnce(&Database) -> Result<(), Box<dyn error::Error>>,
+{
+    let records = parse(script).map_err(|error| {
+        Error::Extension(Box::new(Mismatch {
+            file: name.to_owned(),
+            line: error.line,
+            message: error.message,
+        }))
+    })?;
+
+    let db = Database::new()?;
+    init(&db).map_err(Error::Extension)?;
+    let conn = db.connect()?;
+
+    let mismatches = run_records(&conn, records)?;
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Extension(Box::new(Mismatches(
+            mismatches
+                .into_iter()
+                .map(|(line, message)| Mismatch {
+                    file: name.to_owned(),
+                    line,
+                    message,
+                })
+                .collect(),
+        ))))
+    }
+}
+
+/// Runs a sqllogictest file, see [`run_script`]
+pub fn run_file<F>(path: impl AsRef<Path>, init: F) -> Result<(), Error>
+where
+    F: FnOnce(&Database) -> Result<(), Box<dyn error::Error>>,
+{
+    let path = path.as_ref();
+    let script = fs::read_to_string(path).map_err(|error| {
+        io::Error::new(
+            error.kind(),
+            format!("could not read {}: {}", path.display(), error),
+        )
+    })?;
+    run_script(&path.display().to_string(), &script, init)
+}
@@ -0,0 +1,301 @@
+use std::error::Error;
+use std::fmt;
+
+/// The type of a column in a `query` record, which decides how its values are compared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    /// `I`, compared exactly
+    Integer,
+    /// `T`, compared exactly
+    Text,
+    /// `R`, compared with a small relative tolerance
+    Real,
+}
+
+/// How the results of a `query` record are ordered before being compared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortMode {
+    /// The results are compared in the order the query returns them
+    NoSort,
+    /// The rows are sorted
+    RowSort,
+    /// Every value is sorted individually, regardless of which row it's in
+    ValueSort,
+}
+
+/// A single test in a sqllogictest file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Record {
+    /// `statement ok` or `statement error`, optionally followed by `----` and part of the expected error message
+    Statement {
+        line: usize,
+        sql: String,
+        expect_error: bool,
+        error_message: Option<String>,
+    },
+    /// `query <types> [sort mode] [label]`, followed by `----` and the expected results
+    Query {
+        line: usize,
+        sql: String,
+        types: Vec<ColumnType>,
+        sort_mode: SortMode,
+        label: Option<String>,
+        /// The expected rows, already split into values
+        expected: Vec<Vec<String>>,
+    },
+}
+
+/// A sqllogictest file that could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+fn error<T>(line: usize, message: impl Into<String>) -> Result<T, ParseError> {
+    Err(ParseError {
+        line,
+        message: message.into(),
+    })
+}
+
+/// Splits the lines following a record's header into the SQL and, if there's a `----` separator, the lines after it
+fn take_body<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+) -> (Vec<&'a str>, Option<Vec<&'a str>>) {
+    let mut sql = vec![];
+    let mut results: Option<Vec<&str>> = None;
+    for (_, line) in lines {
+        if line.trim().is_empty() {
+            break;
+        }
+        match &mut results {
+            Some(results) => results.push(line),
+            None if line.trim_end() == "----" => results = Some(vec![]),
+            None => sql.push(line),
+        }
+    }
+    (sql, results)
+}
+
+fn parse_types(line: usize, types: &str) -> Result<Vec<ColumnType>, ParseError> {
+    types
+        .chars()
+        .map(|typ| match typ {
+            'I' => Ok(ColumnType::Integer),
+            'T' => Ok(ColumnType::Text),
+            'R' => Ok(ColumnType::Real),
+            _ => error(
+                line,
+                format!("unknown column type '{}' in \"{}\"", typ, types),
+            ),
+        })
+        .collect()
+}
+
+/// Splits the expected results into rows, which are either one row per line with tab separated values or,
+/// as in the original sqllogictest format, one value per line.
+fn parse_results(
+    line: usize,
+    results: &[&str],
+    columns: usize,
+) -> Result<Vec<Vec<String>>, ParseError> {
+    let split: Vec<Vec<String>> = results
+        .iter()
+        .map(|row| row.split('\t').map(str::to_owned).collect())
+        .collect();
+
+    if split.iter().all(|row| row.len() == columns) {
+        return Ok(split);
+    }
+    if split.iter().all(|row| row.len() == 1) && results.len() % columns == 0 {
+        let values: Vec<String> = results.iter().map(|&value| value.to_owned()).collect();
+        return Ok(values.chunks(columns).map(<[String]>::to_vec).collect());
+    }
+    error(
+        line,
+        format!("expected results don't have {} columns per row", columns),
+    )
+}
+
+/// Parses the records of a sqllogictest file.
+///
+/// `require` directives are accepted and ignored, as the extension under test is already loaded.
+pub fn parse(script: &str) -> Result<Vec<Record>, ParseError> {
+    let mut records = vec![];
+    let mut lines = script.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+    while let Some((line, header)) = lines.next() {
+        let header = header.trim();
+        if header.is_empty() || header.starts_with('#') {
+            continue;
+        }
+
+        let words: Vec<&str> = header.split_whitespace().collect();
+        match words[0] {
+            "require" => continue,
+            "halt" => break,
+            "statement" => {
+                let expect_error = match words.get(1) {
+                    Some(&"ok") => false,
+                    Some(&"error") => true,
+                    _ => return error(line, "expected `statement ok` or `statement error`"),
+                };
+                let (sql, message) = take_body(&mut lines);
+                if sql.is_empty() {
+                    return error(line, "statement has no SQL");
+                }
+                if message.is_some() && !expect_error {
+                    return error(line, "only `statement error` can have an expected result");
+                }
+                records.push(Record::Statement {
+                    line,
+                    sql: sql.join("\n"),
+                    expect_error,
+                    error_message: message.map(|message| message.join("\n")),
+                });
+            }
+            "query" => {
+                let types = match words.get(1) {
+                    Some(types) => parse_types(line, types)?,
+                    None => return error(line, "query is missing its column types"),
+                };
+                let sort_mode = match words.get(2) {
+                    None | Some(&"nosort") => SortMode::NoSort,
+                    Some(&"rowsort") => SortMode::RowSort,
+                    Some(&"valuesort") => SortMode::ValueSort,
+                    Some(mode) => return error(line, format!("unknown sort mode \"{}\"", mode)),
+                };
+                let label = words.get(3).map(|&label| label.to_owned());
+
+                let (sql, results) = take_body(&mut lines);
+                if sql.is_empty() {
+                    return error(line, "query has no SQL");
+                }
+                let results = match results {
+                    Some(results) => results,
+                    None => return error(line, "query is missing its `----` separator"),
+                };
+                if results.iter().any(|row| row.contains("values hashing to")) {
+                    return error(line, "hashed results aren't supported");
+                }
+                let expected = parse_results(line, &results, types.len())?;
+
+                records.push(Record::Query {
+                    line,
+                    sql: sql.join("\n"),
+                    types,
+                    sort_mode,
+                    label,
+                    expected,
+                });
+            }
+            directive => return error(line, format!("unsupported directive \"{}\"", directive)),
+        }
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::testing::parser::{parse, ColumnType, Record, SortMode};
+
+    #[test]
+    fn test_parse() {
+        let records = parse(
+            "# name: test/sql/echo.test
+require echo
+
+statement ok
+CREATE TABLE t AS
+SELECT 42 AS i
+
+statement error
+SELECT * FROM missing
+----
+does not exist
+
+query IT rowsort
+SELECT i, 'x' FROM t
+----
+42\tx
+
+query I nosort total
+SELECT 1 UNION ALL SELECT 2
+----
+1
+2
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            records,
+            vec![
+                Record::Statement {
+                    line: 4,
+                    sql: "CREATE TABLE t AS\nSELECT 42 AS i".to_owned(),
+                    expect_error: false,
+                    error_message: None,
+                },
+                Record::Statement {
+                    line: 8,
+                    sql: "SELECT * FROM missing".to_owned(),
+                    expect_error: true,
+                    error_message: Some("does not exist".to_owned()),
+                },
+                Record::Query {
+                    line: 13,
+                    sql: "SELECT i, 'x' FROM t".to_owned(),
+                    types: vec![ColumnType::Integer, ColumnType::Text],
+                    sort_mode: SortMode::RowSort,
+                    label: None,
+                    expected: vec![vec!["42".to_owned(), "x".to_owned()]],
+                },
+                Record::Query {
+                    line: 18,
+                    sql: "SELECT 1 UNION ALL SELECT 2".to_owned(),
+                    types: vec![ColumnType::Integer],
+                    sort_mode: SortMode::NoSort,
+                    label: Some("total".to_owned()),
+                    expected: vec![vec!["1".to_owned()], vec!["2".to_owned()]],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_value_wise_results() {
+        let records = parse("query II\nSELECT 1, 2\n----\n1\n2\n").unwrap();
+
+        match &records[0] {
+            Record::Query { expected, .. } => {
+                assert_eq!(expected, &vec![vec!["1".to_owned(), "2".to_owned()]])
+            }
+            record => panic!("unexpected record {:?}", record),
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = parse("\n\nquery X\nSELECT 1\n----\n1\n").unwrap_err();
+        assert_eq!(error.line, 3);
+        assert!(error.message.contains("unknown column type"), "{}", error);
+
+        let error = parse("loop i 0 10\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 1: unsupported directive \"loop\"");
+
+        assert!(parse("query I\nSELECT 1\n").is_err());
+        assert!(parse("statement ok\nSELECT 1\n----\n1\n").is_err());
+        assert!(parse("query II\nSELECT 1, 2\n----\n1\t2\t3\n").is_err());
+    }
+}
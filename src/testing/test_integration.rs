@@ -0,0 +1,136 @@
+use crate::duckly::duckdb_pragma_info;
+use crate::pragma_functions::{PragmaFunction, PragmaInfo};
+use crate::testing::{run_file, run_script, Mismatches};
+use crate::{Database, Error, LogicalType, LogicalTypeId};
+use std::{env, fs};
+
+unsafe extern "C" fn echo(info: duckdb_pragma_info) {
+    let info = PragmaInfo::from(info);
+
+    let param = info.get_parameter(0).get_varchar();
+
+    info.set_query(&format!("SELECT '{}' AS echoed", param.to_str().unwrap()));
+}
+
+fn init(db: &Database) -> Result<(), Box<dyn std::error::Error>> {
+    let pragma = PragmaFunction::new_call();
+    pragma
+        .set_name("echo")?
+        .add_parameter(&LogicalType::new(LogicalTypeId::Varchar))
+        .set_function(Some(echo));
+    db.connect()?.register_pragma_function(pragma)?;
+    Ok(())
+}
+
+const SCRIPT: &str = "\
+# the extension's init has been applied
+require echo
+
+statement ok
+CREATE TABLE numbers AS SELECT range AS n, range * 1.5 AS half FROM range(3)
+
+statement error
+SELECT * FROM missing
+----
+missing
+
+query T
+CALL echo('hello')
+----
+hello
+
+query IR rowsort
+SELECT n, half FROM numbers ORDER BY n DESC
+----
+0\t0.0
+1\t1.5
+2\t3.0
+
+query I valuesort
+SELECT n FROM numbers UNION ALL SELECT NULL
+----
+0
+1
+2
+NULL
+
+query T
+SELECT ''
+----
+(empty)
+";
+
+#[test]
+fn test_passing_script() -> Result<(), Error> {
+    run_script("echo.test", SCRIPT, init)
+}
+
+#[test]
+fn test_run_file() -> Result<(), Error> {
+    let path = env::temp_dir().join("duckdb_extension_framework_echo.test");
+    fs::write(&path, SCRIPT)?;
+
+    let result = run_file(&path, init);
+    fs::remove_file(&path)?;
+
+    result
+}
+
+#[test]
+fn test_mismatches() {
+    let error = run_script(
+        "broken.test",
+        "statement ok
+SELECT * FROM missing
+
+query I
+CALL echo('hello')
+----
+goodbye
+
+statement error
+SELECT 1
+
+query II
+SELECT 1
+----
+1\t1
+",
+        init,
+    )
+    .unwrap_err();
+
+    let mismatches = match &error {
+        Error::Extension(error) => &error.downcast_ref::<Mismatches>().unwrap().0,
+        error => panic!("unexpected error {:?}", error),
+    };
+    let lines: Vec<(&str, usize)> = mismatches
+        .iter()
+        .map(|mismatch| (mismatch.file.as_str(), mismatch.line))
+        .collect();
+    assert_eq!(
+        lines,
+        vec![
+            ("broken.test", 1),
+            ("broken.test", 4),
+            ("broken.test", 9),
+            ("broken.test", 12)
+        ]
+    );
+    assert!(
+        mismatches[1].message.contains("goodbye"),
+        "{}",
+        mismatches[1]
+    );
+    assert!(error.to_string().starts_with("4 record(s) failed"));
+}
+
+#[test]
+fn test_parse_error() {
+    let error = run_script("bad.test", "\nloop i 0 3\n", init).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "bad.test:2: unsupported directive \"loop\""
+    );
+}
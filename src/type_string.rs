@@ -0,0 +1,528 @@
+//! Parses and prints [`LogicalType`]s as the type strings DuckDB uses in SQL, such as `STRUCT(a INTEGER, b VARCHAR[])`
+
+use crate::{Error, LogicalType, LogicalTypeId, LogicalTypeShape};
+use std::fmt;
+use std::str::FromStr;
+
+/// The types without parameters, with the name they are printed as
+const PRIMITIVES: &[(LogicalTypeId, &str)] = &[
+    (LogicalTypeId::Boolean, "BOOLEAN"),
+    (LogicalTypeId::Tinyint, "TINYINT"),
+    (LogicalTypeId::Smallint, "SMALLINT"),
+    (LogicalTypeId::Integer, "INTEGER"),
+    (LogicalTypeId::Bigint, "BIGINT"),
+    (LogicalTypeId::Utinyint, "UTINYINT"),
+    (LogicalTypeId::Usmallint, "USMALLINT"),
+    (LogicalTypeId::Uinteger, "UINTEGER"),
+    (LogicalTypeId::Ubigint, "UBIGINT"),
+    (LogicalTypeId::Float, "FLOAT"),
+    (LogicalTypeId::Double, "DOUBLE"),
+    (LogicalTypeId::Timestamp, "TIMESTAMP"),
+    (LogicalTypeId::Date, "DATE"),
+    (LogicalTypeId::Time, "TIME"),
+    (LogicalTypeId::Interval, "INTERVAL"),
+    (LogicalTypeId::Hugeint, "HUGEINT"),
+    (LogicalTypeId::Varchar, "VARCHAR"),
+    (LogicalTypeId::Blob, "BLOB"),
+    (LogicalTypeId::TimestampS, "TIMESTAMP_S"),
+    (LogicalTypeId::TimestampMs, "TIMESTAMP_MS"),
+    (LogicalTypeId::TimestampNs, "TIMESTAMP_NS"),
+    (LogicalTypeId::Uuid, "UUID"),
+    (LogicalTypeId::Bit, "BIT"),
+    (LogicalTypeId::TimeTz, "TIMETZ"),
+    (LogicalTypeId::TimestampTz, "TIMESTAMPTZ"),
+];
+
+/// The types the C API of the DuckDB version the bindings are for can't create, which are only printed
+const PRINTED_ONLY: &[(LogicalTypeId, &str)] = &[(LogicalTypeId::Invalid, "INVALID")];
+
+/// Other names DuckDB accepts for the types above
+const ALIASES: &[(&str, LogicalTypeId)] = &[
+    ("BOOL", LogicalTypeId::Boolean),
+    ("LOGICAL", LogicalTypeId::Boolean),
+    ("INT1", LogicalTypeId::Tinyint),
+    ("INT2", LogicalTypeId::Smallint),
+    ("SHORT", LogicalTypeId::Smallint),
+    ("INT", LogicalTypeId::Integer),
+    ("INT4", LogicalTypeId::Integer),
+    ("SIGNED", LogicalTypeId::Integer),
+    ("INT8", LogicalTypeId::Bigint),
+    ("LONG", LogicalTypeId::Bigint),
+    ("FLOAT4", LogicalTypeId::Float),
+    ("REAL", LogicalTypeId::Float),
+    ("FLOAT8", LogicalTypeId::Double),
+    ("DATETIME", LogicalTypeId::Timestamp),
+    ("TIMESTAMP_US", LogicalTypeId::Timestamp),
+    ("BITSTRING", LogicalTypeId::Bit),
+    ("STRING", LogicalTypeId::Varchar),
+    ("TEXT", LogicalTypeId::Varchar),
+    ("CHAR", LogicalTypeId::Varchar),
+    ("BPCHAR", LogicalTypeId::Varchar),
+    ("BYTEA", LogicalTypeId::Blob),
+    ("BINARY", LogicalTypeId::Blob),
+    ("VARBINARY", LogicalTypeId::Blob),
+];
+
+/// The width and scale of a `DECIMAL` written without them
+const DEFAULT_DECIMAL: (u8, u8) = (18, 3);
+
+/// Writes `name` as is if it's a plain identifier, or double quoted otherwise
+fn write_identifier(f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+    let plain = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        f.write_str(name)
+    } else {
+        write!(f, "\"{}\"", name.replace('"', "\"\""))
+    }
+}
+
+fn write_members(
+    f: &mut fmt::Formatter<'_>,
+    members: &[(String, LogicalTypeShape)],
+) -> fmt::Result {
+    for (i, (name, shape)) in members.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write_identifier(f, name)?;
+        write!(f, " {}", shape)?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for LogicalTypeShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Primitive(id) => match PRIMITIVES
+                .iter()
+                .chain(PRINTED_ONLY)
+                .find(|(primitive, _)| primitive == id)
+            {
+                Some((_, name)) => f.write_str(name),
+                None => f.write_str(&format!("{:?}", id).to_ascii_uppercase()),
+            },
+            Self::Decimal { width, scale } => write!(f, "DECIMAL({},{})", width, scale),
+            Self::Enum(values) => {
+                f.write_str("ENUM(")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "'{}'", value.replace('\'', "''"))?;
+                }
+                f.write_str(")")
+            }
+            Self::List(child) => write!(f, "{}[]", child),
+            #[cfg(feature = "duckdb-v1_1")]
+            Self::Array { child, size } => write!(f, "{}[{}]", child, size),
+            Self::Map { key, value } => write!(f, "MAP({}, {})", key, value),
+            Self::Struct(fields) => {
+                f.write_str("STRUCT(")?;
+                write_members(f, fields)?;
+                f.write_str(")")
+            }
+            Self::Union(members) => {
+                f.write_str("UNION(")?;
+                write_members(f, members)?;
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+impl fmt::Display for LogicalType {
+    /// Prints the type as DuckDB would accept it in SQL, e.g. `MAP(VARCHAR, DECIMAL(18,3))`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.shape().fmt(f)
+    }
+}
+
+impl FromStr for LogicalType {
+    type Err = Error;
+
+    /// Parses a type as written in SQL, e.g. `STRUCT(a INTEGER, b VARCHAR[])`.
+    ///
+    /// Names are case insensitive, and the common aliases such as `INT` and `TEXT` are accepted. Fixed-size arrays such
+    /// as `INTEGER[3]` need the `duckdb-v1_1` feature, as DuckDB 0.7.1 has no `ARRAY` type.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { input: s, pos: 0 };
+        let typ = parser.parse_type()?;
+        parser.skip_whitespace();
+        if parser.pos < s.len() {
+            return Err(parser.error("expected the end of the type"));
+        }
+        Ok(typ)
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &str) -> Error {
+        let found = match self.rest().chars().next() {
+            Some(c) => format!("'{}'", c),
+            None => "the end".to_owned(),
+        };
+        Error::Conversion(format!(
+            "invalid type \"{}\": {}, found {} at offset {}",
+            self.input, message, found, self.pos
+        ))
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skips over `c` if it's next, returning whether it was
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), Error> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", c)))
+        }
+    }
+
+    /// Reads a run of letters, digits and underscores
+    fn word(&mut self) -> Option<&'a str> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        self.pos += len;
+        (len > 0).then(|| &rest[..len])
+    }
+
+    /// Reads a string delimited by `quote`, in which the quote is escaped by doubling it
+    fn quoted(&mut self, quote: char) -> Result<String, Error> {
+        self.expect(quote)?;
+        let mut value = String::new();
+        loop {
+            let mut chars = self.rest().chars();
+            match chars.next() {
+                None => return Err(self.error(&format!("expected a closing {}", quote))),
+                Some(c) if c == quote => {
+                    self.pos += c.len_utf8();
+                    if !self.rest().starts_with(quote) {
+                        return Ok(value);
+                    }
+                    self.pos += quote.len_utf8();
+                    value.push(quote);
+                }
+                Some(c) => {
+                    self.pos += c.len_utf8();
+                    value.push(c);
+                }
+            }
+        }
+    }
+
+    fn identifier(&mut self) -> Result<String, Error> {
+        self.skip_whitespace();
+        if self.rest().starts_with('"') {
+            return self.quoted('"');
+        }
+        match self.word() {
+            Some(word) => Ok(word.to_owned()),
+            None => Err(self.error("expected a name")),
+        }
+    }
+
+    fn number<T: FromStr>(&mut self) -> Result<T, Error> {
+        let start = self.pos;
+        let number = self.word().and_then(|word| word.parse().ok());
+        number.ok_or_else(|| {
+            self.pos = start;
+            self.error("expected a number")
+        })
+    }
+
+    /// Reads a type, followed by any number of `[]` making it a list, or with the `duckdb-v1_1` feature `[size]`
+    /// making it a fixed-size array
+    fn parse_type(&mut self) -> Result<LogicalType, Error> {
+        let mut typ = self.parse_base_type()?;
+        while self.eat('[') {
+            #[cfg(feature = "duckdb-v1_1")]
+            if !self.eat(']') {
+                let size = self.number()?;
+                self.expect(']')?;
+                typ = LogicalType::array(&typ, size);
+                continue;
+            }
+            #[cfg(not(feature = "duckdb-v1_1"))]
+            self.expect(']')?;
+            typ = LogicalType::new_list_type(&typ);
+        }
+        Ok(typ)
+    }
+
+    fn parse_members(&mut self) -> Result<Vec<(String, LogicalType)>, Error> {
+        self.expect('(')?;
+        let mut members = vec![];
+        loop {
+            let name = self.identifier()?;
+            members.push((name, self.parse_type()?));
+            if !self.eat(',') {
+                break;
+            }
+        }
+        self.expect(')')?;
+        Ok(members)
+    }
+
+    fn parse_base_type(&mut self) -> Result<LogicalType, Error> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let name = match self.word() {
+            Some(name) => name.to_ascii_uppercase(),
+            None => return Err(self.error("expected a type")),
+        };
+        // errors from building the type are reported at its name
+        let at_name = |parser: &mut Self, error: Error| {
+            parser.pos = start;
+            match error {
+                Error::Conversion(message) => parser.error(&message),
+                error => error,
+            }
+        };
+
+        match name.as_str() {
+            "DECIMAL" | "NUMERIC" => {
+                let (width, scale) = if self.eat('(') {
+                    let width = self.number()?;
+                    let scale = if self.eat(',') { self.number()? } else { 0 };
+                    self.expect(')')?;
+                    (width, scale)
+                } else {
+                    DEFAULT_DECIMAL
+                };
+                LogicalType::decimal(width, scale).map_err(|error| at_name(self, error))
+            }
+            "ENUM" => {
+                self.expect('(')?;
+                let mut values = vec![];
+                loop {
+                    values.push(self.quoted('\'')?);
+                    if !self.eat(',') {
+                        break;
+                    }
+                }
+                self.expect(')')?;
+                let values: Vec<&str> = values.iter().map(String::as_str).collect();
+                LogicalType::enum_type(&values).map_err(|error| at_name(self, error))
+            }
+            "MAP" => {
+                self.expect('(')?;
+                let key = self.parse_type()?;
+                self.expect(',')?;
+                let value = self.parse_type()?;
+                self.expect(')')?;
+                Ok(LogicalType::new_map_type(&key, &value))
+            }
+            "STRUCT" | "ROW" => {
+                let fields = self.parse_members()?;
+                LogicalType::new_struct_type(
+                    fields
+                        .iter()
+                        .map(|(name, typ)| (name.as_str(), typ.clone()))
+                        .collect(),
+                )
+                .map_err(|error| at_name(self, error))
+            }
+            "UNION" => {
+                let members = self.parse_members()?;
+                LogicalType::new_union_type(
+                    members
+                        .iter()
+                        .map(|(name, typ)| (name.as_str(), typ.clone()))
+                        .collect(),
+                )
+                .map_err(|error| at_name(self, error))
+            }
+            name => {
+                let primitive = PRIMITIVES
+                    .iter()
+                    .find(|(_, primitive)| *primitive == name)
+                    .map(|(id, _)| *id)
+                    .or_else(|| {
+                        ALIASES
+                            .iter()
+                            .find(|(alias, _)| *alias == name)
+                            .map(|(_, id)| *id)
+                    });
+                match primitive {
+                    Some(id) => Ok(LogicalType::new(id)),
+                    None => {
+                        self.pos = start;
+                        Err(self.error(&format!("unknown type {}", name)))
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::type_string::PRIMITIVES;
+    use crate::{Error, LogicalType, LogicalTypeId, LogicalTypeShape};
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_parse() -> Result<(), Error> {
+        let typ: LogicalType =
+            "struct(a INT, \"b c\" varchar[], d MAP(TEXT, DECIMAL(18,3)))".parse()?;
+        assert_eq!(
+            typ.to_string(),
+            "STRUCT(a INTEGER, \"b c\" VARCHAR[], d MAP(VARCHAR, DECIMAL(18,3)))"
+        );
+
+        let typ: LogicalType = "UNION(n BIGINT, mood ENUM('sad', 'it''s ok'))[][]".parse()?;
+        assert_eq!(
+            typ.to_string(),
+            "UNION(n BIGINT, mood ENUM('sad', 'it''s ok'))[][]"
+        );
+
+        assert_eq!(
+            "DECIMAL".parse::<LogicalType>()?.shape(),
+            LogicalTypeShape::Decimal {
+                width: 18,
+                scale: 3
+            }
+        );
+        assert_eq!(
+            "numeric(10)".parse::<LogicalType>()?.shape(),
+            LogicalTypeShape::Decimal {
+                width: 10,
+                scale: 0
+            }
+        );
+
+        #[cfg(feature = "duckdb-v1_1")]
+        {
+            let typ: LogicalType = "int[3][] ".parse()?;
+            assert_eq!(typ.to_string(), "INTEGER[3][]");
+            assert_eq!(
+                typ.list_child_type().and_then(|child| child.array_size()),
+                Some(3)
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |s: &str| match s.parse::<LogicalType>() {
+            Err(Error::Conversion(message)) => message,
+            result => panic!("unexpected result for {}: {:?}", s, result),
+        };
+
+        assert_eq!(
+            error("STRUCT(a INTEGER"),
+            "invalid type \"STRUCT(a INTEGER\": expected ')', found the end at offset 16"
+        );
+        assert_eq!(
+            error("MAP(VARCHAR, INTEGR)"),
+            "invalid type \"MAP(VARCHAR, INTEGR)\": unknown type INTEGR, found 'I' at offset 13"
+        );
+        assert!(error("INTEGER VARCHAR").contains("expected the end of the type"));
+        assert!(error("DECIMAL(40,2)").contains("invalid decimal width 40"));
+        assert!(error("STRUCT(a INTEGER, a VARCHAR)").contains("repeated"));
+        assert!(error("ENUM('a', 'b)").contains("closing '"));
+        // DuckDB 0.7.1 has no fixed-size arrays
+        #[cfg(not(feature = "duckdb-v1_1"))]
+        assert!(error("INTEGER[3]").contains("expected ']'"));
+        #[cfg(feature = "duckdb-v1_1")]
+        assert!(error("INTEGER[x]").contains("expected a number"));
+    }
+
+    fn members(
+        inner: BoxedStrategy<LogicalTypeShape>,
+    ) -> impl Strategy<Value = Vec<(String, LogicalTypeShape)>> {
+        prop::collection::hash_map("[A-Za-z_][A-Za-z0-9_ \"]{0,6}", inner, 1..4)
+            .prop_map(|members| members.into_iter().collect())
+    }
+
+    fn shapes() -> impl Strategy<Value = LogicalTypeShape> {
+        let leaf = prop_oneof![
+            prop::sample::select(PRIMITIVES.to_vec())
+                .prop_map(|(id, _)| LogicalTypeShape::Primitive(id)),
+            (1u8..=38)
+                .prop_flat_map(|width| (Just(width), 0..=width))
+                .prop_map(|(width, scale)| LogicalTypeShape::Decimal { width, scale }),
+            prop::collection::hash_set("[a-z' ]{1,8}", 1..5)
+                .prop_map(|values| LogicalTypeShape::Enum(values.into_iter().collect())),
+        ];
+        leaf.prop_recursive(3, 24, 4, |inner| {
+            prop_oneof![
+                inner
+                    .clone()
+                    .prop_map(|child| LogicalTypeShape::List(Box::new(child))),
+                (inner.clone(), inner.clone()).prop_map(|(key, value)| LogicalTypeShape::Map {
+                    key: Box::new(key),
+                    value: Box::new(value)
+                }),
+                members(inner.clone()).prop_map(LogicalTypeShape::Struct),
+                members(inner).prop_map(LogicalTypeShape::Union),
+            ]
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(shape in shapes()) {
+            let typ: LogicalType = shape.to_string().parse()?;
+            prop_assert_eq!(typ.shape(), shape);
+
+            let reparsed: LogicalType = typ.to_string().parse()?;
+            prop_assert_eq!(reparsed, typ);
+        }
+    }
+
+    #[test]
+    fn test_print_unsupported() {
+        assert_eq!(
+            LogicalTypeShape::Primitive(LogicalTypeId::Invalid).to_string(),
+            "INVALID"
+        );
+        assert_eq!(
+            LogicalTypeShape::Primitive(LogicalTypeId::Unknown(40)).to_string(),
+            "UNKNOWN(40)"
+        );
+    }
+
+    #[test]
+    fn test_every_primitive() -> Result<(), Error> {
+        for (id, name) in PRIMITIVES {
+            let typ: LogicalType = name.parse()?;
+            assert_eq!(typ.type_id(), *id);
+            assert_eq!(typ.to_string(), *name);
+            assert_eq!(LogicalType::new(*id).to_string(), *name);
+        }
+        assert_eq!(
+            "int".parse::<LogicalType>()?.type_id(),
+            LogicalTypeId::Integer
+        );
+        Ok(())
+    }
+}
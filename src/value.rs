@@ -1,4 +1,7 @@
-use crate::duckly::{duckdb_destroy_value, duckdb_get_varchar, duckdb_value};
+use crate::duckly::{
+    duckdb_create_int64, duckdb_create_varchar_length, duckdb_destroy_value, duckdb_get_int64,
+    duckdb_get_varchar, duckdb_value,
+};
 use std::ffi::CString;
 
 /// The Value object holds a single arbitrary value of any type that can be
@@ -7,6 +10,23 @@ use std::ffi::CString;
 pub struct Value(pub(crate) duckdb_value);
 
 impl Value {
+    /// Creates a value from a string
+    pub fn new_varchar(text: &str) -> Self {
+        unsafe {
+            Self(duckdb_create_varchar_length(
+                text.as_ptr().cast(),
+                text.len() as _,
+            ))
+        }
+    }
+    /// Creates a value from a bigint
+    pub fn new_int64(val: i64) -> Self {
+        unsafe { Self(duckdb_create_int64(val)) }
+    }
+    /// Obtains an int64 of the given value
+    pub fn get_int64(&self) -> i64 {
+        unsafe { duckdb_get_int64(self.0) }
+    }
     /// Obtains a string representation of the given value
     pub fn get_varchar(&self) -> CString {
         unsafe { CString::from_raw(duckdb_get_varchar(self.0)) }
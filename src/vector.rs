@@ -1,31 +1,157 @@
 use crate::{
     duckly::{
-        duckdb_validity_row_is_valid, duckdb_validity_set_row_invalid,
-        duckdb_validity_set_row_valid, duckdb_validity_set_row_validity, duckdb_vector,
-        duckdb_vector_assign_string_element, duckdb_vector_assign_string_element_len,
-        duckdb_vector_ensure_validity_writable, duckdb_vector_get_column_type,
-        duckdb_vector_get_data, duckdb_vector_get_validity, duckdb_vector_size, idx_t,
+        duckdb_date, duckdb_decimal_internal_type, duckdb_enum_dictionary_size,
+        duckdb_enum_internal_type, duckdb_hugeint, duckdb_interval, duckdb_list_vector_get_child,
+        duckdb_list_vector_get_size, duckdb_list_vector_reserve, duckdb_list_vector_set_size,
+        duckdb_state_DuckDBSuccess, duckdb_string_t, duckdb_struct_vector_get_child, duckdb_time,
+        duckdb_timestamp, duckdb_type, duckdb_validity_row_is_valid,
+        duckdb_validity_set_row_invalid, duckdb_validity_set_row_valid,
+        duckdb_validity_set_row_validity, duckdb_vector, duckdb_vector_assign_string_element,
+        duckdb_vector_assign_string_element_len, duckdb_vector_ensure_validity_writable,
+        duckdb_vector_get_column_type, duckdb_vector_get_data, duckdb_vector_get_validity,
+        duckdb_vector_size, idx_t, DUCKDB_TYPE_DUCKDB_TYPE_BIGINT, DUCKDB_TYPE_DUCKDB_TYPE_HUGEINT,
+        DUCKDB_TYPE_DUCKDB_TYPE_INTEGER, DUCKDB_TYPE_DUCKDB_TYPE_SMALLINT,
+        DUCKDB_TYPE_DUCKDB_TYPE_UINTEGER, DUCKDB_TYPE_DUCKDB_TYPE_USMALLINT,
+        DUCKDB_TYPE_DUCKDB_TYPE_UTINYINT,
     },
-    LogicalType,
+    Error, LogicalType, LogicalTypeId,
 };
 use std::fmt::Debug;
-use std::{ffi::c_char, marker::PhantomData, slice};
+use std::{
+    ffi::{c_char, c_void},
+    marker::PhantomData,
+    slice,
+};
 
 /// Vector of values of a specified PhysicalType.
 pub struct Vector<T>(duckdb_vector, PhantomData<T>);
 
-impl<T> From<duckdb_vector> for Vector<T> {
+impl From<duckdb_vector> for Vector<()> {
     fn from(ptr: duckdb_vector) -> Self {
         Self(ptr, PhantomData {})
     }
 }
 
-impl<T> Vector<T> {
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A type the values of a vector are stored as, which its data can be accessed through.
+///
+/// This is only implemented for the representations DuckDB uses, see [`DataChunk::get_vector`](crate::DataChunk::get_vector).
+pub trait PhysicalType: sealed::Sealed + Copy {
+    /// The types of the columns whose values are stored as this type
+    const LOGICAL_TYPES: &'static [LogicalTypeId];
+}
+
+macro_rules! physical_type {
+    ($($ty:ty => $($id:ident)|+,)*) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl PhysicalType for $ty {
+                const LOGICAL_TYPES: &'static [LogicalTypeId] = &[$(LogicalTypeId::$id),+];
+            }
+        )*
+    };
+}
+
+// a BOOLEAN is stored as a byte, which isn't necessarily 0 or 1 and so is read as a u8 rather than a bool: any other
+// value than 0 is true
+physical_type! {
+    i8 => Tinyint,
+    i16 => Smallint,
+    i32 => Integer,
+    i64 => Bigint,
+    u8 => Utinyint | Boolean,
+    u16 => Usmallint,
+    u32 => Uinteger,
+    u64 => Ubigint,
+    f32 => Float,
+    f64 => Double,
+    duckdb_hugeint => Hugeint,
+    duckdb_date => Date,
+    duckdb_time => Time | TimeTz,
+    duckdb_timestamp => Timestamp | TimestampS | TimestampMs | TimestampNs | TimestampTz,
+    duckdb_interval => Interval,
+    duckdb_string_t => Varchar | Blob | Bit,
+}
+
+// a hugeint is stored as its lower and then its upper 64 bits, which is how little-endian targets lay out an i128
+#[cfg(target_endian = "little")]
+physical_type! {
+    i128 => Hugeint,
+}
+
+impl<T: PhysicalType> Vector<T> {
     /// Retrieves the data pointer of the vector.
     ///
     /// The data pointer can be used to read or write values from the vector. How to read or write values depends on the type of the vector.
     pub fn get_data(&self) -> *mut T {
-        unsafe { duckdb_vector_get_data(self.0).cast() }
+        self.raw_data().cast()
+    }
+
+    /// Retrieves the data pointer of the vector as a slice
+    ///
+    /// The data pointer can be used to read or write values from the vector. How to read or write values depends on the type of the vector.
+    pub fn get_data_as_slice(&mut self) -> &mut [T] {
+        let ptr = self.get_data();
+        unsafe { slice::from_raw_parts_mut(ptr, duckdb_vector_size() as usize) }
+    }
+}
+
+impl Vector<duckdb_string_t> {
+    /// Reads the first `count` values of a `VARCHAR` or `BLOB` vector, such as the input of a collation.
+    ///
+    /// Prefer [`DataChunk::get_string_vector`](crate::DataChunk::get_string_vector), which knows how many rows its
+    /// vectors hold.
+    ///
+    /// # Arguments
+    /// * `count`: The number of values the vector holds, at most [`duckdb_vector_size`]
+    /// * `returns`: A view of the values, borrowed for as long as the vector
+    ///
+    /// # Safety
+    /// The first `count` rows must have been written or be NULL, as the input of a collation is for the `count` it's
+    /// given. While the view is in use the vector's data mustn't be written, e.g. through
+    /// [`Vector::get_data_as_slice`], and its chunk mustn't be reset or dropped.
+    pub unsafe fn get_strings(&self, count: idx_t) -> StringVector<'_> {
+        self.borrow_strings(count)
+    }
+
+    /// Reads the first `count` values of the vector, for as long as its data is alive
+    ///
+    /// # Safety
+    /// The returned view mustn't outlive the chunk the vector belongs to
+    pub(crate) unsafe fn borrow_strings<'a>(&self, count: idx_t) -> StringVector<'a> {
+        let capacity = duckdb_vector_size();
+        assert!(
+            count <= capacity,
+            "{} values are more than a vector holds ({})",
+            count,
+            capacity
+        );
+        StringVector {
+            data: slice::from_raw_parts(self.get_data(), count as usize),
+            validity: self.get_validity(),
+        }
+    }
+}
+
+impl<T> Vector<T> {
+    /// Wraps a vector whose values are accessed as `T`, such as one passed to a callback.
+    ///
+    /// Use `From` for a `Vector<()>`, whose values are only accessed through the type checked accessors.
+    ///
+    /// # Safety
+    /// `ptr` must be a vector of one of `T`'s [`PhysicalType::LOGICAL_TYPES`], which stays alive for as long as the
+    /// returned `Vector` is used.
+    pub unsafe fn from_raw(ptr: duckdb_vector) -> Self {
+        Self(ptr, PhantomData {})
+    }
+
+    /// Retrieves the data pointer of the vector, for types that are read and written through their own accessors
+    pub(crate) fn raw_data(&self) -> *mut c_void {
+        unsafe { duckdb_vector_get_data(self.0) }
     }
 
     /// Assigns a string element in the vector at the specified location.
@@ -56,14 +182,6 @@ impl<T> Vector<T> {
         duckdb_vector_assign_string_element(self.0, index, str_);
     }
 
-    /// Retrieves the data pointer of the vector as a slice
-    ///
-    /// The data pointer can be used to read or write values from the vector. How to read or write values depends on the type of the vector.
-    pub fn get_data_as_slice(&mut self) -> &mut [T] {
-        let ptr = self.get_data();
-        unsafe { slice::from_raw_parts_mut(ptr, duckdb_vector_size() as usize) }
-    }
-
     /// Retrieves the column type of the specified vector.
     pub fn get_column_type(&self) -> LogicalType {
         unsafe { LogicalType::from(duckdb_vector_get_column_type(self.0)) }
@@ -90,6 +208,187 @@ impl<T> Vector<T> {
     pub fn ensure_validity_writable(&self) {
         unsafe { duckdb_vector_ensure_validity_writable(self.0) };
     }
+
+    /// Retrieves the child vector of a list vector.
+    ///
+    /// The resulting vector is valid as long as the parent vector is valid.
+    pub fn list_child(&self) -> Vector<()> {
+        Vector::from(unsafe { duckdb_list_vector_get_child(self.0) })
+    }
+
+    /// Returns the size of the child vector of the list
+    pub fn list_size(&self) -> idx_t {
+        unsafe { duckdb_list_vector_get_size(self.0) }
+    }
+
+    /// Sets the total size of the underlying child-vector of a list vector.
+    ///
+    /// # Arguments
+    ///  * `size`: The size of the child list.
+    ///
+    /// returns: [`Error::Conversion`] if this isn't a list vector
+    pub fn set_list_size(&self, size: idx_t) -> Result<(), Error> {
+        if unsafe { duckdb_list_vector_set_size(self.0, size) } != duckdb_state_DuckDBSuccess {
+            return Err(self.not_a_list());
+        }
+        Ok(())
+    }
+
+    /// Sets the total capacity of the underlying child-vector of a list.
+    ///
+    /// # Arguments
+    ///  * `required_capacity`: the total capacity to reserve.
+    ///
+    /// returns: [`Error::Conversion`] if this isn't a list vector
+    pub fn reserve_list(&self, required_capacity: idx_t) -> Result<(), Error> {
+        if unsafe { duckdb_list_vector_reserve(self.0, required_capacity) }
+            != duckdb_state_DuckDBSuccess
+        {
+            return Err(self.not_a_list());
+        }
+        Ok(())
+    }
+
+    /// Retrieves the child vector of a struct vector.
+    ///
+    /// The resulting vector is valid as long as the parent vector is valid.
+    ///
+    /// # Arguments
+    ///  * `index`: The child index
+    pub fn struct_child(&self, index: idx_t) -> Vector<()> {
+        Vector::from(unsafe { duckdb_struct_vector_get_child(self.0, index) })
+    }
+
+    /// Reads the unscaled value of the `DECIMAL` at `row`, whichever integer type the decimal's width stores it as.
+    ///
+    /// The value is `unscaled / 10^scale`, see [`LogicalType::decimal_scale`].
+    ///
+    /// returns: The unscaled value, or [`Error::Conversion`] if this isn't a decimal vector
+    pub fn get_decimal(&self, row: idx_t) -> Result<i128, Error> {
+        unsafe {
+            match self.internal_type(LogicalTypeId::Decimal)? {
+                DUCKDB_TYPE_DUCKDB_TYPE_SMALLINT => Ok(i128::from(*self.value_at::<i16>(row))),
+                DUCKDB_TYPE_DUCKDB_TYPE_INTEGER => Ok(i128::from(*self.value_at::<i32>(row))),
+                DUCKDB_TYPE_DUCKDB_TYPE_BIGINT => Ok(i128::from(*self.value_at::<i64>(row))),
+                DUCKDB_TYPE_DUCKDB_TYPE_HUGEINT => {
+                    let value = *self.value_at::<duckdb_hugeint>(row);
+                    Ok(i128::from(value.upper) << 64 | i128::from(value.lower))
+                }
+                internal => Err(unsupported_storage("decimal", internal)),
+            }
+        }
+    }
+
+    /// Writes the unscaled value of the `DECIMAL` at `row`, see [`Vector::get_decimal`].
+    ///
+    /// returns: [`Error::Conversion`] if this isn't a decimal vector, or the value has too many digits for its width
+    pub fn set_decimal(&self, row: idx_t, value: i128) -> Result<(), Error> {
+        let internal = self.internal_type(LogicalTypeId::Decimal)?;
+        let width = self
+            .get_column_type()
+            .decimal_width()
+            .expect("decimal type");
+        if value.unsigned_abs() >= 10u128.pow(width.into()) {
+            return Err(Error::Conversion(format!(
+                "{} has more than the decimal's {} digits",
+                value, width
+            )));
+        }
+        // the storage is chosen to fit every value of the width, so these can't truncate
+        unsafe {
+            match internal {
+                DUCKDB_TYPE_DUCKDB_TYPE_SMALLINT => *self.value_at::<i16>(row) = value as i16,
+                DUCKDB_TYPE_DUCKDB_TYPE_INTEGER => *self.value_at::<i32>(row) = value as i32,
+                DUCKDB_TYPE_DUCKDB_TYPE_BIGINT => *self.value_at::<i64>(row) = value as i64,
+                DUCKDB_TYPE_DUCKDB_TYPE_HUGEINT => {
+                    *self.value_at::<duckdb_hugeint>(row) = duckdb_hugeint {
+                        lower: value as u64,
+                        upper: (value >> 64) as i64,
+                    }
+                }
+                internal => return Err(unsupported_storage("decimal", internal)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the dictionary index of the `ENUM` at `row`, see [`LogicalType::enum_values`].
+    ///
+    /// returns: The index, or [`Error::Conversion`] if this isn't an enum vector
+    pub fn get_enum(&self, row: idx_t) -> Result<u32, Error> {
+        unsafe {
+            match self.internal_type(LogicalTypeId::Enum)? {
+                DUCKDB_TYPE_DUCKDB_TYPE_UTINYINT => Ok(u32::from(*self.value_at::<u8>(row))),
+                DUCKDB_TYPE_DUCKDB_TYPE_USMALLINT => Ok(u32::from(*self.value_at::<u16>(row))),
+                DUCKDB_TYPE_DUCKDB_TYPE_UINTEGER => Ok(*self.value_at::<u32>(row)),
+                internal => Err(unsupported_storage("enum", internal)),
+            }
+        }
+    }
+
+    /// Writes the dictionary index of the `ENUM` at `row`, see [`Vector::get_enum`].
+    ///
+    /// returns: [`Error::Conversion`] if this isn't an enum vector, or the index is outside its dictionary
+    pub fn set_enum(&self, row: idx_t, index: u32) -> Result<(), Error> {
+        let internal = self.internal_type(LogicalTypeId::Enum)?;
+        let size = unsafe { duckdb_enum_dictionary_size(self.get_column_type().typ) };
+        if index >= size {
+            return Err(Error::Conversion(format!(
+                "{} is outside the enum's {} values",
+                index, size
+            )));
+        }
+        // the storage is chosen to fit every index of the dictionary, so these can't truncate
+        unsafe {
+            match internal {
+                DUCKDB_TYPE_DUCKDB_TYPE_UTINYINT => *self.value_at::<u8>(row) = index as u8,
+                DUCKDB_TYPE_DUCKDB_TYPE_USMALLINT => *self.value_at::<u16>(row) = index as u16,
+                DUCKDB_TYPE_DUCKDB_TYPE_UINTEGER => *self.value_at::<u32>(row) = index,
+                internal => return Err(unsupported_storage("enum", internal)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a pointer to the value at `row`, when the column is stored as `U`
+    ///
+    /// # Safety
+    /// `U` must be the column's storage type
+    unsafe fn value_at<U>(&self, row: idx_t) -> *mut U {
+        let size = duckdb_vector_size();
+        assert!(row < size, "row {} is out of bounds for {} rows", row, size);
+        self.raw_data().cast::<U>().add(row as usize)
+    }
+
+    fn not_a_list(&self) -> Error {
+        Error::Conversion(format!(
+            "expected a List vector, found {:?}",
+            self.get_column_type().type_id()
+        ))
+    }
+
+    /// Returns the integer type a decimal or enum column is stored as, or an error if it isn't of the `expected` type
+    fn internal_type(&self, expected: LogicalTypeId) -> Result<duckdb_type, Error> {
+        let typ = self.get_column_type();
+        let actual = typ.type_id();
+        if actual != expected {
+            return Err(Error::Conversion(format!(
+                "expected a {:?} vector, found {:?}",
+                expected, actual
+            )));
+        }
+        Ok(unsafe {
+            if expected == LogicalTypeId::Decimal {
+                duckdb_decimal_internal_type(typ.typ)
+            } else {
+                duckdb_enum_internal_type(typ.typ)
+            }
+        })
+    }
+}
+
+fn unsupported_storage(what: &str, internal: duckdb_type) -> Error {
+    Error::Conversion(format!("unsupported {} storage type {}", what, internal))
 }
 
 pub struct ValidityMask(*mut u64, idx_t);
@@ -146,17 +445,194 @@ impl ValidityMask {
     }
 }
 
+/// The values of a `VARCHAR` or `BLOB` vector, borrowed from its chunk.
+///
+/// Rows that are NULL in the vector's [`ValidityMask`] are read as `None`.
+pub struct StringVector<'a> {
+    data: &'a [duckdb_string_t],
+    validity: ValidityMask,
+}
+
+impl<'a> StringVector<'a> {
+    /// Returns the number of rows
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns whether there are no rows
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns whether `row` isn't NULL
+    ///
+    /// # Panics
+    /// If `row` is out of bounds
+    pub fn is_valid(&self, row: idx_t) -> bool {
+        assert!(
+            (row as usize) < self.len(),
+            "row {} is out of bounds for {} rows",
+            row,
+            self.len()
+        );
+        self.validity.row_is_valid(row)
+    }
+
+    /// Reads the bytes of a row.
+    ///
+    /// # Arguments
+    /// * `row`: The row index, which panics if it's out of bounds
+    /// * `returns`: The bytes, or `None` if the row is NULL
+    pub fn get_bytes(&self, row: idx_t) -> Option<&'a [u8]> {
+        if self.is_valid(row) {
+            Some(unsafe { string_bytes(&self.data[row as usize]) })
+        } else {
+            None
+        }
+    }
+
+    /// Reads a row as a string.
+    ///
+    /// # Arguments
+    /// * `row`: The row index, which panics if it's out of bounds
+    /// * `returns`: The string, `None` if the row is NULL, or [`Error::Utf8`] if it isn't valid UTF-8, as a `BLOB`
+    ///   might not be
+    pub fn get_str(&self, row: idx_t) -> Result<Option<&'a str>, Error> {
+        match self.get_bytes(row) {
+            Some(bytes) => Ok(Some(std::str::from_utf8(bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Iterates over the bytes of every row, see [`StringVector::get_bytes`]
+    pub fn iter_bytes(&self) -> impl Iterator<Item = Option<&'a [u8]>> + '_ {
+        (0..self.len() as idx_t).map(|row| self.get_bytes(row))
+    }
+
+    /// Iterates over every row as a string, see [`StringVector::get_str`]
+    pub fn iter_str(&self) -> impl Iterator<Item = Result<Option<&'a str>, Error>> + '_ {
+        (0..self.len() as idx_t).map(|row| self.get_str(row))
+    }
+}
+
+impl Debug for StringVector<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(
+                self.iter_bytes()
+                    .map(|bytes| bytes.map(String::from_utf8_lossy)),
+            )
+            .finish()
+    }
+}
+
+/// Returns the bytes of a `VARCHAR` or `BLOB` value
+///
+/// # Safety
+/// `string` must be a value in the data of a vector, which outlives the returned bytes
+pub(crate) unsafe fn string_bytes(string: &duckdb_string_t) -> &[u8] {
+    let length = string.value.inlined.length as usize;
+    // strings of up to 12 bytes are stored in place of the pointer
+    if length <= 12 {
+        slice::from_raw_parts(string.value.inlined.inlined.as_ptr().cast(), length)
+    } else {
+        slice::from_raw_parts(string.value.pointer.ptr.cast(), length)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::constants::LogicalTypeId;
-    use crate::{DataChunk, LogicalType};
+    use crate::duckly::idx_t;
+    use crate::{DataChunk, Error, LogicalType};
 
     #[test]
     fn test_vector() {
         let datachunk = DataChunk::new(vec![LogicalType::new(LogicalTypeId::Bigint)]);
-        let mut vector = datachunk.get_vector::<u64>(0);
+        let mut vector = datachunk.get_vector::<i64>(0).unwrap();
         let data = vector.get_data_as_slice();
 
         data[0] = 42;
     }
+
+    #[test]
+    fn test_decimal_and_enum_vectors() -> Result<(), Error> {
+        let datachunk = DataChunk::new(vec![
+            LogicalType::decimal(4, 1)?,
+            LogicalType::decimal(38, 10)?,
+            LogicalType::enum_type(&["low", "medium", "high"])?,
+            LogicalType::new(LogicalTypeId::Bigint),
+        ]);
+
+        let narrow = datachunk.get_untyped_vector(0);
+        narrow.set_decimal(1, -999)?;
+        assert_eq!(narrow.get_decimal(1)?, -999);
+        assert!(matches!(
+            narrow.set_decimal(0, 10_000),
+            Err(Error::Conversion(_))
+        ));
+
+        let wide = datachunk.get_untyped_vector(1);
+        let value = -12_345_678_901_234_567_890_123_456_789;
+        wide.set_decimal(2, value)?;
+        assert_eq!(wide.get_decimal(2)?, value);
+
+        let enum_ = datachunk.get_untyped_vector(2);
+        enum_.set_enum(0, 2)?;
+        assert_eq!(enum_.get_enum(0)?, 2);
+        assert!(matches!(enum_.set_enum(0, 3), Err(Error::Conversion(_))));
+
+        let bigint = datachunk.get_untyped_vector(3);
+        assert!(matches!(bigint.get_decimal(0), Err(Error::Conversion(_))));
+        assert!(matches!(bigint.get_enum(0), Err(Error::Conversion(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_vector() -> Result<(), Error> {
+        let mut datachunk = DataChunk::new(vec![
+            LogicalType::new(LogicalTypeId::Varchar),
+            LogicalType::new(LogicalTypeId::Blob),
+            LogicalType::new(LogicalTypeId::Bigint),
+        ]);
+        let long = "a string too long to be inlined";
+        let varchar = datachunk.get_untyped_vector(0);
+        for (row, value) in ["inlined", "", long].iter().enumerate() {
+            unsafe {
+                varchar.assign_string_element_len(
+                    row as idx_t,
+                    value.as_ptr().cast(),
+                    value.len() as idx_t,
+                )
+            };
+        }
+        varchar.ensure_validity_writable();
+        varchar.get_validity().set_row_invalid(3);
+        let blob = datachunk.get_untyped_vector(1);
+        let invalid = [0xff, 0xfe, 0x00];
+        unsafe {
+            blob.assign_string_element_len(0, invalid.as_ptr().cast(), invalid.len() as idx_t)
+        };
+        datachunk.set_size(4);
+
+        let strings = datachunk.get_string_vector(0)?;
+        assert_eq!(strings.len(), 4);
+        assert_eq!(strings.get_str(0)?, Some("inlined"));
+        assert_eq!(strings.get_bytes(2), Some(long.as_bytes()));
+        assert_eq!(
+            strings.iter_str().collect::<Result<Vec<_>, Error>>()?,
+            [Some("inlined"), Some(""), Some(long), None]
+        );
+
+        let blobs = datachunk.get_string_vector(1)?;
+        assert_eq!(blobs.get_bytes(0), Some(&invalid[..]));
+        assert!(matches!(blobs.get_str(0), Err(Error::Utf8(_))));
+
+        assert!(matches!(
+            datachunk.get_string_vector(2),
+            Err(Error::Conversion(_))
+        ));
+        Ok(())
+    }
 }
@@ -0,0 +1,185 @@
+//! Checks that the DuckDB an extension is loaded into matches the headers the bindings were generated from
+
+use crate::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The version of the DuckDB headers that [`duckly`](crate::duckly) was generated from, e.g. `v0.7.1`
+pub const HEADERS_VERSION: &str = env!("DUCKDB_HEADERS_VERSION");
+
+/// A DuckDB version, as returned by `duckdb_library_version`, e.g. `v0.7.1` or `v0.7.2-dev1234`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    /// The number of commits since the previous release, for development builds
+    pub dev: Option<u32>,
+}
+
+impl Version {
+    /// The version of the DuckDB headers that [`duckly`](crate::duckly) was generated from
+    pub fn headers() -> Self {
+        HEADERS_VERSION
+            .parse()
+            .expect("DUCKDB_HEADERS_VERSION set by build.rs")
+    }
+}
+
+impl FromStr for Version {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::Conversion(format!("invalid DuckDB version: {}", s));
+
+        let version = s.strip_prefix('v').unwrap_or(s);
+        let (version, dev) = match version.split_once("-dev") {
+            Some((version, dev)) => (version, Some(dev.parse().map_err(|_| invalid())?)),
+            None => (version, None),
+        };
+
+        let mut parts = version.split('.');
+        let mut next = || -> Result<u32, Error> {
+            parts
+                .next()
+                .and_then(|part| part.parse().ok())
+                .ok_or_else(invalid)
+        };
+        let (major, minor, patch) = (next()?, next()?, next()?);
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            major,
+            minor,
+            patch,
+            dev,
+        })
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(dev) = self.dev {
+            write!(f, "-dev{}", dev)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decides whether an extension built against one version of DuckDB can be loaded into another
+///
+/// This is checked when the extension is initialized. DuckDB compares the headers version the extension reports
+/// with its own before that, see [`entrypoint::version`](crate::entrypoint::version).
+#[derive(Clone, Copy, Default)]
+pub enum VersionPolicy {
+    /// The versions must be identical, including the development build number
+    #[default]
+    Exact,
+    /// The major and minor versions must match, so patch releases of DuckDB can load the extension
+    MinorCompatible,
+    /// Called with the version of the headers and the version of the library, returning whether they are compatible
+    Custom(fn(&Version, &Version) -> bool),
+}
+
+impl fmt::Debug for VersionPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Exact => "Exact",
+            Self::MinorCompatible => "MinorCompatible",
+            Self::Custom(_) => "Custom",
+        })
+    }
+}
+
+impl VersionPolicy {
+    /// Returns whether an extension built against the `headers` version can be loaded into the `library` version
+    pub fn is_compatible(&self, headers: &Version, library: &Version) -> bool {
+        match self {
+            Self::Exact => headers == library,
+            Self::MinorCompatible => {
+                headers.major == library.major && headers.minor == library.minor
+            }
+            Self::Custom(is_compatible) => is_compatible(headers, library),
+        }
+    }
+
+    /// Compares the version of the headers with the version of the library the process is running,
+    /// returning [`Error::IncompatibleVersion`] if they aren't compatible.
+    pub fn check(&self, library: &Version) -> Result<(), Error> {
+        let headers = Version::headers();
+        if self.is_compatible(&headers, library) {
+            Ok(())
+        } else {
+            Err(Error::IncompatibleVersion {
+                headers,
+                library: *library,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::version::{Version, VersionPolicy};
+    use std::error::Error;
+
+    #[test]
+    fn test_parse() -> Result<(), Box<dyn Error>> {
+        let release: Version = "v0.7.1".parse()?;
+        assert_eq!(
+            release,
+            Version {
+                major: 0,
+                minor: 7,
+                patch: 1,
+                dev: None
+            }
+        );
+        assert_eq!(release.to_string(), "v0.7.1");
+
+        let dev: Version = "v0.7.2-dev1234".parse()?;
+        assert_eq!(dev.dev, Some(1234));
+        assert_eq!(dev.to_string(), "v0.7.2-dev1234");
+        assert_ne!(dev, release);
+
+        assert!("v0.7".parse::<Version>().is_err());
+        assert!("v0.7.1.2".parse::<Version>().is_err());
+        assert!("v0.7.2-devx".parse::<Version>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_policies() -> Result<(), Box<dyn Error>> {
+        let headers: Version = "v0.7.1".parse()?;
+        let patch: Version = "v0.7.2".parse()?;
+        let minor: Version = "v0.8.0".parse()?;
+
+        assert!(VersionPolicy::Exact.is_compatible(&headers, &headers));
+        assert!(!VersionPolicy::Exact.is_compatible(&headers, &patch));
+
+        assert!(VersionPolicy::MinorCompatible.is_compatible(&headers, &patch));
+        assert!(!VersionPolicy::MinorCompatible.is_compatible(&headers, &minor));
+
+        let any = VersionPolicy::Custom(|_, library| library.major == 0);
+        assert!(any.is_compatible(&headers, &minor));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_check() {
+        let library = Version {
+            major: 99,
+            ..Version::headers()
+        };
+
+        let error = VersionPolicy::Exact.check(&library).unwrap_err();
+        assert!(error.to_string().contains("v99."), "{}", error);
+
+        assert!(VersionPolicy::Exact.check(&Version::headers()).is_ok());
+    }
+}
@@ -1,7 +1,34 @@
 #include "duckdb.hpp"
+#include "duckdb/catalog/catalog.hpp"
+#include "duckdb/catalog/catalog_entry/copy_function_catalog_entry.hpp"
+#include "duckdb/catalog/catalog_entry/table_function_catalog_entry.hpp"
+#include "duckdb/catalog/duck_catalog.hpp"
+#include "duckdb/common/file_system.hpp"
+#include "duckdb/common/string_util.hpp"
+#include "duckdb/function/copy_function.hpp"
+#include "duckdb/function/pragma_function.hpp"
+#include "duckdb/function/table_function.hpp"
+#include "duckdb/main/client_context.hpp"
+#include "duckdb/main/config.hpp"
+#include "duckdb/main/database.hpp"
+#include "duckdb/parser/parsed_data/create_copy_function_info.hpp"
+#include "duckdb/parser/keyword_helper.hpp"
+#include "duckdb/parser/parsed_data/attach_info.hpp"
+#include "duckdb/parser/parsed_data/create_collation_info.hpp"
+#include "duckdb/parser/parsed_data/create_pragma_function_info.hpp"
+#include "duckdb/parser/parsed_data/create_schema_info.hpp"
+#include "duckdb/parser/parsed_data/create_view_info.hpp"
+#include "duckdb/parser/parser.hpp"
+#include "duckdb/parser/statement/select_statement.hpp"
+#include "duckdb/storage/object_cache.hpp"
+#include "duckdb/storage/storage_extension.hpp"
+#include "duckdb/transaction/duck_transaction_manager.hpp"
 #include "wrapper.hpp"
 
+#include <array>
 #include <memory>
+#include <mutex>
+#include <utility>
 
 static duckdb::child_list_t<duckdb::LogicalType>
 getVector(idx_t n_pairs, const char *const *names, duckdb_logical_type const *types) {
@@ -15,18 +42,1836 @@ getVector(idx_t n_pairs, const char *const *names, duckdb_logical_type const *ty
     return members;
 }
 
+// Returns the registry of type T kept in the object cache of the database, creating it on first use
+static std::mutex registry_lock;
+
+template <class T>
+static std::shared_ptr<T> GetRegistry(duckdb::DatabaseInstance &db) {
+    std::lock_guard<std::mutex> guard(registry_lock);
+    auto &cache = db.GetObjectCache();
+    auto registry = cache.Get<T>(T::ObjectType());
+    if (!registry) {
+        registry = std::make_shared<T>();
+        cache.Put(T::ObjectType(), registry);
+    }
+    return registry;
+}
+
+//===--------------------------------------------------------------------===//
+// Errors
+//===--------------------------------------------------------------------===//
+// Why the last registration on this thread failed, returned by duckdb_extension_last_error
+static thread_local std::string last_error;
+
+static duckdb_state SetRegistrationError(std::string error) {
+    last_error = std::move(error);
+    return DuckDBError;
+}
+
+extern "C" {
+
+const char *duckdb_extension_last_error() {
+    return last_error.empty() ? nullptr : last_error.c_str();
+}
+
+}
+
+//===--------------------------------------------------------------------===//
+// Table Functions
+//===--------------------------------------------------------------------===//
+extern "C" {
+
+duckdb_state duckdb_extension_register_table_function(duckdb_connection connection, duckdb_table_function function) {
+    if (!connection || !function) {
+        return SetRegistrationError("invalid connection or table function");
+    }
+    auto con = (duckdb::Connection *) connection;
+    auto tf = (duckdb::TableFunction *) function;
+    if (tf->name.empty()) {
+        return SetRegistrationError("table function needs a name");
+    }
+
+    try {
+        // The C API reports a name that's taken the same way as a missing callback, so look for it first
+        bool exists = false;
+        con->context->RunFunctionInTransaction([&]() {
+            exists = duckdb::Catalog::GetEntry<duckdb::TableFunctionCatalogEntry>(
+                         *con->context, SYSTEM_CATALOG, DEFAULT_SCHEMA, tf->name, true) != nullptr;
+        });
+        if (exists) {
+            return SetRegistrationError("a table function named \"" + tf->name + "\" already exists");
+        }
+        // The callbacks are kept where only the C API can see them, so it's the one to check them
+        if (duckdb_register_table_function(connection, function) != DuckDBSuccess) {
+            return SetRegistrationError("table function needs a bind function, an init function and a main function");
+        }
+    } catch (std::exception &ex) {
+        return SetRegistrationError(ex.what());
+    }
+    return DuckDBSuccess;
+}
+
+}
+
+//===--------------------------------------------------------------------===//
+// Pragma Functions
+//===--------------------------------------------------------------------===//
+// DuckDB's pragma callbacks are plain function pointers that receive neither user data nor the function they belong
+// to. Every database keeps its pragma functions in a registry in its object cache, each function is assigned a slot
+// in it, and a trampoline instantiated for that slot forwards to the C callback of the database it's called from.
+static constexpr idx_t MAX_PRAGMA_FUNCTIONS = 64;
+
+struct CPragmaFunctionInfo {
+    ~CPragmaFunctionInfo() {
+        if (extra_info && delete_callback) {
+            delete_callback(extra_info);
+        }
+        extra_info = nullptr;
+        delete_callback = nullptr;
+    }
+
+    duckdb_pragma_function_t callback = nullptr;
+    bool returns_query = false;
+    void *extra_info = nullptr;
+    duckdb_delete_callback_t delete_callback = nullptr;
+};
+
+struct CPragmaFunction {
+    std::string name;
+    bool is_statement = false;
+    std::vector<duckdb::LogicalType> arguments;
+    std::shared_ptr<CPragmaFunctionInfo> info = std::make_shared<CPragmaFunctionInfo>();
+};
+
+struct CPragmaInternalInfo {
+    CPragmaInternalInfo(CPragmaFunctionInfo &function_info, const duckdb::FunctionParameters &parameters)
+            : function_info(function_info), parameters(parameters), success(true) {
+    }
+
+    CPragmaFunctionInfo &function_info;
+    const duckdb::FunctionParameters &parameters;
+    std::string query;
+    bool success;
+    std::string error;
+};
+
+struct CPragmaRegistry : public duckdb::ObjectCacheEntry {
+    static std::string ObjectType() {
+        return "duckdb_extension_framework_pragma_functions";
+    }
+
+    std::string GetObjectType() override {
+        return ObjectType();
+    }
+
+    std::mutex lock;
+    std::vector<std::shared_ptr<CPragmaFunctionInfo>> slots;
+};
+
+static std::string InvokePragma(duckdb::ClientContext &context, idx_t slot,
+                                const duckdb::FunctionParameters &parameters) {
+    auto registry = GetRegistry<CPragmaRegistry>(duckdb::DatabaseInstance::GetDatabase(context));
+    std::shared_ptr<CPragmaFunctionInfo> function_info;
+    {
+        std::lock_guard<std::mutex> guard(registry->lock);
+        if (slot < registry->slots.size()) {
+            function_info = registry->slots[slot];
+        }
+    }
+    if (!function_info) {
+        throw duckdb::InternalException("pragma function is not registered with this database");
+    }
+    CPragmaInternalInfo info(*function_info, parameters);
+    function_info->callback(&info);
+    if (!info.success) {
+        throw duckdb::InvalidInputException(info.error);
+    }
+    return info.query;
+}
+
+template <idx_t SLOT>
+static std::string PragmaQueryTrampoline(duckdb::ClientContext &context, const duckdb::FunctionParameters &parameters) {
+    return InvokePragma(context, SLOT, parameters);
+}
+
+template <idx_t SLOT>
+static void PragmaFunctionTrampoline(duckdb::ClientContext &context, const duckdb::FunctionParameters &parameters) {
+    InvokePragma(context, SLOT, parameters);
+}
+
+template <std::size_t... SLOTS>
+static std::array<duckdb::pragma_query_t, sizeof...(SLOTS)> MakePragmaQueryTrampolines(std::index_sequence<SLOTS...>) {
+    return {{&PragmaQueryTrampoline<SLOTS>...}};
+}
+
+template <std::size_t... SLOTS>
+static std::array<duckdb::pragma_function_t, sizeof...(SLOTS)> MakePragmaFunctionTrampolines(std::index_sequence<SLOTS...>) {
+    return {{&PragmaFunctionTrampoline<SLOTS>...}};
+}
+
+static const auto pragma_query_trampolines =
+        MakePragmaQueryTrampolines(std::make_index_sequence<MAX_PRAGMA_FUNCTIONS>());
+static const auto pragma_function_trampolines =
+        MakePragmaFunctionTrampolines(std::make_index_sequence<MAX_PRAGMA_FUNCTIONS>());
+
+static duckdb::PragmaFunction BuildPragmaFunction(CPragmaFunction &function, idx_t slot) {
+    if (function.is_statement) {
+        if (function.info->returns_query) {
+            return duckdb::PragmaFunction::PragmaStatement(function.name, pragma_query_trampolines[slot]);
+        }
+        return duckdb::PragmaFunction::PragmaStatement(function.name, pragma_function_trampolines[slot]);
+    }
+    if (function.info->returns_query) {
+        return duckdb::PragmaFunction::PragmaCall(function.name, pragma_query_trampolines[slot], function.arguments);
+    }
+    return duckdb::PragmaFunction::PragmaCall(function.name, pragma_function_trampolines[slot], function.arguments);
+}
+
 extern "C" {
 
 duckdb_logical_type duckdb_create_struct_type(idx_t n_pairs, const char **names, const duckdb_logical_type *types) {
-    auto *stype = new duckdb::LogicalType;
-    *stype = duckdb::LogicalType::STRUCT(getVector(n_pairs, names, types));
-    return stype;
+    try {
+        return new duckdb::LogicalType(duckdb::LogicalType::STRUCT(getVector(n_pairs, names, types)));
+    } catch (std::exception &ex) {
+        return nullptr;
+    }
 }
 
 duckdb_logical_type duckdb_create_union(idx_t nmembers, const char **names, const duckdb_logical_type *types) {
-    auto *utype = new duckdb::LogicalType;
-    *utype = duckdb::LogicalType::UNION(getVector(nmembers, names, types));
-    return utype;
+    try {
+        return new duckdb::LogicalType(duckdb::LogicalType::UNION(getVector(nmembers, names, types)));
+    } catch (std::exception &ex) {
+        return nullptr;
+    }
+}
+
+duckdb_logical_type duckdb_create_enum_type(const char **member_names, idx_t member_count) {
+    if (!member_names) {
+        return nullptr;
+    }
+    try {
+        duckdb::Vector values(duckdb::LogicalType::VARCHAR, member_count);
+        auto data = duckdb::FlatVector::GetData<duckdb::string_t>(values);
+        for (idx_t i = 0; i < member_count; i++) {
+            data[i] = duckdb::StringVector::AddStringOrBlob(values, member_names[i]);
+        }
+        return new duckdb::LogicalType(duckdb::LogicalType::ENUM("", values, member_count));
+    } catch (std::exception &ex) {
+        return nullptr;
+    }
+}
+
+duckdb_logical_type duckdb_extension_create_logical_type(duckdb_type type) {
+    switch (type) {
+    case DUCKDB_TYPE_BIT:
+        return new duckdb::LogicalType(duckdb::LogicalTypeId::BIT);
+    case DUCKDB_EXTENSION_TYPE_TIME_TZ:
+        return new duckdb::LogicalType(duckdb::LogicalTypeId::TIME_TZ);
+    case DUCKDB_EXTENSION_TYPE_TIMESTAMP_TZ:
+        return new duckdb::LogicalType(duckdb::LogicalTypeId::TIMESTAMP_TZ);
+    default:
+        return duckdb_create_logical_type(type);
+    }
+}
+
+duckdb_type duckdb_extension_get_type_id(duckdb_logical_type type) {
+    if (!type) {
+        return DUCKDB_TYPE_INVALID;
+    }
+    switch (((duckdb::LogicalType *) type)->id()) {
+    case duckdb::LogicalTypeId::TIME_TZ:
+        return (duckdb_type) DUCKDB_EXTENSION_TYPE_TIME_TZ;
+    case duckdb::LogicalTypeId::TIMESTAMP_TZ:
+        return (duckdb_type) DUCKDB_EXTENSION_TYPE_TIMESTAMP_TZ;
+    default:
+        return duckdb_get_type_id(type);
+    }
+}
+
+duckdb_logical_type duckdb_copy_logical_type(duckdb_logical_type type) {
+    if (!type) {
+        return nullptr;
+    }
+    return new duckdb::LogicalType(*(duckdb::LogicalType *) type);
+}
+
+bool duckdb_logical_type_equals(duckdb_logical_type left, duckdb_logical_type right) {
+    if (!left || !right) {
+        return left == right;
+    }
+    return *(duckdb::LogicalType *) left == *(duckdb::LogicalType *) right;
+}
+
+idx_t duckdb_union_type_member_count(duckdb_logical_type type) {
+    if (!type) {
+        return 0;
+    }
+    auto &ltype = *(duckdb::LogicalType *) type;
+    if (ltype.id() != duckdb::LogicalTypeId::UNION) {
+        return 0;
+    }
+    return duckdb::UnionType::GetMemberCount(ltype);
+}
+
+char *duckdb_union_type_member_name(duckdb_logical_type type, idx_t index) {
+    if (index >= duckdb_union_type_member_count(type)) {
+        return nullptr;
+    }
+    auto &ltype = *(duckdb::LogicalType *) type;
+    try {
+        return strdup(duckdb::UnionType::GetMemberName(ltype, index).c_str());
+    } catch (std::exception &ex) {
+        return nullptr;
+    }
+}
+
+duckdb_logical_type duckdb_union_type_member_type(duckdb_logical_type type, idx_t index) {
+    if (index >= duckdb_union_type_member_count(type)) {
+        return nullptr;
+    }
+    auto &ltype = *(duckdb::LogicalType *) type;
+    try {
+        return new duckdb::LogicalType(duckdb::UnionType::GetMemberType(ltype, index));
+    } catch (std::exception &ex) {
+        return nullptr;
+    }
+}
+
+duckdb_pragma_function duckdb_create_pragma_function(bool is_statement) {
+    auto function = new CPragmaFunction();
+    function->is_statement = is_statement;
+    return function;
+}
+
+void duckdb_destroy_pragma_function(duckdb_pragma_function *pragma_function) {
+    if (pragma_function && *pragma_function) {
+        auto function = (CPragmaFunction *) *pragma_function;
+        delete function;
+        *pragma_function = nullptr;
+    }
+}
+
+void duckdb_pragma_function_set_name(duckdb_pragma_function pragma_function, const char *name) {
+    if (!pragma_function || !name) {
+        return;
+    }
+    auto function = (CPragmaFunction *) pragma_function;
+    function->name = name;
+}
+
+void duckdb_pragma_function_add_parameter(duckdb_pragma_function pragma_function, duckdb_logical_type type) {
+    if (!pragma_function || !type) {
+        return;
+    }
+    auto function = (CPragmaFunction *) pragma_function;
+    function->arguments.push_back(*(duckdb::LogicalType *) type);
+}
+
+void duckdb_pragma_function_set_function(duckdb_pragma_function pragma_function, duckdb_pragma_function_t callback) {
+    if (!pragma_function) {
+        return;
+    }
+    auto function = (CPragmaFunction *) pragma_function;
+    function->info->callback = callback;
+    function->info->returns_query = false;
+}
+
+void duckdb_pragma_function_set_query(duckdb_pragma_function pragma_function, duckdb_pragma_function_t query) {
+    if (!pragma_function) {
+        return;
+    }
+    auto function = (CPragmaFunction *) pragma_function;
+    function->info->callback = query;
+    function->info->returns_query = true;
+}
+
+void duckdb_pragma_function_set_extra_info(duckdb_pragma_function pragma_function, void *extra_info,
+                                           duckdb_delete_callback_t destroy) {
+    if (!pragma_function) {
+        return;
+    }
+    auto function = (CPragmaFunction *) pragma_function;
+    function->info->extra_info = extra_info;
+    function->info->delete_callback = destroy;
+}
+
+duckdb_state duckdb_register_pragma_function(duckdb_connection connection, duckdb_pragma_function pragma_function) {
+    if (!connection || !pragma_function) {
+        return SetRegistrationError("invalid connection or pragma function");
+    }
+    auto con = (duckdb::Connection *) connection;
+    auto function = (CPragmaFunction *) pragma_function;
+    if (function->name.empty() || !function->info->callback) {
+        return SetRegistrationError("pragma function needs a name and a function or query");
+    }
+    if (function->is_statement && !function->arguments.empty()) {
+        return SetRegistrationError("pragma statements can't have parameters");
+    }
+
+    try {
+        auto registry = GetRegistry<CPragmaRegistry>(duckdb::DatabaseInstance::GetDatabase(*con->context));
+        std::lock_guard<std::mutex> guard(registry->lock);
+        if (registry->slots.size() >= MAX_PRAGMA_FUNCTIONS) {
+            return SetRegistrationError("too many pragma functions in this database");
+        }
+        auto slot = registry->slots.size();
+        auto pf = BuildPragmaFunction(*function, slot);
+        con->context->RunFunctionInTransaction([&]() {
+            auto &catalog = duckdb::Catalog::GetSystemCatalog(*con->context);
+            duckdb::CreatePragmaFunctionInfo pf_info(pf);
+            catalog.CreatePragmaFunction(*con->context, &pf_info);
+        });
+        registry->slots.push_back(function->info);
+    } catch (std::exception &ex) {
+        return SetRegistrationError(ex.what());
+    }
+    return DuckDBSuccess;
+}
+
+void *duckdb_pragma_get_extra_info(duckdb_pragma_info info) {
+    if (!info) {
+        return nullptr;
+    }
+    auto internal_info = (CPragmaInternalInfo *) info;
+    return internal_info->function_info.extra_info;
+}
+
+idx_t duckdb_pragma_get_parameter_count(duckdb_pragma_info info) {
+    if (!info) {
+        return 0;
+    }
+    auto internal_info = (CPragmaInternalInfo *) info;
+    return internal_info->parameters.values.size();
+}
+
+duckdb_value duckdb_pragma_get_parameter(duckdb_pragma_info info, idx_t index) {
+    if (!info) {
+        return nullptr;
+    }
+    auto internal_info = (CPragmaInternalInfo *) info;
+    if (index >= internal_info->parameters.values.size()) {
+        return nullptr;
+    }
+    return new duckdb::Value(internal_info->parameters.values[index]);
+}
+
+void duckdb_pragma_set_query(duckdb_pragma_info info, const char *query) {
+    if (!info || !query) {
+        return;
+    }
+    auto internal_info = (CPragmaInternalInfo *) info;
+    internal_info->query = query;
+}
+
+void duckdb_pragma_set_error(duckdb_pragma_info info, const char *error) {
+    if (!info || !error) {
+        return;
+    }
+    auto internal_info = (CPragmaInternalInfo *) info;
+    internal_info->error = error;
+    internal_info->success = false;
 }
 
-}
\ No newline at end of file
+}
+
+//===--------------------------------------------------------------------===//
+// Copy Functions
+//===--------------------------------------------------------------------===//
+// DuckDB's copy callbacks do not receive the function they belong to, so the callbacks are kept in the function info
+// of the copy function's (otherwise unused) COPY FROM table function. The bind callback looks the copy function up in
+// the catalog by its FORMAT and carries the callbacks in the bind data from then on.
+struct CCopyFunctionInfo {
+    ~CCopyFunctionInfo() {
+        if (extra_info && delete_callback) {
+            delete_callback(extra_info);
+        }
+        extra_info = nullptr;
+        delete_callback = nullptr;
+    }
+
+    duckdb_copy_function_t bind = nullptr;
+    duckdb_copy_function_t init_global = nullptr;
+    duckdb_copy_function_t init_local = nullptr;
+    duckdb_copy_sink_t sink = nullptr;
+    duckdb_copy_function_t combine = nullptr;
+    duckdb_copy_function_t finalize = nullptr;
+    void *extra_info = nullptr;
+    duckdb_delete_callback_t delete_callback = nullptr;
+};
+
+struct CCopyFunction {
+    std::string name;
+    std::shared_ptr<CCopyFunctionInfo> info = std::make_shared<CCopyFunctionInfo>();
+};
+
+struct CCopyTableFunctionInfo : public duckdb::TableFunctionInfo {
+    explicit CCopyTableFunctionInfo(std::shared_ptr<CCopyFunctionInfo> info_p) : info(std::move(info_p)) {
+    }
+
+    std::shared_ptr<CCopyFunctionInfo> info;
+};
+
+struct CCopyState {
+    ~CCopyState() {
+        Set(nullptr, nullptr);
+    }
+
+    void Set(void *data_p, duckdb_delete_callback_t delete_callback_p) {
+        if (data && delete_callback) {
+            delete_callback(data);
+        }
+        data = data_p;
+        delete_callback = delete_callback_p;
+    }
+
+    void *data = nullptr;
+    duckdb_delete_callback_t delete_callback = nullptr;
+};
+
+struct CCopyBindData : public duckdb::FunctionData {
+    explicit CCopyBindData(std::shared_ptr<CCopyFunctionInfo> info_p)
+        : info(std::move(info_p)), state(std::make_shared<CCopyState>()) {
+    }
+
+    // DuckDB copies bind data along with plans, e.g. for prepared statements. The bind state is opaque, so copies
+    // share it, and it's destroyed with the last of them.
+    duckdb::unique_ptr<duckdb::FunctionData> Copy() const override {
+        auto copy = duckdb::make_unique<CCopyBindData>(info);
+        copy->file_path = file_path;
+        copy->state = state;
+        return std::move(copy);
+    }
+
+    bool Equals(const duckdb::FunctionData &other_p) const override {
+        auto &other = (const CCopyBindData &) other_p;
+        return info == other.info && state == other.state && file_path == other.file_path;
+    }
+
+    std::shared_ptr<CCopyFunctionInfo> info;
+    std::string file_path;
+    std::shared_ptr<CCopyState> state;
+};
+
+struct CCopyGlobalState : public duckdb::GlobalFunctionData {
+    CCopyState state;
+};
+
+struct CCopyLocalState : public duckdb::LocalFunctionData {
+    CCopyState state;
+};
+
+struct CCopyOption {
+    std::string name;
+    std::vector<duckdb::Value> values;
+};
+
+struct CCopyInternalInfo {
+    explicit CCopyInternalInfo(CCopyBindData &bind_data) : function_info(*bind_data.info), bind_data(bind_data) {
+    }
+
+    void Check() {
+        if (!success) {
+            throw duckdb::InvalidInputException(error);
+        }
+    }
+
+    CCopyFunctionInfo &function_info;
+    CCopyBindData &bind_data;
+    std::vector<CCopyOption> options;
+    const std::vector<std::string> *names = nullptr;
+    const std::vector<duckdb::LogicalType> *types = nullptr;
+    CCopyState *global_state = nullptr;
+    CCopyState *local_state = nullptr;
+    bool success = true;
+    std::string error;
+};
+
+static duckdb::unique_ptr<duckdb::FunctionData> CCopyBind(duckdb::ClientContext &context, duckdb::CopyInfo &info,
+                                                          std::vector<std::string> &names,
+                                                          std::vector<duckdb::LogicalType> &sql_types) {
+    auto entry = duckdb::Catalog::GetEntry<duckdb::CopyFunctionCatalogEntry>(context, SYSTEM_CATALOG, DEFAULT_SCHEMA,
+                                                                            info.format);
+    auto copy_info = entry->function.copy_from_function.function_info;
+    if (!copy_info) {
+        throw duckdb::InternalException("Copy function \"%s\" was not registered through the C API", info.format);
+    }
+    auto function_info = ((CCopyTableFunctionInfo &) *copy_info).info;
+    auto bind_data = duckdb::make_unique<CCopyBindData>(function_info);
+    bind_data->file_path = info.file_path;
+
+    CCopyInternalInfo internal_info(*bind_data);
+    for (auto &option : info.options) {
+        internal_info.options.push_back(CCopyOption {option.first, option.second});
+    }
+    internal_info.names = &names;
+    internal_info.types = &sql_types;
+    function_info->bind(&internal_info);
+    internal_info.Check();
+    return std::move(bind_data);
+}
+
+static duckdb::unique_ptr<duckdb::GlobalFunctionData> CCopyInitGlobal(duckdb::ClientContext &context,
+                                                                      duckdb::FunctionData &bind_data_p,
+                                                                      const std::string &file_path) {
+    auto &bind_data = (CCopyBindData &) bind_data_p;
+    bind_data.file_path = file_path;
+    auto global_state = duckdb::make_unique<CCopyGlobalState>();
+    if (bind_data.info->init_global) {
+        CCopyInternalInfo internal_info(bind_data);
+        internal_info.global_state = &global_state->state;
+        bind_data.info->init_global(&internal_info);
+        internal_info.Check();
+    }
+    return std::move(global_state);
+}
+
+static duckdb::unique_ptr<duckdb::LocalFunctionData> CCopyInitLocal(duckdb::ExecutionContext &context,
+                                                                    duckdb::FunctionData &bind_data_p) {
+    auto &bind_data = (CCopyBindData &) bind_data_p;
+    auto local_state = duckdb::make_unique<CCopyLocalState>();
+    if (bind_data.info->init_local) {
+        CCopyInternalInfo internal_info(bind_data);
+        internal_info.local_state = &local_state->state;
+        bind_data.info->init_local(&internal_info);
+        internal_info.Check();
+    }
+    return std::move(local_state);
+}
+
+static void CCopySink(duckdb::ExecutionContext &context, duckdb::FunctionData &bind_data_p,
+                      duckdb::GlobalFunctionData &gstate, duckdb::LocalFunctionData &lstate, duckdb::DataChunk &input) {
+    auto &bind_data = (CCopyBindData &) bind_data_p;
+    CCopyInternalInfo internal_info(bind_data);
+    internal_info.global_state = &((CCopyGlobalState &) gstate).state;
+    internal_info.local_state = &((CCopyLocalState &) lstate).state;
+    bind_data.info->sink(&internal_info, &input);
+    internal_info.Check();
+}
+
+static void CCopyCombine(duckdb::ExecutionContext &context, duckdb::FunctionData &bind_data_p,
+                         duckdb::GlobalFunctionData &gstate, duckdb::LocalFunctionData &lstate) {
+    auto &bind_data = (CCopyBindData &) bind_data_p;
+    if (!bind_data.info->combine) {
+        return;
+    }
+    CCopyInternalInfo internal_info(bind_data);
+    internal_info.global_state = &((CCopyGlobalState &) gstate).state;
+    internal_info.local_state = &((CCopyLocalState &) lstate).state;
+    bind_data.info->combine(&internal_info);
+    internal_info.Check();
+}
+
+static void CCopyFinalize(duckdb::ClientContext &context, duckdb::FunctionData &bind_data_p,
+                          duckdb::GlobalFunctionData &gstate) {
+    auto &bind_data = (CCopyBindData &) bind_data_p;
+    if (!bind_data.info->finalize) {
+        return;
+    }
+    CCopyInternalInfo internal_info(bind_data);
+    internal_info.global_state = &((CCopyGlobalState &) gstate).state;
+    bind_data.info->finalize(&internal_info);
+    internal_info.Check();
+}
+
+extern "C" {
+
+duckdb_copy_function duckdb_create_copy_function() {
+    return new CCopyFunction();
+}
+
+void duckdb_destroy_copy_function(duckdb_copy_function *copy_function) {
+    if (copy_function && *copy_function) {
+        auto function = (CCopyFunction *) *copy_function;
+        delete function;
+        *copy_function = nullptr;
+    }
+}
+
+void duckdb_copy_function_set_name(duckdb_copy_function copy_function, const char *name) {
+    if (!copy_function || !name) {
+        return;
+    }
+    auto function = (CCopyFunction *) copy_function;
+    function->name = name;
+}
+
+void duckdb_copy_function_set_extra_info(duckdb_copy_function copy_function, void *extra_info,
+                                         duckdb_delete_callback_t destroy) {
+    if (!copy_function) {
+        return;
+    }
+    auto function = (CCopyFunction *) copy_function;
+    function->info->extra_info = extra_info;
+    function->info->delete_callback = destroy;
+}
+
+void duckdb_copy_function_set_bind(duckdb_copy_function copy_function, duckdb_copy_function_t bind) {
+    if (!copy_function) {
+        return;
+    }
+    auto function = (CCopyFunction *) copy_function;
+    function->info->bind = bind;
+}
+
+void duckdb_copy_function_set_init_global(duckdb_copy_function copy_function, duckdb_copy_function_t init) {
+    if (!copy_function) {
+        return;
+    }
+    auto function = (CCopyFunction *) copy_function;
+    function->info->init_global = init;
+}
+
+void duckdb_copy_function_set_init_local(duckdb_copy_function copy_function, duckdb_copy_function_t init) {
+    if (!copy_function) {
+        return;
+    }
+    auto function = (CCopyFunction *) copy_function;
+    function->info->init_local = init;
+}
+
+void duckdb_copy_function_set_sink(duckdb_copy_function copy_function, duckdb_copy_sink_t sink) {
+    if (!copy_function) {
+        return;
+    }
+    auto function = (CCopyFunction *) copy_function;
+    function->info->sink = sink;
+}
+
+void duckdb_copy_function_set_combine(duckdb_copy_function copy_function, duckdb_copy_function_t combine) {
+    if (!copy_function) {
+        return;
+    }
+    auto function = (CCopyFunction *) copy_function;
+    function->info->combine = combine;
+}
+
+void duckdb_copy_function_set_finalize(duckdb_copy_function copy_function, duckdb_copy_function_t finalize) {
+    if (!copy_function) {
+        return;
+    }
+    auto function = (CCopyFunction *) copy_function;
+    function->info->finalize = finalize;
+}
+
+duckdb_state duckdb_register_copy_function(duckdb_connection connection, duckdb_copy_function copy_function) {
+    if (!connection || !copy_function) {
+        return SetRegistrationError("invalid connection or copy function");
+    }
+    auto con = (duckdb::Connection *) connection;
+    auto function = (CCopyFunction *) copy_function;
+    if (function->name.empty() || !function->info->bind || !function->info->sink) {
+        return SetRegistrationError("copy function needs a name, a bind function and a sink function");
+    }
+
+    duckdb::CopyFunction cf(function->name);
+    cf.copy_to_bind = CCopyBind;
+    cf.copy_to_initialize_global = CCopyInitGlobal;
+    cf.copy_to_initialize_local = CCopyInitLocal;
+    cf.copy_to_sink = CCopySink;
+    cf.copy_to_combine = CCopyCombine;
+    cf.copy_to_finalize = CCopyFinalize;
+    cf.copy_from_function.function_info = std::make_shared<CCopyTableFunctionInfo>(function->info);
+    try {
+        con->context->RunFunctionInTransaction([&]() {
+            auto &catalog = duckdb::Catalog::GetSystemCatalog(*con->context);
+            duckdb::CreateCopyFunctionInfo cf_info(cf);
+            catalog.CreateCopyFunction(*con->context, &cf_info);
+        });
+    } catch (std::exception &ex) {
+        return SetRegistrationError(ex.what());
+    }
+    return DuckDBSuccess;
+}
+
+void *duckdb_copy_get_extra_info(duckdb_copy_info info) {
+    if (!info) {
+        return nullptr;
+    }
+    auto internal_info = (CCopyInternalInfo *) info;
+    return internal_info->function_info.extra_info;
+}
+
+idx_t duckdb_copy_get_option_count(duckdb_copy_info info) {
+    if (!info) {
+        return 0;
+    }
+    auto internal_info = (CCopyInternalInfo *) info;
+    return internal_info->options.size();
+}
+
+const char *duckdb_copy_get_option_name(duckdb_copy_info info, idx_t index) {
+    if (!info) {
+        return nullptr;
+    }
+    auto internal_info = (CCopyInternalInfo *) info;
+    if (index >= internal_info->options.size()) {
+        return nullptr;
+    }
+    return internal_info->options[index].name.c_str();
+}
+
+idx_t duckdb_copy_get_option_value_count(duckdb_copy_info info, idx_t index) {
+    if (!info) {
+        return 0;
+    }
+    auto internal_info = (CCopyInternalInfo *) info;
+    if (index >= internal_info->options.size()) {
+        return 0;
+    }
+    return internal_info->options[index].values.size();
+}
+
+duckdb_value duckdb_copy_get_option_value(duckdb_copy_info info, idx_t index, idx_t value_index) {
+    if (!info) {
+        return nullptr;
+    }
+    auto internal_info = (CCopyInternalInfo *) info;
+    if (index >= internal_info->options.size() || value_index >= internal_info->options[index].values.size()) {
+        return nullptr;
+    }
+    return new duckdb::Value(internal_info->options[index].values[value_index]);
+}
+
+idx_t duckdb_copy_get_column_count(duckdb_copy_info info) {
+    if (!info) {
+        return 0;
+    }
+    auto internal_info = (CCopyInternalInfo *) info;
+    return internal_info->names ? internal_info->names->size() : 0;
+}
+
+const char *duckdb_copy_get_column_name(duckdb_copy_info info, idx_t index) {
+    if (!info) {
+        return nullptr;
+    }
+    auto internal_info = (CCopyInternalInfo *) info;
+    if (!internal_info->names || index >= internal_info->names->size()) {
+        return nullptr;
+    }
+    return (*internal_info->names)[index].c_str();
+}
+
+duckdb_logical_type duckdb_copy_get_column_type(duckdb_copy_info info, idx_t index) {
+    if (!info) {
+        return nullptr;
+    }
+    auto internal_info = (CCopyInternalInfo *) info;
+    if (!internal_info->types || index >= internal_info->types->size()) {
+        return nullptr;
+    }
+    return new duckdb::LogicalType((*internal_info->types)[index]);
+}
+
+const char *duckdb_copy_get_file_path(duckdb_copy_info info) {
+    if (!info) {
+        return nullptr;
+    }
+    auto internal_info = (CCopyInternalInfo *) info;
+    return internal_info->bind_data.file_path.c_str();
+}
+
+void duckdb_copy_set_bind_data(duckdb_copy_info info, void *bind_data, duckdb_delete_callback_t destroy) {
+    if (!info) {
+        return;
+    }
+    auto internal_info = (CCopyInternalInfo *) info;
+    internal_info->bind_data.state->Set(bind_data, destroy);
+}
+
+void *duckdb_copy_get_bind_data(duckdb_copy_info info) {
+    if (!info) {
+        return nullptr;
+    }
+    auto internal_info = (CCopyInternalInfo *) info;
+    return internal_info->bind_data.state->data;
+}
+
+void duckdb_copy_set_global_state(duckdb_copy_info info, void *global_state, duckdb_delete_callback_t destroy) {
+    if (!info) {
+        return;
+    }
+    auto internal_info = (CCopyInternalInfo *) info;
+    if (internal_info->global_state) {
+        internal_info->global_state->Set(global_state, destroy);
+    }
+}
+
+void *duckdb_copy_get_global_state(duckdb_copy_info info) {
+    if (!info) {
+        return nullptr;
+    }
+    auto internal_info = (CCopyInternalInfo *) info;
+    return internal_info->global_state ? internal_info->global_state->data : nullptr;
+}
+
+void duckdb_copy_set_local_state(duckdb_copy_info info, void *local_state, duckdb_delete_callback_t destroy) {
+    if (!info) {
+        return;
+    }
+    auto internal_info = (CCopyInternalInfo *) info;
+    if (internal_info->local_state) {
+        internal_info->local_state->Set(local_state, destroy);
+    }
+}
+
+void *duckdb_copy_get_local_state(duckdb_copy_info info) {
+    if (!info) {
+        return nullptr;
+    }
+    auto internal_info = (CCopyInternalInfo *) info;
+    return internal_info->local_state ? internal_info->local_state->data : nullptr;
+}
+
+void duckdb_copy_set_error(duckdb_copy_info info, const char *error) {
+    if (!info || !error) {
+        return;
+    }
+    auto internal_info = (CCopyInternalInfo *) info;
+    internal_info->error = error;
+    internal_info->success = false;
+}
+
+}
+
+//===--------------------------------------------------------------------===//
+// File Systems
+//===--------------------------------------------------------------------===//
+// Mirrors the layout of the C API's internal DatabaseData, which is what a duckdb_database points to.
+struct CDatabaseData {
+    duckdb::unique_ptr<duckdb::DuckDB> database;
+};
+
+struct CFileSystemInfo {
+    ~CFileSystemInfo() {
+        if (extra_info && delete_callback) {
+            delete_callback(extra_info);
+        }
+        extra_info = nullptr;
+        delete_callback = nullptr;
+    }
+
+    std::string prefix;
+    duckdb_file_system_open_t open = nullptr;
+    duckdb_file_system_close_t close = nullptr;
+    duckdb_file_system_read_t read = nullptr;
+    duckdb_file_system_write_t write = nullptr;
+    duckdb_file_system_stat_t stat = nullptr;
+    duckdb_file_system_glob_t glob = nullptr;
+    duckdb_file_system_remove_t remove = nullptr;
+    void *extra_info = nullptr;
+    duckdb_delete_callback_t delete_callback = nullptr;
+};
+
+struct CFileSystemInternalInfo {
+    explicit CFileSystemInternalInfo(CFileSystemInfo &function_info) : function_info(function_info) {
+    }
+
+    void Check() {
+        if (!success) {
+            throw duckdb::IOException(error);
+        }
+    }
+
+    CFileSystemInfo &function_info;
+    bool success = true;
+    std::string error;
+};
+
+class CFileHandle : public duckdb::FileHandle {
+public:
+    CFileHandle(duckdb::FileSystem &file_system, std::string path, std::shared_ptr<CFileSystemInfo> info_p,
+                void *handle_p)
+            : FileHandle(file_system, std::move(path)), info(std::move(info_p)), handle(handle_p) {
+    }
+
+    ~CFileHandle() override {
+        Close();
+    }
+
+    void Close() override {
+        if (!handle) {
+            return;
+        }
+        if (info->close) {
+            CFileSystemInternalInfo internal_info(*info);
+            info->close(&internal_info, handle);
+        }
+        handle = nullptr;
+    }
+
+    std::shared_ptr<CFileSystemInfo> info;
+    void *handle;
+    idx_t position = 0;
+};
+
+class CFileSystem : public duckdb::FileSystem {
+public:
+    explicit CFileSystem(std::shared_ptr<CFileSystemInfo> info_p) : info(std::move(info_p)) {
+    }
+
+    duckdb::unique_ptr<duckdb::FileHandle> OpenFile(const std::string &path, uint8_t flags,
+                                                    duckdb::FileLockType lock = duckdb::FileLockType::NO_LOCK,
+                                                    duckdb::FileCompressionType compression =
+                                                            duckdb::FileCompressionType::UNCOMPRESSED,
+                                                    duckdb::FileOpener *opener = nullptr) override {
+        if (compression != duckdb::FileCompressionType::UNCOMPRESSED) {
+            throw duckdb::NotImplementedException("Compressed files are not supported by file system \"%s\"",
+                                                  info->prefix);
+        }
+        CFileSystemInternalInfo internal_info(*info);
+        auto handle = info->open(&internal_info, path.c_str(), flags);
+        internal_info.Check();
+        if (!handle) {
+            throw duckdb::IOException("Could not open file \"%s\"", path);
+        }
+        return duckdb::make_unique<CFileHandle>(*this, path, info, handle);
+    }
+
+    void Read(duckdb::FileHandle &handle, void *buffer, int64_t nr_bytes, idx_t location) override {
+        auto data = (char *) buffer;
+        while (nr_bytes > 0) {
+            auto bytes_read = ReadAt(handle, data, nr_bytes, location);
+            if (bytes_read == 0) {
+                throw duckdb::IOException("Could not read all bytes from file \"%s\"", handle.path);
+            }
+            data += bytes_read;
+            nr_bytes -= bytes_read;
+            location += bytes_read;
+        }
+    }
+
+    int64_t Read(duckdb::FileHandle &handle, void *buffer, int64_t nr_bytes) override {
+        auto &c_handle = (CFileHandle &) handle;
+        auto bytes_read = ReadAt(handle, buffer, nr_bytes, c_handle.position);
+        c_handle.position += bytes_read;
+        return bytes_read;
+    }
+
+    void Write(duckdb::FileHandle &handle, void *buffer, int64_t nr_bytes, idx_t location) override {
+        auto data = (const char *) buffer;
+        while (nr_bytes > 0) {
+            auto bytes_written = WriteAt(handle, data, nr_bytes, location);
+            if (bytes_written == 0) {
+                throw duckdb::IOException("Could not write all bytes to file \"%s\"", handle.path);
+            }
+            data += bytes_written;
+            nr_bytes -= bytes_written;
+            location += bytes_written;
+        }
+    }
+
+    int64_t Write(duckdb::FileHandle &handle, void *buffer, int64_t nr_bytes) override {
+        auto &c_handle = (CFileHandle &) handle;
+        Write(handle, buffer, nr_bytes, c_handle.position);
+        c_handle.position += nr_bytes;
+        return nr_bytes;
+    }
+
+    int64_t GetFileSize(duckdb::FileHandle &handle) override {
+        return Stat(handle.path).size;
+    }
+
+    time_t GetLastModifiedTime(duckdb::FileHandle &handle) override {
+        return Stat(handle.path).last_modified;
+    }
+
+    duckdb::FileType GetFileType(duckdb::FileHandle &handle) override {
+        return Stat(handle.path).is_directory ? duckdb::FileType::FILE_TYPE_DIR : duckdb::FileType::FILE_TYPE_REGULAR;
+    }
+
+    bool FileExists(const std::string &filename) override {
+        duckdb_file_stat stat;
+        return TryStat(filename, stat) && !stat.is_directory;
+    }
+
+    bool DirectoryExists(const std::string &directory) override {
+        duckdb_file_stat stat;
+        return TryStat(directory, stat) && stat.is_directory;
+    }
+
+    void RemoveFile(const std::string &filename) override {
+        if (!info->remove) {
+            throw duckdb::NotImplementedException("File system \"%s\" does not support removing files", info->prefix);
+        }
+        CFileSystemInternalInfo internal_info(*info);
+        info->remove(&internal_info, filename.c_str());
+        internal_info.Check();
+    }
+
+    std::vector<std::string> Glob(const std::string &path, duckdb::FileOpener *opener = nullptr) override {
+        std::vector<std::string> result;
+        if (!info->glob) {
+            if (FileExists(path)) {
+                result.push_back(path);
+            }
+            return result;
+        }
+        CFileSystemInternalInfo internal_info(*info);
+        info->glob(&internal_info, path.c_str(), &result);
+        internal_info.Check();
+        return result;
+    }
+
+    void FileSync(duckdb::FileHandle &handle) override {
+    }
+
+    void Seek(duckdb::FileHandle &handle, idx_t location) override {
+        ((CFileHandle &) handle).position = location;
+    }
+
+    idx_t SeekPosition(duckdb::FileHandle &handle) override {
+        return ((CFileHandle &) handle).position;
+    }
+
+    void Reset(duckdb::FileHandle &handle) override {
+        ((CFileHandle &) handle).position = 0;
+    }
+
+    bool CanSeek() override {
+        return true;
+    }
+
+    bool OnDiskFile(duckdb::FileHandle &handle) override {
+        return false;
+    }
+
+    bool CanHandleFile(const std::string &fpath) override {
+        return duckdb::StringUtil::StartsWith(fpath, info->prefix);
+    }
+
+    std::string GetName() const override {
+        return info->prefix;
+    }
+
+private:
+    int64_t ReadAt(duckdb::FileHandle &handle, void *buffer, int64_t nr_bytes, idx_t location) {
+        CFileSystemInternalInfo internal_info(*info);
+        auto bytes_read = info->read(&internal_info, ((CFileHandle &) handle).handle, buffer, nr_bytes, location);
+        internal_info.Check();
+        return bytes_read;
+    }
+
+    int64_t WriteAt(duckdb::FileHandle &handle, const void *buffer, int64_t nr_bytes, idx_t location) {
+        if (!info->write) {
+            throw duckdb::NotImplementedException("File system \"%s\" does not support writing", info->prefix);
+        }
+        CFileSystemInternalInfo internal_info(*info);
+        auto bytes_written =
+                info->write(&internal_info, ((CFileHandle &) handle).handle, buffer, nr_bytes, location);
+        internal_info.Check();
+        return bytes_written;
+    }
+
+    bool TryStat(const std::string &path, duckdb_file_stat &stat) {
+        stat = duckdb_file_stat {0, 0, false};
+        CFileSystemInternalInfo internal_info(*info);
+        auto exists = info->stat(&internal_info, path.c_str(), &stat);
+        internal_info.Check();
+        return exists;
+    }
+
+    duckdb_file_stat Stat(const std::string &path) {
+        duckdb_file_stat stat;
+        if (!TryStat(path, stat)) {
+            throw duckdb::IOException("File \"%s\" does not exist", path);
+        }
+        return stat;
+    }
+
+    std::shared_ptr<CFileSystemInfo> info;
+};
+
+extern "C" {
+
+duckdb_file_system duckdb_create_file_system() {
+    return new std::shared_ptr<CFileSystemInfo>(std::make_shared<CFileSystemInfo>());
+}
+
+void duckdb_destroy_file_system(duckdb_file_system *file_system) {
+    if (file_system && *file_system) {
+        auto info = (std::shared_ptr<CFileSystemInfo> *) *file_system;
+        delete info;
+        *file_system = nullptr;
+    }
+}
+
+void duckdb_file_system_set_prefix(duckdb_file_system file_system, const char *prefix) {
+    if (!file_system || !prefix) {
+        return;
+    }
+    auto &info = *(std::shared_ptr<CFileSystemInfo> *) file_system;
+    info->prefix = prefix;
+}
+
+void duckdb_file_system_set_extra_info(duckdb_file_system file_system, void *extra_info,
+                                       duckdb_delete_callback_t destroy) {
+    if (!file_system) {
+        return;
+    }
+    auto &info = *(std::shared_ptr<CFileSystemInfo> *) file_system;
+    info->extra_info = extra_info;
+    info->delete_callback = destroy;
+}
+
+void duckdb_file_system_set_open(duckdb_file_system file_system, duckdb_file_system_open_t open) {
+    if (!file_system) {
+        return;
+    }
+    auto &info = *(std::shared_ptr<CFileSystemInfo> *) file_system;
+    info->open = open;
+}
+
+void duckdb_file_system_set_close(duckdb_file_system file_system, duckdb_file_system_close_t close) {
+    if (!file_system) {
+        return;
+    }
+    auto &info = *(std::shared_ptr<CFileSystemInfo> *) file_system;
+    info->close = close;
+}
+
+void duckdb_file_system_set_read(duckdb_file_system file_system, duckdb_file_system_read_t read) {
+    if (!file_system) {
+        return;
+    }
+    auto &info = *(std::shared_ptr<CFileSystemInfo> *) file_system;
+    info->read = read;
+}
+
+void duckdb_file_system_set_write(duckdb_file_system file_system, duckdb_file_system_write_t write) {
+    if (!file_system) {
+        return;
+    }
+    auto &info = *(std::shared_ptr<CFileSystemInfo> *) file_system;
+    info->write = write;
+}
+
+void duckdb_file_system_set_stat(duckdb_file_system file_system, duckdb_file_system_stat_t stat) {
+    if (!file_system) {
+        return;
+    }
+    auto &info = *(std::shared_ptr<CFileSystemInfo> *) file_system;
+    info->stat = stat;
+}
+
+void duckdb_file_system_set_glob(duckdb_file_system file_system, duckdb_file_system_glob_t glob) {
+    if (!file_system) {
+        return;
+    }
+    auto &info = *(std::shared_ptr<CFileSystemInfo> *) file_system;
+    info->glob = glob;
+}
+
+void duckdb_file_system_set_remove(duckdb_file_system file_system, duckdb_file_system_remove_t remove) {
+    if (!file_system) {
+        return;
+    }
+    auto &info = *(std::shared_ptr<CFileSystemInfo> *) file_system;
+    info->remove = remove;
+}
+
+duckdb_state duckdb_register_file_system(duckdb_database database, duckdb_file_system file_system) {
+    if (!database || !file_system) {
+        return SetRegistrationError("invalid database or file system");
+    }
+    auto db = (CDatabaseData *) database;
+    auto &info = *(std::shared_ptr<CFileSystemInfo> *) file_system;
+    if (info->prefix.empty() || !info->open || !info->read || !info->stat) {
+        return SetRegistrationError("file system needs a prefix and open, read and stat functions");
+    }
+    try {
+        auto &fs = db->database->instance->GetFileSystem();
+        fs.RegisterSubSystem(duckdb::make_unique<CFileSystem>(info));
+    } catch (std::exception &ex) {
+        return SetRegistrationError(ex.what());
+    }
+    return DuckDBSuccess;
+}
+
+void *duckdb_file_system_get_extra_info(duckdb_file_system_info info) {
+    if (!info) {
+        return nullptr;
+    }
+    auto internal_info = (CFileSystemInternalInfo *) info;
+    return internal_info->function_info.extra_info;
+}
+
+void duckdb_file_system_set_error(duckdb_file_system_info info, const char *error) {
+    if (!info || !error) {
+        return;
+    }
+    auto internal_info = (CFileSystemInternalInfo *) info;
+    internal_info->error = error;
+    internal_info->success = false;
+}
+
+void duckdb_glob_result_add(duckdb_glob_result result, const char *path) {
+    if (!result || !path) {
+        return;
+    }
+    auto paths = (std::vector<std::string> *) result;
+    paths->push_back(path);
+}
+
+}
+
+//===--------------------------------------------------------------------===//
+// Storage Extensions
+//===--------------------------------------------------------------------===//
+// An attached database is an in-memory catalog in which every table is a view over the extension's scan function,
+// called with the path of the attached database, the schema and the name of the table.
+struct CStorageExtensionInfo : public duckdb::StorageExtensionInfo {
+    ~CStorageExtensionInfo() override {
+        if (extra_info && delete_callback) {
+            delete_callback(extra_info);
+        }
+        extra_info = nullptr;
+        delete_callback = nullptr;
+    }
+
+    std::string scan_function;
+    duckdb_storage_attach_t attach = nullptr;
+    void *extra_info = nullptr;
+    duckdb_delete_callback_t delete_callback = nullptr;
+};
+
+struct CStorageExtension {
+    std::string name;
+    std::shared_ptr<CStorageExtensionInfo> info = std::make_shared<CStorageExtensionInfo>();
+};
+
+struct CStorageTable {
+    std::string schema;
+    std::string name;
+    std::vector<std::string> column_names;
+    std::vector<duckdb::LogicalType> column_types;
+};
+
+struct CStorageInternalInfo {
+    CStorageInternalInfo(CStorageExtensionInfo &extension_info, std::string path)
+            : extension_info(extension_info), path(std::move(path)) {
+    }
+
+    void Check() {
+        if (!success) {
+            throw duckdb::BinderException(error);
+        }
+    }
+
+    CStorageExtensionInfo &extension_info;
+    std::string path;
+    std::vector<std::string> schemas;
+    std::vector<CStorageTable> tables;
+    bool success = true;
+    std::string error;
+};
+
+class CStorageCatalog : public duckdb::DuckCatalog {
+public:
+    CStorageCatalog(duckdb::AttachedDatabase &db, std::string scan_function_p, std::string path_p,
+                    std::vector<std::string> schemas_p, std::vector<CStorageTable> tables_p)
+            : DuckCatalog(db), scan_function(std::move(scan_function_p)), path(std::move(path_p)),
+              schemas(std::move(schemas_p)), tables(std::move(tables_p)) {
+    }
+
+    void Initialize(bool load_builtin) override {
+        DuckCatalog::Initialize(load_builtin);
+
+        auto transaction = duckdb::CatalogTransaction::GetSystemTransaction(GetDatabase());
+        for (auto &schema : schemas) {
+            duckdb::CreateSchemaInfo info;
+            info.schema = schema;
+            info.on_conflict = duckdb::OnCreateConflict::IGNORE_ON_CONFLICT;
+            CreateSchema(transaction, &info);
+        }
+        for (auto &table : tables) {
+            auto sql = "SELECT * FROM " + duckdb::KeywordHelper::WriteOptionallyQuoted(scan_function) + "(" +
+                       duckdb::KeywordHelper::WriteQuoted(path) + ", " +
+                       duckdb::KeywordHelper::WriteQuoted(table.schema) + ", " +
+                       duckdb::KeywordHelper::WriteQuoted(table.name) + ")";
+            duckdb::Parser parser;
+            parser.ParseQuery(sql);
+
+            duckdb::CreateViewInfo info;
+            info.schema = table.schema;
+            info.view_name = table.name;
+            info.query = duckdb::unique_ptr_cast<duckdb::SQLStatement, duckdb::SelectStatement>(
+                    std::move(parser.statements[0]));
+            info.aliases = table.column_names;
+            info.types = table.column_types;
+            info.on_conflict = duckdb::OnCreateConflict::REPLACE_ON_CONFLICT;
+            CreateView(transaction, &info);
+        }
+    }
+
+private:
+    std::string scan_function;
+    std::string path;
+    std::vector<std::string> schemas;
+    std::vector<CStorageTable> tables;
+};
+
+static duckdb::unique_ptr<duckdb::Catalog> CStorageAttach(duckdb::StorageExtensionInfo *storage_info,
+                                                          duckdb::AttachedDatabase &db, const std::string &name,
+                                                          duckdb::AttachInfo &info, duckdb::AccessMode access_mode) {
+    auto &extension_info = (CStorageExtensionInfo &) *storage_info;
+    CStorageInternalInfo internal_info(extension_info, info.path);
+    extension_info.attach(&internal_info);
+    internal_info.Check();
+    return duckdb::make_unique<CStorageCatalog>(db, extension_info.scan_function, internal_info.path,
+                                                std::move(internal_info.schemas), std::move(internal_info.tables));
+}
+
+static duckdb::unique_ptr<duckdb::TransactionManager>
+CStorageCreateTransactionManager(duckdb::StorageExtensionInfo *storage_info, duckdb::AttachedDatabase &db,
+                                 duckdb::Catalog &catalog) {
+    return duckdb::make_unique<duckdb::DuckTransactionManager>(db);
+}
+
+extern "C" {
+
+duckdb_storage_extension duckdb_create_storage_extension() {
+    return new CStorageExtension();
+}
+
+void duckdb_destroy_storage_extension(duckdb_storage_extension *storage_extension) {
+    if (storage_extension && *storage_extension) {
+        auto extension = (CStorageExtension *) *storage_extension;
+        delete extension;
+        *storage_extension = nullptr;
+    }
+}
+
+void duckdb_storage_extension_set_name(duckdb_storage_extension storage_extension, const char *name) {
+    if (!storage_extension || !name) {
+        return;
+    }
+    auto extension = (CStorageExtension *) storage_extension;
+    extension->name = name;
+}
+
+void duckdb_storage_extension_set_scan_function(duckdb_storage_extension storage_extension,
+                                                const char *function_name) {
+    if (!storage_extension || !function_name) {
+        return;
+    }
+    auto extension = (CStorageExtension *) storage_extension;
+    extension->info->scan_function = function_name;
+}
+
+void duckdb_storage_extension_set_attach(duckdb_storage_extension storage_extension, duckdb_storage_attach_t attach) {
+    if (!storage_extension) {
+        return;
+    }
+    auto extension = (CStorageExtension *) storage_extension;
+    extension->info->attach = attach;
+}
+
+void duckdb_storage_extension_set_extra_info(duckdb_storage_extension storage_extension, void *extra_info,
+                                             duckdb_delete_callback_t destroy) {
+    if (!storage_extension) {
+        return;
+    }
+    auto extension = (CStorageExtension *) storage_extension;
+    extension->info->extra_info = extra_info;
+    extension->info->delete_callback = destroy;
+}
+
+duckdb_state duckdb_register_storage_extension(duckdb_database database, duckdb_storage_extension storage_extension) {
+    if (!database || !storage_extension) {
+        return SetRegistrationError("invalid database or storage extension");
+    }
+    auto db = (CDatabaseData *) database;
+    auto extension = (CStorageExtension *) storage_extension;
+    if (extension->name.empty() || extension->info->scan_function.empty() || !extension->info->attach) {
+        return SetRegistrationError("storage extension needs a name, a scan function and an attach function");
+    }
+    try {
+        auto &config = duckdb::DBConfig::GetConfig(*db->database->instance);
+        if (config.storage_extensions.find(extension->name) != config.storage_extensions.end()) {
+            return SetRegistrationError("a storage extension named \"" + extension->name + "\" already exists");
+        }
+        auto storage = duckdb::make_unique<duckdb::StorageExtension>();
+        storage->attach = CStorageAttach;
+        storage->create_transaction_manager = CStorageCreateTransactionManager;
+        storage->storage_info = extension->info;
+        config.storage_extensions[extension->name] = std::move(storage);
+    } catch (std::exception &ex) {
+        return SetRegistrationError(ex.what());
+    }
+    return DuckDBSuccess;
+}
+
+void duckdb_unregister_storage_extension(duckdb_database database, const char *name) {
+    if (!database || !name) {
+        return;
+    }
+    auto db = (CDatabaseData *) database;
+    auto &config = duckdb::DBConfig::GetConfig(*db->database->instance);
+    config.storage_extensions.erase(name);
+}
+
+void *duckdb_storage_get_extra_info(duckdb_storage_info info) {
+    if (!info) {
+        return nullptr;
+    }
+    auto internal_info = (CStorageInternalInfo *) info;
+    return internal_info->extension_info.extra_info;
+}
+
+const char *duckdb_storage_get_path(duckdb_storage_info info) {
+    if (!info) {
+        return nullptr;
+    }
+    auto internal_info = (CStorageInternalInfo *) info;
+    return internal_info->path.c_str();
+}
+
+void duckdb_storage_add_schema(duckdb_storage_info info, const char *schema) {
+    if (!info || !schema) {
+        return;
+    }
+    auto internal_info = (CStorageInternalInfo *) info;
+    internal_info->schemas.emplace_back(schema);
+}
+
+void duckdb_storage_add_table(duckdb_storage_info info, const char *schema, const char *table, idx_t column_count,
+                              const char **names, const duckdb_logical_type *types) {
+    if (!info || !schema || !table || (column_count > 0 && (!names || !types))) {
+        return;
+    }
+    auto internal_info = (CStorageInternalInfo *) info;
+    CStorageTable entry;
+    entry.schema = schema;
+    entry.name = table;
+    for (idx_t i = 0; i < column_count; i++) {
+        entry.column_names.emplace_back(names[i]);
+        entry.column_types.push_back(*(duckdb::LogicalType *) types[i]);
+    }
+    internal_info->tables.push_back(std::move(entry));
+}
+
+void duckdb_storage_set_error(duckdb_storage_info info, const char *error) {
+    if (!info || !error) {
+        return;
+    }
+    auto internal_info = (CStorageInternalInfo *) info;
+    internal_info->error = error;
+    internal_info->success = false;
+}
+
+}
+
+//===--------------------------------------------------------------------===//
+// Extension Options
+//===--------------------------------------------------------------------===//
+// Like pragma callbacks, the callbacks run when an option is set are plain function pointers, so every option with
+// a validator is assigned a slot in a registry kept by its database.
+static constexpr idx_t MAX_EXTENSION_OPTIONS = 64;
+
+struct CExtensionOptionInfo {
+    ~CExtensionOptionInfo() {
+        if (extra_info && delete_callback) {
+            delete_callback(extra_info);
+        }
+        extra_info = nullptr;
+        delete_callback = nullptr;
+    }
+
+    duckdb_extension_option_validate_t validate = nullptr;
+    void *extra_info = nullptr;
+    duckdb_delete_callback_t delete_callback = nullptr;
+};
+
+struct CExtensionOptionInternalInfo {
+    explicit CExtensionOptionInternalInfo(CExtensionOptionInfo &option_info) : option_info(option_info) {
+    }
+
+    CExtensionOptionInfo &option_info;
+    bool success = true;
+    std::string error;
+};
+
+struct CExtensionOptionRegistry : public duckdb::ObjectCacheEntry {
+    static std::string ObjectType() {
+        return "duckdb_extension_framework_extension_options";
+    }
+
+    std::string GetObjectType() override {
+        return ObjectType();
+    }
+
+    std::mutex lock;
+    std::vector<std::shared_ptr<CExtensionOptionInfo>> slots;
+};
+
+template <idx_t SLOT>
+static void ExtensionOptionTrampoline(duckdb::ClientContext &context, duckdb::SetScope scope,
+                                      duckdb::Value &parameter) {
+    auto registry = GetRegistry<CExtensionOptionRegistry>(duckdb::DatabaseInstance::GetDatabase(context));
+    std::shared_ptr<CExtensionOptionInfo> option_info;
+    {
+        std::lock_guard<std::mutex> guard(registry->lock);
+        if (SLOT < registry->slots.size()) {
+            option_info = registry->slots[SLOT];
+        }
+    }
+    if (!option_info) {
+        throw duckdb::InternalException("extension option is not registered with this database");
+    }
+    CExtensionOptionInternalInfo info(*option_info);
+    option_info->validate(&info, new duckdb::Value(parameter));
+    if (!info.success) {
+        throw duckdb::InvalidInputException(info.error);
+    }
+}
+
+template <std::size_t... SLOTS>
+static std::array<duckdb::set_option_callback_t, sizeof...(SLOTS)>
+MakeExtensionOptionTrampolines(std::index_sequence<SLOTS...>) {
+    return {{&ExtensionOptionTrampoline<SLOTS>...}};
+}
+
+static const auto extension_option_trampolines =
+        MakeExtensionOptionTrampolines(std::make_index_sequence<MAX_EXTENSION_OPTIONS>());
+
+// The C API's internal bind info isn't exposed, but starts with a reference to the client context running the bind.
+// This mirrors the leading member of CTableInternalBindInfo.
+struct CTableBindInfoPrefix {
+    duckdb::ClientContext &context;
+};
+
+static duckdb_value GetCurrentSetting(duckdb::ClientContext &context, const char *name) {
+    try {
+        duckdb::Value result;
+        if (!context.TryGetCurrentSetting(name, result)) {
+            return nullptr;
+        }
+        return new duckdb::Value(result);
+    } catch (std::exception &ex) {
+        return nullptr;
+    }
+}
+
+extern "C" {
+
+duckdb_state duckdb_add_extension_option(duckdb_database database, const char *name, const char *description,
+                                         duckdb_logical_type type, duckdb_value default_value,
+                                         duckdb_extension_option_validate_t validate, void *extra_info,
+                                         duckdb_delete_callback_t destroy) {
+    auto option_info = std::make_shared<CExtensionOptionInfo>();
+    option_info->extra_info = extra_info;
+    option_info->delete_callback = destroy;
+    if (!database || !name || !type) {
+        return SetRegistrationError("invalid database, name or type");
+    }
+    auto db = (CDatabaseData *) database;
+    auto &logical_type = *(duckdb::LogicalType *) type;
+    option_info->validate = validate;
+    try {
+        auto &instance = *db->database->instance;
+        auto &config = duckdb::DBConfig::GetConfig(instance);
+        duckdb::Value value;
+        if (default_value) {
+            value = ((duckdb::Value *) default_value)->DefaultCastAs(logical_type);
+        }
+
+        auto registry = GetRegistry<CExtensionOptionRegistry>(instance);
+        std::lock_guard<std::mutex> guard(registry->lock);
+        duckdb::set_option_callback_t callback = nullptr;
+        if (validate) {
+            if (registry->slots.size() >= MAX_EXTENSION_OPTIONS) {
+                return SetRegistrationError("too many extension options with a validator in this database");
+            }
+            callback = extension_option_trampolines[registry->slots.size()];
+        }
+        config.AddExtensionOption(name, description ? description : "", logical_type, callback);
+        if (validate) {
+            registry->slots.push_back(option_info);
+        }
+        // The default is the option's global value, which every connection reads until it sets its own
+        if (default_value) {
+            config.SetOption(name, std::move(value));
+        }
+    } catch (std::exception &ex) {
+        return SetRegistrationError(ex.what());
+    }
+    return DuckDBSuccess;
+}
+
+void *duckdb_extension_option_get_extra_info(duckdb_extension_option_info info) {
+    if (!info) {
+        return nullptr;
+    }
+    auto internal_info = (CExtensionOptionInternalInfo *) info;
+    return internal_info->option_info.extra_info;
+}
+
+void duckdb_extension_option_set_error(duckdb_extension_option_info info, const char *error) {
+    if (!info || !error) {
+        return;
+    }
+    auto internal_info = (CExtensionOptionInternalInfo *) info;
+    internal_info->error = error;
+    internal_info->success = false;
+}
+
+duckdb_value duckdb_get_extension_option(duckdb_connection connection, const char *name) {
+    if (!connection || !name) {
+        return nullptr;
+    }
+    auto con = (duckdb::Connection *) connection;
+    return GetCurrentSetting(*con->context, name);
+}
+
+duckdb_value duckdb_bind_get_extension_option(duckdb_bind_info info, const char *name) {
+    if (!info || !name) {
+        return nullptr;
+    }
+    auto bind_info = (CTableBindInfoPrefix *) info;
+    return GetCurrentSetting(bind_info->context, name);
+}
+
+}
+
+//===--------------------------------------------------------------------===//
+// Collations
+//===--------------------------------------------------------------------===//
+// A collation is a scalar function from a VARCHAR to its sort key, which DuckDB applies before comparing values.
+struct CCollationInfo {
+    ~CCollationInfo() {
+        if (extra_info && delete_callback) {
+            delete_callback(extra_info);
+        }
+        extra_info = nullptr;
+        delete_callback = nullptr;
+    }
+
+    duckdb_collation_function_t function = nullptr;
+    void *extra_info = nullptr;
+    duckdb_delete_callback_t delete_callback = nullptr;
+};
+
+struct CCollationInternalInfo {
+    explicit CCollationInternalInfo(CCollationInfo &collation_info) : collation_info(collation_info) {
+    }
+
+    CCollationInfo &collation_info;
+    bool success = true;
+    std::string error;
+};
+
+extern "C" {
+
+duckdb_state duckdb_register_collation(duckdb_connection connection, const char *name,
+                                       duckdb_collation_function_t function, void *extra_info,
+                                       duckdb_delete_callback_t destroy) {
+    auto info = std::make_shared<CCollationInfo>();
+    info->extra_info = extra_info;
+    info->delete_callback = destroy;
+    if (!connection || !name || !function) {
+        return SetRegistrationError("invalid connection, name or function");
+    }
+    auto con = (duckdb::Connection *) connection;
+    info->function = function;
+
+    duckdb::ScalarFunction sort_key(
+            name, {duckdb::LogicalType::VARCHAR}, duckdb::LogicalType::VARCHAR,
+            [info](duckdb::DataChunk &args, duckdb::ExpressionState &state, duckdb::Vector &result) {
+                args.Flatten();
+                result.SetVectorType(duckdb::VectorType::FLAT_VECTOR);
+                duckdb::FlatVector::SetValidity(result, duckdb::FlatVector::Validity(args.data[0]));
+
+                CCollationInternalInfo internal_info(*info);
+                info->function(&internal_info, &args.data[0], &result, args.size());
+                if (!internal_info.success) {
+                    throw duckdb::InvalidInputException(internal_info.error);
+                }
+            });
+    try {
+        con->context->RunFunctionInTransaction([&]() {
+            auto &catalog = duckdb::Catalog::GetSystemCatalog(*con->context);
+            duckdb::CreateCollationInfo collation_info(name, sort_key, false, false);
+            catalog.CreateCollation(*con->context, &collation_info);
+        });
+    } catch (std::exception &ex) {
+        return SetRegistrationError(ex.what());
+    }
+    return DuckDBSuccess;
+}
+
+void *duckdb_collation_get_extra_info(duckdb_collation_info info) {
+    if (!info) {
+        return nullptr;
+    }
+    auto internal_info = (CCollationInternalInfo *) info;
+    return internal_info->collation_info.extra_info;
+}
+
+void duckdb_collation_set_error(duckdb_collation_info info, const char *error) {
+    if (!info || !error) {
+        return;
+    }
+    auto internal_info = (CCollationInternalInfo *) info;
+    internal_info->error = error;
+    internal_info->success = false;
+}
+
+}
+
+//===--------------------------------------------------------------------===//
+// Extension Entry Points
+//===--------------------------------------------------------------------===//
+extern "C" {
+
+void duckdb_extension_load(void *instance, duckdb_extension_init_t init, void *state) {
+    // The exception must not unwind through the extension's Rust code, so it's only thrown once init has returned
+    auto error = init(instance, state);
+    if (!error) {
+        return;
+    }
+    std::string message = error;
+    duckdb_free(error);
+    throw duckdb::IOException("Failed to load extension: %s", message);
+}
+
+duckdb_database duckdb_database_from_instance(void *instance) {
+    if (!instance) {
+        return nullptr;
+    }
+    try {
+        auto wrapper = duckdb::make_unique<CDatabaseData>();
+        wrapper->database = duckdb::make_unique<duckdb::DuckDB>(*(duckdb::DatabaseInstance *) instance);
+        return wrapper.release();
+    } catch (std::exception &ex) {
+        return nullptr;
+    }
+}
+
+duckdb_state duckdb_extension_connect(duckdb_database database, duckdb_connection *out, char **out_error) {
+    if (!database || !out) {
+        if (out_error) {
+            *out_error = strdup("no database to connect to");
+        }
+        return DuckDBError;
+    }
+    try {
+        auto db = (CDatabaseData *) database;
+        *out = (duckdb_connection) new duckdb::Connection(*db->database);
+        return DuckDBSuccess;
+    } catch (std::exception &ex) {
+        if (out_error) {
+            *out_error = strdup(ex.what());
+        }
+        return DuckDBError;
+    }
+}
+
+void duckdb_destroy_database_ref(duckdb_database *database) {
+    if (database && *database) {
+        // Only releases this reference, the database stays open for as long as DuckDB holds its own
+        delete (CDatabaseData *) *database;
+        *database = nullptr;
+    }
+}
+
+}
@@ -5,4 +5,201 @@ extern "C" {
 DUCKDB_EXTENSION_API duckdb_logical_type duckdb_create_union(idx_t nmembers, const char** names, const duckdb_logical_type* types);
 
 DUCKDB_EXTENSION_API duckdb_logical_type duckdb_create_struct_type(idx_t n_pairs, const char** names, const duckdb_logical_type* types);
+
+// Creates an enum type with the given dictionary values, in order, or returns NULL if they aren't valid
+DUCKDB_EXTENSION_API duckdb_logical_type duckdb_create_enum_type(const char** member_names, idx_t member_count);
+
+// The ids later versions of the C API give types that this one reports as TIME and TIMESTAMP
+#define DUCKDB_EXTENSION_TYPE_TIME_TZ 30
+#define DUCKDB_EXTENSION_TYPE_TIMESTAMP_TZ 31
+
+// Creates a type like duckdb_create_logical_type, which also accepts BIT and the ids above
+DUCKDB_EXTENSION_API duckdb_logical_type duckdb_extension_create_logical_type(duckdb_type type);
+// Returns the id of a type like duckdb_get_type_id, giving the types with time zones the ids above
+DUCKDB_EXTENSION_API duckdb_type duckdb_extension_get_type_id(duckdb_logical_type type);
+
+// Returns a deep copy of a type, including its children, alias and other metadata
+DUCKDB_EXTENSION_API duckdb_logical_type duckdb_copy_logical_type(duckdb_logical_type type);
+// Returns whether two types are identical, including their children, alias and other metadata
+DUCKDB_EXTENSION_API bool duckdb_logical_type_equals(duckdb_logical_type left, duckdb_logical_type right);
+
+// Returns the number of members of a union type, or 0 if the type isn't a union
+DUCKDB_EXTENSION_API idx_t duckdb_union_type_member_count(duckdb_logical_type type);
+// Returns the name of a member of a union type, which must be freed with duckdb_free, or NULL if there is no such member
+DUCKDB_EXTENSION_API char *duckdb_union_type_member_name(duckdb_logical_type type, idx_t index);
+// Returns the type of a member of a union type, which must be destroyed with duckdb_destroy_logical_type, or NULL if
+// there is no such member
+DUCKDB_EXTENSION_API duckdb_logical_type duckdb_union_type_member_type(duckdb_logical_type type, idx_t index);
+
+// Returns why the last registration function in this file failed on the calling thread, or NULL
+DUCKDB_EXTENSION_API const char *duckdb_extension_last_error();
+
+//===--------------------------------------------------------------------===//
+// Table Functions
+//===--------------------------------------------------------------------===//
+// Registers a table function like duckdb_register_table_function, recording why it failed for duckdb_extension_last_error
+DUCKDB_EXTENSION_API duckdb_state duckdb_extension_register_table_function(duckdb_connection connection, duckdb_table_function function);
+
+//===--------------------------------------------------------------------===//
+// Pragma Functions
+//===--------------------------------------------------------------------===//
+typedef void *duckdb_pragma_function;
+typedef void *duckdb_pragma_info;
+
+typedef void (*duckdb_pragma_function_t)(duckdb_pragma_info info);
+
+DUCKDB_EXTENSION_API duckdb_pragma_function duckdb_create_pragma_function(bool is_statement);
+DUCKDB_EXTENSION_API void duckdb_destroy_pragma_function(duckdb_pragma_function *pragma_function);
+DUCKDB_EXTENSION_API void duckdb_pragma_function_set_name(duckdb_pragma_function pragma_function, const char *name);
+DUCKDB_EXTENSION_API void duckdb_pragma_function_add_parameter(duckdb_pragma_function pragma_function, duckdb_logical_type type);
+DUCKDB_EXTENSION_API void duckdb_pragma_function_set_function(duckdb_pragma_function pragma_function, duckdb_pragma_function_t function);
+DUCKDB_EXTENSION_API void duckdb_pragma_function_set_query(duckdb_pragma_function pragma_function, duckdb_pragma_function_t query);
+DUCKDB_EXTENSION_API void duckdb_pragma_function_set_extra_info(duckdb_pragma_function pragma_function, void *extra_info, duckdb_delete_callback_t destroy);
+DUCKDB_EXTENSION_API duckdb_state duckdb_register_pragma_function(duckdb_connection con, duckdb_pragma_function pragma_function);
+
+DUCKDB_EXTENSION_API void *duckdb_pragma_get_extra_info(duckdb_pragma_info info);
+DUCKDB_EXTENSION_API idx_t duckdb_pragma_get_parameter_count(duckdb_pragma_info info);
+DUCKDB_EXTENSION_API duckdb_value duckdb_pragma_get_parameter(duckdb_pragma_info info, idx_t index);
+DUCKDB_EXTENSION_API void duckdb_pragma_set_query(duckdb_pragma_info info, const char *query);
+DUCKDB_EXTENSION_API void duckdb_pragma_set_error(duckdb_pragma_info info, const char *error);
+
+//===--------------------------------------------------------------------===//
+// Copy Functions
+//===--------------------------------------------------------------------===//
+typedef void *duckdb_copy_function;
+typedef void *duckdb_copy_info;
+
+typedef void (*duckdb_copy_function_t)(duckdb_copy_info info);
+typedef void (*duckdb_copy_sink_t)(duckdb_copy_info info, duckdb_data_chunk input);
+
+DUCKDB_EXTENSION_API duckdb_copy_function duckdb_create_copy_function();
+DUCKDB_EXTENSION_API void duckdb_destroy_copy_function(duckdb_copy_function *copy_function);
+DUCKDB_EXTENSION_API void duckdb_copy_function_set_name(duckdb_copy_function copy_function, const char *name);
+DUCKDB_EXTENSION_API void duckdb_copy_function_set_extra_info(duckdb_copy_function copy_function, void *extra_info, duckdb_delete_callback_t destroy);
+DUCKDB_EXTENSION_API void duckdb_copy_function_set_bind(duckdb_copy_function copy_function, duckdb_copy_function_t bind);
+DUCKDB_EXTENSION_API void duckdb_copy_function_set_init_global(duckdb_copy_function copy_function, duckdb_copy_function_t init);
+DUCKDB_EXTENSION_API void duckdb_copy_function_set_init_local(duckdb_copy_function copy_function, duckdb_copy_function_t init);
+DUCKDB_EXTENSION_API void duckdb_copy_function_set_sink(duckdb_copy_function copy_function, duckdb_copy_sink_t sink);
+DUCKDB_EXTENSION_API void duckdb_copy_function_set_combine(duckdb_copy_function copy_function, duckdb_copy_function_t combine);
+DUCKDB_EXTENSION_API void duckdb_copy_function_set_finalize(duckdb_copy_function copy_function, duckdb_copy_function_t finalize);
+DUCKDB_EXTENSION_API duckdb_state duckdb_register_copy_function(duckdb_connection con, duckdb_copy_function copy_function);
+
+DUCKDB_EXTENSION_API void *duckdb_copy_get_extra_info(duckdb_copy_info info);
+DUCKDB_EXTENSION_API idx_t duckdb_copy_get_option_count(duckdb_copy_info info);
+DUCKDB_EXTENSION_API const char *duckdb_copy_get_option_name(duckdb_copy_info info, idx_t index);
+DUCKDB_EXTENSION_API idx_t duckdb_copy_get_option_value_count(duckdb_copy_info info, idx_t index);
+DUCKDB_EXTENSION_API duckdb_value duckdb_copy_get_option_value(duckdb_copy_info info, idx_t index, idx_t value_index);
+DUCKDB_EXTENSION_API idx_t duckdb_copy_get_column_count(duckdb_copy_info info);
+DUCKDB_EXTENSION_API const char *duckdb_copy_get_column_name(duckdb_copy_info info, idx_t index);
+DUCKDB_EXTENSION_API duckdb_logical_type duckdb_copy_get_column_type(duckdb_copy_info info, idx_t index);
+DUCKDB_EXTENSION_API const char *duckdb_copy_get_file_path(duckdb_copy_info info);
+DUCKDB_EXTENSION_API void duckdb_copy_set_bind_data(duckdb_copy_info info, void *bind_data, duckdb_delete_callback_t destroy);
+DUCKDB_EXTENSION_API void *duckdb_copy_get_bind_data(duckdb_copy_info info);
+DUCKDB_EXTENSION_API void duckdb_copy_set_global_state(duckdb_copy_info info, void *global_state, duckdb_delete_callback_t destroy);
+DUCKDB_EXTENSION_API void *duckdb_copy_get_global_state(duckdb_copy_info info);
+DUCKDB_EXTENSION_API void duckdb_copy_set_local_state(duckdb_copy_info info, void *local_state, duckdb_delete_callback_t destroy);
+DUCKDB_EXTENSION_API void *duckdb_copy_get_local_state(duckdb_copy_info info);
+DUCKDB_EXTENSION_API void duckdb_copy_set_error(duckdb_copy_info info, const char *error);
+
+//===--------------------------------------------------------------------===//
+// File Systems
+//===--------------------------------------------------------------------===//
+typedef void *duckdb_file_system;
+typedef void *duckdb_file_system_info;
+typedef void *duckdb_glob_result;
+
+typedef struct {
+	idx_t size;
+	int64_t last_modified;
+	bool is_directory;
+} duckdb_file_stat;
+
+typedef void *(*duckdb_file_system_open_t)(duckdb_file_system_info info, const char *path, uint8_t flags);
+typedef void (*duckdb_file_system_close_t)(duckdb_file_system_info info, void *handle);
+typedef int64_t (*duckdb_file_system_read_t)(duckdb_file_system_info info, void *handle, void *buffer, int64_t nr_bytes, idx_t location);
+typedef int64_t (*duckdb_file_system_write_t)(duckdb_file_system_info info, void *handle, const void *buffer, int64_t nr_bytes, idx_t location);
+typedef bool (*duckdb_file_system_stat_t)(duckdb_file_system_info info, const char *path, duckdb_file_stat *out_stat);
+typedef void (*duckdb_file_system_glob_t)(duckdb_file_system_info info, const char *pattern, duckdb_glob_result result);
+typedef void (*duckdb_file_system_remove_t)(duckdb_file_system_info info, const char *path);
+
+DUCKDB_EXTENSION_API duckdb_file_system duckdb_create_file_system();
+DUCKDB_EXTENSION_API void duckdb_destroy_file_system(duckdb_file_system *file_system);
+DUCKDB_EXTENSION_API void duckdb_file_system_set_prefix(duckdb_file_system file_system, const char *prefix);
+DUCKDB_EXTENSION_API void duckdb_file_system_set_extra_info(duckdb_file_system file_system, void *extra_info, duckdb_delete_callback_t destroy);
+DUCKDB_EXTENSION_API void duckdb_file_system_set_open(duckdb_file_system file_system, duckdb_file_system_open_t open);
+DUCKDB_EXTENSION_API void duckdb_file_system_set_close(duckdb_file_system file_system, duckdb_file_system_close_t close);
+DUCKDB_EXTENSION_API void duckdb_file_system_set_read(duckdb_file_system file_system, duckdb_file_system_read_t read);
+DUCKDB_EXTENSION_API void duckdb_file_system_set_write(duckdb_file_system file_system, duckdb_file_system_write_t write);
+DUCKDB_EXTENSION_API void duckdb_file_system_set_stat(duckdb_file_system file_system, duckdb_file_system_stat_t stat);
+DUCKDB_EXTENSION_API void duckdb_file_system_set_glob(duckdb_file_system file_system, duckdb_file_system_glob_t glob);
+DUCKDB_EXTENSION_API void duckdb_file_system_set_remove(duckdb_file_system file_system, duckdb_file_system_remove_t remove);
+DUCKDB_EXTENSION_API duckdb_state duckdb_register_file_system(duckdb_database database, duckdb_file_system file_system);
+
+DUCKDB_EXTENSION_API void *duckdb_file_system_get_extra_info(duckdb_file_system_info info);
+DUCKDB_EXTENSION_API void duckdb_file_system_set_error(duckdb_file_system_info info, const char *error);
+DUCKDB_EXTENSION_API void duckdb_glob_result_add(duckdb_glob_result result, const char *path);
+
+//===--------------------------------------------------------------------===//
+// Storage Extensions
+//===--------------------------------------------------------------------===//
+typedef void *duckdb_storage_extension;
+typedef void *duckdb_storage_info;
+
+typedef void (*duckdb_storage_attach_t)(duckdb_storage_info info);
+
+DUCKDB_EXTENSION_API duckdb_storage_extension duckdb_create_storage_extension();
+DUCKDB_EXTENSION_API void duckdb_destroy_storage_extension(duckdb_storage_extension *storage_extension);
+DUCKDB_EXTENSION_API void duckdb_storage_extension_set_name(duckdb_storage_extension storage_extension, const char *name);
+DUCKDB_EXTENSION_API void duckdb_storage_extension_set_scan_function(duckdb_storage_extension storage_extension, const char *function_name);
+DUCKDB_EXTENSION_API void duckdb_storage_extension_set_attach(duckdb_storage_extension storage_extension, duckdb_storage_attach_t attach);
+DUCKDB_EXTENSION_API void duckdb_storage_extension_set_extra_info(duckdb_storage_extension storage_extension, void *extra_info, duckdb_delete_callback_t destroy);
+DUCKDB_EXTENSION_API duckdb_state duckdb_register_storage_extension(duckdb_database database, duckdb_storage_extension storage_extension);
+DUCKDB_EXTENSION_API void duckdb_unregister_storage_extension(duckdb_database database, const char *name);
+
+DUCKDB_EXTENSION_API void *duckdb_storage_get_extra_info(duckdb_storage_info info);
+DUCKDB_EXTENSION_API const char *duckdb_storage_get_path(duckdb_storage_info info);
+DUCKDB_EXTENSION_API void duckdb_storage_add_schema(duckdb_storage_info info, const char *schema);
+DUCKDB_EXTENSION_API void duckdb_storage_add_table(duckdb_storage_info info, const char *schema, const char *table, idx_t column_count, const char **names, const duckdb_logical_type *types);
+DUCKDB_EXTENSION_API void duckdb_storage_set_error(duckdb_storage_info info, const char *error);
+
+//===--------------------------------------------------------------------===//
+// Extension Options
+//===--------------------------------------------------------------------===//
+typedef void *duckdb_extension_option_info;
+
+typedef void (*duckdb_extension_option_validate_t)(duckdb_extension_option_info info, duckdb_value value);
+
+DUCKDB_EXTENSION_API duckdb_state duckdb_add_extension_option(duckdb_database database, const char *name, const char *description, duckdb_logical_type type, duckdb_value default_value, duckdb_extension_option_validate_t validate, void *extra_info, duckdb_delete_callback_t destroy);
+DUCKDB_EXTENSION_API void *duckdb_extension_option_get_extra_info(duckdb_extension_option_info info);
+DUCKDB_EXTENSION_API void duckdb_extension_option_set_error(duckdb_extension_option_info info, const char *error);
+DUCKDB_EXTENSION_API duckdb_value duckdb_get_extension_option(duckdb_connection connection, const char *name);
+DUCKDB_EXTENSION_API duckdb_value duckdb_bind_get_extension_option(duckdb_bind_info info, const char *name);
+
+//===--------------------------------------------------------------------===//
+// Collations
+//===--------------------------------------------------------------------===//
+typedef void *duckdb_collation_info;
+
+typedef void (*duckdb_collation_function_t)(duckdb_collation_info info, duckdb_vector input, duckdb_vector output, idx_t count);
+
+DUCKDB_EXTENSION_API duckdb_state duckdb_register_collation(duckdb_connection con, const char *name, duckdb_collation_function_t function, void *extra_info, duckdb_delete_callback_t destroy);
+DUCKDB_EXTENSION_API void *duckdb_collation_get_extra_info(duckdb_collation_info info);
+DUCKDB_EXTENSION_API void duckdb_collation_set_error(duckdb_collation_info info, const char *error);
+
+//===--------------------------------------------------------------------===//
+// Extension Entry Points
+//===--------------------------------------------------------------------===//
+// Loads the extension, returning an error allocated with duckdb_malloc or nullptr
+typedef char *(*duckdb_extension_init_t)(void *instance, void *state);
+
+// Calls init with the database instance an extension's init function is called with. Once init has returned, an error
+// is freed and thrown as the error of the LOAD statement.
+DUCKDB_EXTENSION_API void duckdb_extension_load(void *instance, duckdb_extension_init_t init, void *state);
+// Wraps the database instance an extension's init function is called with in a duckdb_database, for use with the
+// rest of the API. Must be destroyed with duckdb_destroy_database_ref, which leaves the database itself open.
+DUCKDB_EXTENSION_API duckdb_database duckdb_database_from_instance(void *instance);
+DUCKDB_EXTENSION_API void duckdb_destroy_database_ref(duckdb_database *database);
+// Like duckdb_connect, but on failure sets out_error to why, which must be freed with duckdb_free
+DUCKDB_EXTENSION_API duckdb_state duckdb_extension_connect(duckdb_database database, duckdb_connection *out,
+                                                           char **out_error);
 };
@@ -0,0 +1,6 @@
+//! Checks that misuses the API's lifetimes are meant to rule out don't compile
+
+#[test]
+fn test_compile_fail() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,8 @@
+use duckdb_extension_framework::Database;
+
+fn main() {
+    let database = Database::new().unwrap();
+    let connection = database.connect().unwrap();
+    drop(database);
+    drop(connection);
+}